@@ -0,0 +1,123 @@
+use serde::Deserialize;
+
+/// A Lua module implemented by a C binding.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct BindingModule {
+    /// Module name as passed to `require`, e.g. `mylib.core`.
+    pub name: String,
+    pub description: Option<String>,
+    /// Functions of the module table.
+    pub functions: Vec<BindingFunction>,
+    /// Classes (usually userdata types) exposed by the module.
+    pub classes: Vec<BindingClass>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct BindingClass {
+    pub name: String,
+    pub parent: Option<String>,
+    pub description: Option<String>,
+    pub fields: Vec<BindingField>,
+    /// Methods are emitted with `:` unless marked `static`.
+    pub methods: Vec<BindingFunction>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct BindingField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub typ: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct BindingFunction {
+    pub name: String,
+    pub description: Option<String>,
+    pub params: Vec<BindingParam>,
+    pub returns: Vec<BindingReturn>,
+    /// Extra signatures, e.g. `fun(fd: integer): mylib.File`.
+    pub overloads: Vec<String>,
+    /// Emit a class method with `.` instead of `:`.
+    #[serde(rename = "static")]
+    pub is_static: bool,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct BindingParam {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub typ: Option<String>,
+    pub description: Option<String>,
+    pub optional: bool,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct BindingReturn {
+    #[serde(rename = "type")]
+    pub typ: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+impl BindingModule {
+    /// Load a JSON binding manifest.
+    pub fn from_json_str(text: &str) -> Result<Self, String> {
+        serde_json::from_str(text).map_err(|e| format!("Failed to parse JSON manifest: {}", e))
+    }
+
+    /// Load a TOML binding manifest.
+    pub fn from_toml_str(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| format!("Failed to parse TOML manifest: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_and_toml_manifest() {
+        let json = r#"{
+            "name": "mylib",
+            "functions": [
+                {
+                    "name": "open",
+                    "params": [{ "name": "path", "type": "string" }],
+                    "returns": [{ "type": "mylib.File" }],
+                    "overloads": ["fun(fd: integer): mylib.File"]
+                }
+            ],
+            "classes": [
+                { "name": "mylib.File", "methods": [{ "name": "close" }] }
+            ]
+        }"#;
+        let toml = r#"
+name = "mylib"
+
+[[functions]]
+name = "open"
+params = [{ name = "path", type = "string" }]
+returns = [{ type = "mylib.File" }]
+overloads = ["fun(fd: integer): mylib.File"]
+
+[[classes]]
+name = "mylib.File"
+methods = [{ name = "close" }]
+"#;
+        let from_json = BindingModule::from_json_str(json).unwrap();
+        let from_toml = BindingModule::from_toml_str(toml).unwrap();
+        assert_eq!(from_json, from_toml);
+        assert_eq!(
+            from_json.functions[0].params[0].typ.as_deref(),
+            Some("string")
+        );
+        assert_eq!(from_json.classes[0].methods[0].name, "close");
+    }
+}
@@ -0,0 +1,552 @@
+use std::collections::HashMap;
+
+use crate::binding::{
+    BindingClass, BindingField, BindingFunction, BindingModule, BindingParam, BindingReturn,
+};
+
+/// A top-level declaration of a C source file.
+#[derive(Debug)]
+enum CItem {
+    /// `int name(params);` or `int name(params) { ... }`
+    Function { header: String, doc: Vec<String> },
+    /// `static const luaL_Reg name[] = { ... };`
+    RegTable {
+        header: String,
+        body: String,
+        doc: Vec<String>,
+    },
+}
+
+#[derive(Debug)]
+struct CFunction {
+    return_type: String,
+    params: Vec<(String, String)>,
+    doc: Vec<String>,
+}
+
+impl BindingModule {
+    /// Build a binding module from a C source or header.
+    ///
+    /// Every `luaL_Reg` table is read as a list of exported functions. A table is a class when
+    /// the comment above it contains `@class Name [: Parent]`, and otherwise adds functions to
+    /// the module. The comments above the C functions describe them with `@param`, `@return`
+    /// and `@overload`. Without `@param` tags, the parameters come from the C prototype unless
+    /// it is a `lua_CFunction`.
+    ///
+    /// The module name is `module_name`, or derived from the `luaopen_*` function.
+    pub fn from_c_source(source: &str, module_name: Option<&str>) -> Self {
+        let items = split_c_items(source);
+        let mut functions = HashMap::new();
+        let mut tables = Vec::new();
+        for item in items {
+            match item {
+                CItem::Function { header, doc } => {
+                    if let Some((name, function)) = parse_c_function(&header, doc) {
+                        functions.insert(name, function);
+                    }
+                }
+                CItem::RegTable { header, body, doc } => tables.push((header, body, doc)),
+            }
+        }
+
+        let luaopen = functions
+            .iter()
+            .filter_map(|(name, function)| Some((name.strip_prefix("luaopen_")?, function)))
+            .min_by_key(|(name, _)| name.len());
+        let mut module = BindingModule {
+            name: match (module_name, &luaopen) {
+                (Some(name), _) => name.to_string(),
+                (None, Some((name, _))) => name.replace('_', "."),
+                (None, None) => "module".to_string(),
+            },
+            description: luaopen.and_then(|(_, function)| get_doc_description(&function.doc)),
+            ..Default::default()
+        };
+
+        for (_, body, doc) in tables {
+            let entries = parse_reg_entries(&body)
+                .into_iter()
+                .filter(|(lua_name, _)| !lua_name.starts_with("__"))
+                .map(|(lua_name, c_name)| build_function(lua_name, functions.get(&c_name)))
+                .collect::<Vec<_>>();
+            match get_doc_class(&doc) {
+                Some((name, parent)) => {
+                    let class = match module.classes.iter_mut().position(|c| c.name == name) {
+                        Some(index) => &mut module.classes[index],
+                        None => {
+                            module.classes.push(BindingClass {
+                                name,
+                                ..Default::default()
+                            });
+                            module.classes.last_mut().expect("class was just pushed")
+                        }
+                    };
+                    if parent.is_some() {
+                        class.parent = parent;
+                    }
+                    if class.description.is_none() {
+                        class.description = get_doc_description(&doc);
+                    }
+                    class.fields.extend(get_doc_fields(&doc));
+                    class.methods.extend(entries);
+                }
+                None => module.functions.extend(entries),
+            }
+        }
+
+        module
+    }
+}
+
+/// Split a C file into top-level function declarations and `luaL_Reg` tables, together with
+/// the `///` or `/** */` comments right above them.
+fn split_c_items(source: &str) -> Vec<CItem> {
+    let chars = source.chars().collect::<Vec<_>>();
+    let mut items = Vec::new();
+    let mut doc: Vec<String> = Vec::new();
+    let mut statement = String::new();
+    let mut i = 0;
+    let mut at_line_start = true;
+    while i < chars.len() {
+        let ch = chars[i];
+        let next = chars.get(i + 1).copied();
+        match ch {
+            '#' if at_line_start => {
+                // 预处理指令, 跳过续行
+                while i < chars.len() && !(chars[i] == '\n' && chars[i - 1] != '\\') {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if next == Some('/') => {
+                let end = find_from(&chars, i, "\n").unwrap_or(chars.len());
+                let line = chars[i..end].iter().collect::<String>();
+                if let Some(text) = line.strip_prefix("///") {
+                    doc.push(
+                        text.strip_prefix(' ')
+                            .unwrap_or(text)
+                            .trim_end()
+                            .to_string(),
+                    );
+                }
+                i = end;
+                continue;
+            }
+            '/' if next == Some('*') => {
+                let end = find_from(&chars, i + 2, "*/").map_or(chars.len(), |end| end + 2);
+                let block = chars[i..end].iter().collect::<String>();
+                if let Some(text) = block.strip_prefix("/**") {
+                    let text = text.strip_suffix("*/").unwrap_or(text);
+                    doc.extend(
+                        text.lines()
+                            .map(|line| line.trim().trim_start_matches('*').trim().to_string())
+                            .skip_while(|line| line.is_empty()),
+                    );
+                    while doc.last().is_some_and(|line| line.is_empty()) {
+                        doc.pop();
+                    }
+                }
+                i = end;
+                continue;
+            }
+            '"' | '\'' => {
+                let end = find_literal_end(&chars, i);
+                statement.extend(&chars[i..end]);
+                i = end;
+                at_line_start = false;
+                continue;
+            }
+            '{' => {
+                let header = statement.trim().to_string();
+                if header.ends_with("extern \"C\"") {
+                    // extern "C" { ... } 内部仍是顶层声明
+                    statement.clear();
+                    i += 1;
+                    continue;
+                }
+                let end = find_block_end(&chars, i);
+                if header.ends_with('=') {
+                    if header.contains("luaL_Reg") {
+                        items.push(CItem::RegTable {
+                            header,
+                            body: chars[i + 1..end.saturating_sub(1)].iter().collect(),
+                            doc: std::mem::take(&mut doc),
+                        });
+                    }
+                    // 跳到初始化语句的 `;`
+                    i = find_from(&chars, end, ";").map_or(chars.len(), |end| end + 1);
+                } else {
+                    if header.ends_with(')') {
+                        items.push(CItem::Function {
+                            header,
+                            doc: std::mem::take(&mut doc),
+                        });
+                    }
+                    i = end;
+                }
+                statement.clear();
+                doc.clear();
+                continue;
+            }
+            '}' => {
+                // extern "C" 的结束括号
+                statement.clear();
+            }
+            ';' => {
+                let header = statement.trim().to_string();
+                if header.ends_with(')') {
+                    items.push(CItem::Function {
+                        header,
+                        doc: std::mem::take(&mut doc),
+                    });
+                }
+                statement.clear();
+                doc.clear();
+            }
+            _ => statement.push(ch),
+        }
+        if ch == '\n' {
+            at_line_start = true;
+        } else if !ch.is_whitespace() {
+            at_line_start = false;
+        }
+        i += 1;
+    }
+
+    items
+}
+
+fn find_from(chars: &[char], start: usize, pattern: &str) -> Option<usize> {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    (start..chars.len()).find(|&i| chars[i..].starts_with(&pattern))
+}
+
+fn find_literal_end(chars: &[char], start: usize) -> usize {
+    let quote = chars[start];
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            ch if ch == quote || ch == '\n' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    chars.len()
+}
+
+/// The index after the `}` matching the `{` at `start`.
+fn find_block_end(chars: &[char], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i < chars.len() {
+        match chars[i] {
+            '"' | '\'' => {
+                i = find_literal_end(chars, i);
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                i = find_from(chars, i, "\n").unwrap_or(chars.len());
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i = find_from(chars, i + 2, "*/").map_or(chars.len(), |end| end + 2);
+                continue;
+            }
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    chars.len()
+}
+
+/// `static int l_open(lua_State *L)` -> `l_open`
+fn parse_c_function(header: &str, doc: Vec<String>) -> Option<(String, CFunction)> {
+    let open = header.find('(')?;
+    let close = header.rfind(')')?;
+    let before = header[..open].trim_end();
+    let name_start = before
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .map_or(0, |pos| pos + 1);
+    let name = &before[name_start..];
+    let return_type = before[..name_start].trim();
+    if name.is_empty() || return_type.is_empty() || return_type.contains('=') {
+        return None;
+    }
+
+    let params = header[open + 1..close]
+        .split(',')
+        .map(str::trim)
+        .filter(|param| !param.is_empty() && *param != "void")
+        .enumerate()
+        .map(|(index, param)| {
+            let name_start = param
+                .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .map_or(0, |pos| pos + 1);
+            let (typ, name) = param.split_at(name_start);
+            if typ.trim().is_empty() {
+                (param.to_string(), format!("p{}", index + 1))
+            } else {
+                (typ.trim().to_string(), name.to_string())
+            }
+        })
+        .collect();
+
+    Some((
+        name.to_string(),
+        CFunction {
+            return_type: return_type.to_string(),
+            params,
+            doc,
+        },
+    ))
+}
+
+/// `{"open", l_open},` -> `("open", "l_open")`
+fn parse_reg_entries(body: &str) -> Vec<(String, String)> {
+    body.split('{')
+        .skip(1)
+        .filter_map(|entry| {
+            let entry = entry.split('}').next()?;
+            let (lua_name, c_name) = entry.split_once(',')?;
+            let lua_name = lua_name.trim().strip_prefix('"')?.strip_suffix('"')?;
+            let c_name = c_name.trim();
+            if c_name == "NULL" || c_name.is_empty() {
+                return None;
+            }
+            Some((lua_name.to_string(), c_name.to_string()))
+        })
+        .collect()
+}
+
+fn build_function(lua_name: String, c_function: Option<&CFunction>) -> BindingFunction {
+    let mut function = BindingFunction {
+        name: lua_name,
+        ..Default::default()
+    };
+    let Some(c_function) = c_function else {
+        return function;
+    };
+
+    function.description = get_doc_description(&c_function.doc);
+    for line in &c_function.doc {
+        let Some(tag) = line.strip_prefix('@') else {
+            continue;
+        };
+        let (tag_name, rest) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        match tag_name {
+            "param" => {
+                let (name, typ, description) = split_tag(rest);
+                let optional = name.ends_with('?');
+                function.params.push(BindingParam {
+                    name: name.trim_end_matches('?').to_string(),
+                    typ,
+                    description,
+                    optional,
+                });
+            }
+            "return" => {
+                let (typ, name, description) = split_tag(rest);
+                function.returns.push(BindingReturn {
+                    typ: Some(typ),
+                    name: name.filter(|name| !name.is_empty()),
+                    description,
+                });
+            }
+            "overload" => function.overloads.push(rest.trim().to_string()),
+            "static" => function.is_static = true,
+            _ => {}
+        }
+    }
+
+    let is_lua_cfunction =
+        c_function.params.len() == 1 && c_function.params[0].0.contains("lua_State");
+    if !is_lua_cfunction && function.params.is_empty() && function.returns.is_empty() {
+        function.params = c_function
+            .params
+            .iter()
+            .map(|(typ, name)| BindingParam {
+                name: name.clone(),
+                typ: Some(c_type_to_lua(typ).to_string()),
+                ..Default::default()
+            })
+            .collect();
+        let return_type = c_type_to_lua(&c_function.return_type);
+        if return_type != "nil" {
+            function.returns.push(BindingReturn {
+                typ: Some(return_type.to_string()),
+                ..Default::default()
+            });
+        }
+    }
+
+    function
+}
+
+/// `name type # description` or `name type description`
+fn split_tag(rest: &str) -> (String, Option<String>, Option<String>) {
+    let (main, description) = match rest.split_once(" # ") {
+        Some((main, description)) => (main, Some(description.trim().to_string())),
+        None => (rest, None),
+    };
+    let mut parts = main.split_whitespace();
+    let first = parts.next().unwrap_or("").to_string();
+    let second = parts.next().map(str::to_string);
+    let remain = parts.collect::<Vec<_>>().join(" ");
+    let description = description.or((!remain.is_empty()).then_some(remain));
+    (first, second, description)
+}
+
+fn get_doc_description(doc: &[String]) -> Option<String> {
+    let lines = doc
+        .iter()
+        .take_while(|line| !line.starts_with('@'))
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    let description = lines.join("\n").trim().to_string();
+    (!description.is_empty()).then_some(description)
+}
+
+/// `@class mylib.File : mylib.Stream`
+fn get_doc_class(doc: &[String]) -> Option<(String, Option<String>)> {
+    let rest = doc.iter().find_map(|line| line.strip_prefix("@class "))?;
+    let (name, parent) = match rest.split_once(':') {
+        Some((name, parent)) => (name.trim(), Some(parent.trim().to_string())),
+        None => (rest.trim(), None),
+    };
+    Some((name.to_string(), parent))
+}
+
+fn get_doc_fields(doc: &[String]) -> Vec<BindingField> {
+    doc.iter()
+        .filter_map(|line| line.strip_prefix("@field "))
+        .map(|rest| {
+            let (name, typ, description) = split_tag(rest);
+            BindingField {
+                name,
+                typ,
+                description,
+            }
+        })
+        .collect()
+}
+
+fn c_type_to_lua(c_type: &str) -> &'static str {
+    let is_pointer = c_type.contains('*');
+    let base = c_type
+        .replace('*', " ")
+        .split_whitespace()
+        .filter(|word| {
+            !matches!(
+                *word,
+                "const"
+                    | "static"
+                    | "inline"
+                    | "extern"
+                    | "volatile"
+                    | "struct"
+                    | "LUA_API"
+                    | "LUALIB_API"
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    match base.as_str() {
+        "char" | "unsigned char" if is_pointer => "string",
+        "void" if !is_pointer => "nil",
+        "bool" | "_Bool" => "boolean",
+        "float" | "double" | "long double" | "lua_Number" => "number",
+        "lua_CFunction" => "function",
+        _ if is_pointer => "lightuserdata",
+        "int" | "long" | "short" | "char" | "unsigned" | "unsigned int" | "unsigned long"
+        | "long long" | "unsigned long long" | "unsigned short" | "unsigned char" | "size_t"
+        | "ssize_t" | "ptrdiff_t" | "lua_Integer" | "lua_Unsigned" => "integer",
+        _ if base.starts_with("int") || base.starts_with("uint") => "integer",
+        _ => "any",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_c_binding() {
+        let source = r#"
+#include <lua.h>
+#include <lauxlib.h>
+
+/// Open a file.
+/// @param path string # file path
+/// @param mode? string
+/// @return mylib.File
+/// @overload fun(fd: integer): mylib.File
+static int l_open(lua_State *L) {
+    const char *s = "}";
+    return 1;
+}
+
+/* plain comment */
+static int l_close(lua_State *L);
+
+/// Add two numbers.
+double add(double a, double b);
+
+int l_version(lua_State *L) { return 0; }
+
+static const luaL_Reg mylib_funcs[] = {
+    {"open", l_open},
+    {"add", add},
+    {NULL, NULL}
+};
+
+/// A file handle.
+/// @class mylib.File
+/// @field size integer # size in bytes
+static const luaL_Reg file_methods[] = {
+    {"close", l_close},
+    {"__gc", l_close},
+    {NULL, NULL}
+};
+
+/** The mylib module. */
+LUALIB_API int luaopen_mylib(lua_State *L) {
+    luaL_newlib(L, mylib_funcs);
+    return 1;
+}
+"#;
+        let module = BindingModule::from_c_source(source, None);
+        assert_eq!(module.name, "mylib");
+        assert_eq!(module.description.as_deref(), Some("The mylib module."));
+
+        let open = &module.functions[0];
+        assert_eq!(open.name, "open");
+        assert_eq!(open.description.as_deref(), Some("Open a file."));
+        assert_eq!(open.params.len(), 2);
+        assert_eq!(open.params[0].description.as_deref(), Some("file path"));
+        assert!(open.params[1].optional);
+        assert_eq!(open.returns[0].typ.as_deref(), Some("mylib.File"));
+        assert_eq!(open.overloads, vec!["fun(fd: integer): mylib.File"]);
+
+        let add = &module.functions[1];
+        assert_eq!(add.name, "add");
+        assert_eq!(add.params[0].name, "a");
+        assert_eq!(add.params[0].typ.as_deref(), Some("number"));
+        assert_eq!(add.returns[0].typ.as_deref(), Some("number"));
+
+        assert_eq!(module.classes.len(), 1);
+        let file = &module.classes[0];
+        assert_eq!(file.name, "mylib.File");
+        assert_eq!(file.description.as_deref(), Some("A file handle."));
+        assert_eq!(file.fields[0].description.as_deref(), Some("size in bytes"));
+        assert_eq!(file.methods.len(), 1);
+        assert_eq!(file.methods[0].name, "close");
+        assert!(file.methods[0].params.is_empty());
+    }
+}
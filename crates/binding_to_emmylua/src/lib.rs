@@ -0,0 +1,16 @@
+mod binding;
+mod c_binding;
+mod lua_emitter;
+mod meta_merge;
+
+pub use binding::{
+    BindingClass, BindingField, BindingFunction, BindingModule, BindingParam, BindingReturn,
+};
+pub use lua_emitter::MetaEmitter;
+
+impl BindingModule {
+    /// Emit the `---@meta` file text of this module.
+    pub fn to_meta(&self) -> String {
+        MetaEmitter::new().emit(self)
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt::Write;
+
+use crate::binding::{BindingClass, BindingFunction, BindingModule};
+
+/// Emits a `---@meta` file for a binding module.
+pub struct MetaEmitter {
+    output: String,
+}
+
+impl MetaEmitter {
+    pub fn new() -> Self {
+        Self {
+            output: String::new(),
+        }
+    }
+
+    pub fn emit(mut self, module: &BindingModule) -> String {
+        let module_local = local_name(&module.name, &[]);
+        let _ = writeln!(self.output, "---@meta {}", module.name);
+        self.output.push('\n');
+
+        self.write_description(module.description.as_deref());
+        let _ = writeln!(self.output, "---@class {}", module.name);
+        let _ = writeln!(self.output, "local {} = {{}}", module_local);
+        self.output.push('\n');
+
+        for function in &module.functions {
+            self.write_function(&module_local, ".", function);
+        }
+
+        let mut used_locals = vec![module_local.clone()];
+        for class in &module.classes {
+            let class_local = local_name(&class.name, &used_locals);
+            self.write_class(&class_local, class);
+            used_locals.push(class_local);
+        }
+
+        let _ = writeln!(self.output, "return {}", module_local);
+        self.output
+    }
+
+    fn write_class(&mut self, class_local: &str, class: &BindingClass) {
+        self.write_description(class.description.as_deref());
+        match &class.parent {
+            Some(parent) => {
+                let _ = writeln!(self.output, "---@class {} : {}", class.name, parent);
+            }
+            None => {
+                let _ = writeln!(self.output, "---@class {}", class.name);
+            }
+        }
+        for field in &class.fields {
+            self.write_description(field.description.as_deref());
+            let _ = writeln!(
+                self.output,
+                "---@field {} {}",
+                field.name,
+                field.typ.as_deref().unwrap_or("any")
+            );
+        }
+        let _ = writeln!(self.output, "local {} = {{}}", class_local);
+        self.output.push('\n');
+
+        for method in &class.methods {
+            let separator = if method.is_static { "." } else { ":" };
+            self.write_function(class_local, separator, method);
+        }
+    }
+
+    fn write_function(&mut self, owner: &str, separator: &str, function: &BindingFunction) {
+        self.write_description(function.description.as_deref());
+        for param in &function.params {
+            let _ = write!(
+                self.output,
+                "---@param {}{} {}",
+                param.name,
+                if param.optional { "?" } else { "" },
+                param.typ.as_deref().unwrap_or("any")
+            );
+            self.write_tag_description(param.description.as_deref());
+        }
+        for ret in &function.returns {
+            let _ = write!(
+                self.output,
+                "---@return {}",
+                ret.typ.as_deref().unwrap_or("any")
+            );
+            if let Some(name) = &ret.name {
+                let _ = write!(self.output, " {}", name);
+            }
+            self.write_tag_description(ret.description.as_deref());
+        }
+        for overload in &function.overloads {
+            let _ = writeln!(self.output, "---@overload {}", overload);
+        }
+
+        let params = function
+            .params
+            .iter()
+            .map(|param| param.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(
+            self.output,
+            "function {}{}{}({}) end",
+            owner, separator, function.name, params
+        );
+        self.output.push('\n');
+    }
+
+    /// Write `--- text` lines above a declaration.
+    fn write_description(&mut self, description: Option<&str>) {
+        if let Some(description) = description {
+            for line in description.lines() {
+                let _ = writeln!(self.output, "--- {}", line);
+            }
+        }
+    }
+
+    /// Finish a `---@param`/`---@return` line with ` # description`.
+    fn write_tag_description(&mut self, description: Option<&str>) {
+        match description {
+            Some(description) if !description.is_empty() => {
+                let _ = writeln!(self.output, " # {}", description.replace('\n', " "));
+            }
+            _ => self.output.push('\n'),
+        }
+    }
+}
+
+/// The local variable used for a module or class table: the last segment of its name.
+pub fn local_name(name: &str, used: &[String]) -> String {
+    let last = name.rsplit(['.', ':']).next().unwrap_or(name);
+    let mut local = last
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if local.is_empty() || local.starts_with(|c: char| c.is_ascii_digit()) {
+        local.insert(0, '_');
+    }
+    while used.contains(&local) {
+        local.push('_');
+    }
+    local
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::{BindingField, BindingParam, BindingReturn};
+
+    #[test]
+    fn test_emit_module() {
+        let module = BindingModule {
+            name: "mylib".to_string(),
+            description: Some("My library".to_string()),
+            functions: vec![BindingFunction {
+                name: "open".to_string(),
+                description: Some("Open a file.".to_string()),
+                params: vec![
+                    BindingParam {
+                        name: "path".to_string(),
+                        typ: Some("string".to_string()),
+                        description: Some("file path".to_string()),
+                        optional: false,
+                    },
+                    BindingParam {
+                        name: "mode".to_string(),
+                        typ: Some("string".to_string()),
+                        description: None,
+                        optional: true,
+                    },
+                ],
+                returns: vec![BindingReturn {
+                    typ: Some("mylib.File".to_string()),
+                    name: None,
+                    description: None,
+                }],
+                overloads: vec!["fun(fd: integer): mylib.File".to_string()],
+                is_static: false,
+            }],
+            classes: vec![BindingClass {
+                name: "mylib.File".to_string(),
+                parent: None,
+                description: None,
+                fields: vec![BindingField {
+                    name: "size".to_string(),
+                    typ: Some("integer".to_string()),
+                    description: Some("Size in bytes".to_string()),
+                }],
+                methods: vec![BindingFunction {
+                    name: "close".to_string(),
+                    ..Default::default()
+                }],
+            }],
+        };
+
+        let expected = r#"---@meta mylib
+
+--- My library
+---@class mylib
+local mylib = {}
+
+--- Open a file.
+---@param path string # file path
+---@param mode? string
+---@return mylib.File
+---@overload fun(fd: integer): mylib.File
+function mylib.open(path, mode) end
+
+---@class mylib.File
+--- Size in bytes
+---@field size integer
+local File = {}
+
+function File:close() end
+
+return mylib
+"#;
+        assert_eq!(MetaEmitter::new().emit(&module), expected);
+    }
+}
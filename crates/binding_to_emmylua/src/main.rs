@@ -0,0 +1,63 @@
+use binding_to_emmylua::BindingModule;
+use std::{fs, path::Path};
+
+fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let module_name = match args.iter().position(|arg| arg == "--module") {
+        Some(index) if index + 1 < args.len() => {
+            let name = args.remove(index + 1);
+            args.remove(index);
+            Some(name)
+        }
+        _ => None,
+    };
+
+    if args.is_empty() {
+        eprintln!("Usage: binding_to_emmylua <input> [output.lua] [--module <name>]");
+        eprintln!();
+        eprintln!("Generates an EmmyLua `---@meta` file from a C binding or a binding manifest.");
+        eprintln!();
+        eprintln!("Arguments:");
+        eprintln!("  <input>          A `.json`/`.toml` manifest, or a C/C++ source or header");
+        eprintln!("  [output.lua]     Optional path for output file (defaults to stdout).");
+        eprintln!("                   An existing file keeps its hand-written descriptions.");
+        eprintln!("  --module <name>  Module name, overrides the manifest or `luaopen_*` name");
+        std::process::exit(1);
+    }
+
+    let input_path = Path::new(&args[0]);
+    let text = fs::read_to_string(input_path).unwrap_or_else(|e| {
+        eprintln!("Failed to read '{}': {}", input_path.display(), e);
+        std::process::exit(1);
+    });
+
+    let extension = input_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("");
+    let result = match extension {
+        "json" => BindingModule::from_json_str(&text),
+        "toml" => BindingModule::from_toml_str(&text),
+        _ => Ok(BindingModule::from_c_source(&text, module_name.as_deref())),
+    };
+    let mut module = result.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+    if let Some(name) = module_name {
+        module.name = name;
+    }
+
+    if let Some(output_path) = args.get(1) {
+        if let Ok(existing) = fs::read_to_string(output_path) {
+            module.merge_descriptions(&existing);
+        }
+        fs::write(output_path, module.to_meta()).unwrap_or_else(|e| {
+            eprintln!("Failed to write '{}': {}", output_path, e);
+            std::process::exit(1);
+        });
+        eprintln!("Written to {}", output_path);
+    } else {
+        print!("{}", module.to_meta());
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+use crate::binding::{BindingFunction, BindingModule};
+
+/// Descriptions found in an existing meta file, keyed by declaration.
+#[derive(Debug, Default)]
+struct ExistingDescriptions {
+    descriptions: HashMap<String, String>,
+}
+
+impl ExistingDescriptions {
+    fn parse(text: &str) -> Self {
+        let mut result = Self::default();
+        let mut pending: Vec<String> = Vec::new();
+        let mut params: Vec<(String, String)> = Vec::new();
+        let mut returns: Vec<(usize, String)> = Vec::new();
+        let mut return_count = 0;
+        let mut current_class: Option<String> = None;
+        let mut local_to_class: HashMap<String, String> = HashMap::new();
+
+        for line in text.lines() {
+            let line = line.trim();
+            if let Some(rest) = line.strip_prefix("---@class") {
+                let Some(name) = get_class_name(rest) else {
+                    continue;
+                };
+                result.insert(format!("class:{}", name), &pending);
+                pending.clear();
+                current_class = Some(name);
+            } else if let Some(rest) = line.strip_prefix("---@field") {
+                let name = rest.split_whitespace().next().unwrap_or("");
+                if let Some(class) = &current_class {
+                    result.insert(format!("field:{}.{}", class, name), &pending);
+                }
+                pending.clear();
+            } else if let Some(rest) = line.strip_prefix("---@param") {
+                let name = rest.split_whitespace().next().unwrap_or("");
+                if let Some(description) = get_tag_description(rest) {
+                    params.push((name.trim_end_matches('?').to_string(), description));
+                }
+            } else if let Some(rest) = line.strip_prefix("---@return") {
+                if let Some(description) = get_tag_description(rest) {
+                    returns.push((return_count, description));
+                }
+                return_count += 1;
+            } else if line.starts_with("---@") || line.starts_with("---|") {
+                continue;
+            } else if let Some(rest) = line.strip_prefix("---") {
+                pending.push(rest.strip_prefix(' ').unwrap_or(rest).to_string());
+            } else if let Some(rest) = line.strip_prefix("local ") {
+                if let (Some((local, _)), Some(class)) = (rest.split_once('='), &current_class) {
+                    local_to_class.insert(local.trim().to_string(), class.clone());
+                }
+                current_class = None;
+                pending.clear();
+            } else if let Some(rest) = line.strip_prefix("function ") {
+                let name = rest.split('(').next().unwrap_or("").trim();
+                if let Some(pos) = name.rfind(['.', ':']) {
+                    let owner = &name[..pos];
+                    let owner = local_to_class
+                        .get(owner)
+                        .map(String::as_str)
+                        .unwrap_or(owner);
+                    let key = format!("{}.{}", owner, &name[pos + 1..]);
+                    result.insert(format!("function:{}", key), &pending);
+                    for (param, description) in params.drain(..) {
+                        result
+                            .descriptions
+                            .insert(format!("param:{}.{}", key, param), description);
+                    }
+                    for (index, description) in returns.drain(..) {
+                        result
+                            .descriptions
+                            .insert(format!("return:{}.{}", key, index), description);
+                    }
+                }
+                pending.clear();
+                params.clear();
+                returns.clear();
+                return_count = 0;
+            } else {
+                pending.clear();
+                params.clear();
+                returns.clear();
+                return_count = 0;
+            }
+        }
+
+        result
+    }
+
+    fn insert(&mut self, key: String, lines: &[String]) {
+        if !lines.is_empty() {
+            self.descriptions.insert(key, lines.join("\n"));
+        }
+    }
+
+    fn apply(&self, key: &str, description: &mut Option<String>) {
+        if let Some(existing) = self.descriptions.get(key)
+            && !existing.trim().is_empty()
+        {
+            *description = Some(existing.clone());
+        }
+    }
+
+    fn apply_function(&self, owner: &str, function: &mut BindingFunction) {
+        let key = format!("{}.{}", owner, function.name);
+        self.apply(&format!("function:{}", key), &mut function.description);
+        for param in &mut function.params {
+            self.apply(
+                &format!("param:{}.{}", key, param.name),
+                &mut param.description,
+            );
+        }
+        for (index, ret) in function.returns.iter_mut().enumerate() {
+            self.apply(&format!("return:{}.{}", key, index), &mut ret.description);
+        }
+    }
+}
+
+/// `---@class(partial) Name : Parent` -> `Name`
+fn get_class_name(rest: &str) -> Option<String> {
+    let rest = rest.trim_start();
+    let rest = match rest.strip_prefix('(') {
+        Some(rest) => rest.split_once(')')?.1,
+        None => rest,
+    };
+    let name = rest.split([' ', ':', '<']).find(|it| !it.is_empty())?;
+    Some(name.to_string())
+}
+
+/// The text after ` # ` on a `---@param`/`---@return` line.
+fn get_tag_description(rest: &str) -> Option<String> {
+    let (_, description) = rest.split_once(" # ")?;
+    let description = description.trim();
+    if description.is_empty() {
+        None
+    } else {
+        Some(description.to_string())
+    }
+}
+
+impl BindingModule {
+    /// Keep the hand-written descriptions of an existing meta file, so that regenerating it only
+    /// refreshes the declarations.
+    pub fn merge_descriptions(&mut self, existing_meta: &str) {
+        let existing = ExistingDescriptions::parse(existing_meta);
+        existing.apply(&format!("class:{}", self.name), &mut self.description);
+        for function in &mut self.functions {
+            existing.apply_function(&self.name, function);
+        }
+        for class in &mut self.classes {
+            existing.apply(&format!("class:{}", class.name), &mut class.description);
+            for field in &mut class.fields {
+                existing.apply(
+                    &format!("field:{}.{}", class.name, field.name),
+                    &mut field.description,
+                );
+            }
+            for method in &mut class.methods {
+                existing.apply_function(&class.name, method);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_hand_written_descriptions() {
+        let existing = r#"---@meta mylib
+
+--- My hand-written library docs.
+--- Second line.
+---@class mylib
+local mylib = {}
+
+--- Opens a file, see the manual.
+---@param path string # absolute or relative path
+---@return mylib.File # the opened file
+function mylib.open(path) end
+
+---@class mylib.File
+--- Size of the file in bytes.
+---@field size integer
+local File = {}
+
+--- Closes the file.
+function File:close() end
+
+return mylib
+"#;
+        let mut module = BindingModule::from_json_str(
+            r#"{
+                "name": "mylib",
+                "functions": [
+                    {
+                        "name": "open",
+                        "description": "Open a file.",
+                        "params": [
+                            { "name": "path", "type": "string" },
+                            { "name": "mode", "type": "string", "optional": true }
+                        ],
+                        "returns": [{ "type": "mylib.File" }]
+                    }
+                ],
+                "classes": [
+                    {
+                        "name": "mylib.File",
+                        "fields": [{ "name": "size", "type": "integer" }],
+                        "methods": [{ "name": "close" }, { "name": "flush" }]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        module.merge_descriptions(existing);
+
+        assert_eq!(
+            module.description.as_deref(),
+            Some("My hand-written library docs.\nSecond line.")
+        );
+        let open = &module.functions[0];
+        assert_eq!(
+            open.description.as_deref(),
+            Some("Opens a file, see the manual.")
+        );
+        assert_eq!(
+            open.params[0].description.as_deref(),
+            Some("absolute or relative path")
+        );
+        assert_eq!(open.params[1].description, None);
+        assert_eq!(
+            open.returns[0].description.as_deref(),
+            Some("the opened file")
+        );
+        let file = &module.classes[0];
+        assert_eq!(
+            file.fields[0].description.as_deref(),
+            Some("Size of the file in bytes.")
+        );
+        assert_eq!(
+            file.methods[0].description.as_deref(),
+            Some("Closes the file.")
+        );
+        assert_eq!(file.methods[1].description, None);
+    }
+}
@@ -0,0 +1,181 @@
+-- Records the runtime types of function parameters and return values, for
+-- `emmylua_trace` and the "Add annotations from runtime trace" code action.
+--
+-- Usage from a test runner:
+--
+--     local recorder = dofile("trace_recorder.lua")
+--     recorder.start()
+--     -- run the tests
+--     recorder.stop("trace.json")
+--
+-- Return values are only recorded on Lua 5.4 and later, which expose them to hooks.
+
+local recorder = {}
+
+local getinfo = debug.getinfo
+local getlocal = debug.getlocal
+local getmetatable = debug.getmetatable
+local math_type = math.type
+
+local functions = {}
+local order = {}
+local self_source = getinfo(1, "S").source
+
+local function type_name(value)
+    local typ = type(value)
+    if typ == "number" then
+        return math_type and math_type(value) or "number"
+    elseif typ == "table" or typ == "userdata" then
+        local mt = getmetatable(value)
+        local name = type(mt) == "table" and rawget(mt, "__name")
+        if type(name) == "string" then
+            return name
+        end
+    end
+    return typ
+end
+
+local function get_function(info)
+    local key = info.source .. ":" .. info.linedefined
+    local func = functions[key]
+    if not func then
+        func = {
+            source = info.source,
+            line = info.linedefined,
+            params = {},
+            param_types = {},
+            returns = {},
+            return_calls = 0,
+        }
+        functions[key] = func
+        order[#order + 1] = func
+    end
+    return func
+end
+
+local function add_param(func, name, value)
+    local types = func.param_types[name]
+    if not types then
+        types = {}
+        func.param_types[name] = types
+        func.params[#func.params + 1] = name
+    end
+    types[type_name(value)] = true
+end
+
+local function on_call(info)
+    local func = get_function(info)
+    for i = 1, info.nparams or 0 do
+        local name, value = getlocal(3, i)
+        if not name then
+            break
+        end
+        add_param(func, name, value)
+    end
+    if info.isvararg then
+        local i = 1
+        while true do
+            local name, value = getlocal(3, -i)
+            if not name then
+                break
+            end
+            add_param(func, "...", value)
+            i = i + 1
+        end
+    end
+end
+
+local function on_return(info)
+    if not info.ftransfer then
+        return
+    end
+    local func = get_function(info)
+    local returns = func.returns
+    local count = info.ntransfer
+    for i = 1, math.max(count, #returns) do
+        local types = returns[i]
+        if not types then
+            types = {}
+            returns[i] = types
+            -- 之前的调用没有返回这个位置
+            if func.return_calls > 0 then
+                types["nil"] = true
+            end
+        end
+        if i <= count then
+            local _, value = getlocal(3, info.ftransfer + i - 1)
+            types[type_name(value)] = true
+        else
+            types["nil"] = true
+        end
+    end
+    func.return_calls = func.return_calls + 1
+end
+
+local function hook(event)
+    local info = getinfo(2, "Sur")
+    if not info or info.what ~= "Lua" or info.source == self_source then
+        return
+    end
+    if event == "return" then
+        on_return(info)
+    else
+        on_call(info)
+    end
+end
+
+local function encode_string(s)
+    return '"' .. s:gsub('[%c"\\]', function(c)
+        if c == '"' then
+            return '\\"'
+        elseif c == "\\" then
+            return "\\\\"
+        end
+        return string.format("\\u%04x", c:byte())
+    end) .. '"'
+end
+
+local function encode_types(types)
+    local names = {}
+    for name in pairs(types) do
+        names[#names + 1] = encode_string(name)
+    end
+    table.sort(names)
+    return "[" .. table.concat(names, ", ") .. "]"
+end
+
+local function encode()
+    local entries = {}
+    for _, func in ipairs(order) do
+        local params = {}
+        for _, name in ipairs(func.params) do
+            params[#params + 1] = string.format('{ "name": %s, "types": %s }',
+                encode_string(name), encode_types(func.param_types[name]))
+        end
+        local returns = {}
+        for _, types in ipairs(func.returns) do
+            returns[#returns + 1] = encode_types(types)
+        end
+        entries[#entries + 1] = string.format(
+            '  { "source": %s, "line": %d, "params": [%s], "returns": [%s] }',
+            encode_string(func.source), func.line,
+            table.concat(params, ", "), table.concat(returns, ", "))
+    end
+    return "[\n" .. table.concat(entries, ",\n") .. "\n]\n"
+end
+
+--- Start recording calls made on the current coroutine.
+function recorder.start()
+    debug.sethook(hook, "cr")
+end
+
+--- Stop recording and write the trace to `path`.
+---@param path string
+function recorder.stop(path)
+    debug.sethook()
+    local file = assert(io.open(path, "w"))
+    file:write(encode())
+    file:close()
+end
+
+return recorder
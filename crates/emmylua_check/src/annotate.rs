@@ -0,0 +1,73 @@
+#[cfg(feature = "cli")]
+use clap::Parser;
+
+use emmylua_code_analysis::build_doc_stubs;
+use std::{error::Error, path::PathBuf};
+
+use crate::init::{self, CliEdit, setup_logger};
+
+#[allow(unused)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "cli", derive(Parser))]
+#[cfg_attr(feature = "cli", command(version))]
+pub struct AnnotateArgs {
+    /// Comma separated list of files to annotate.
+    /// If not provided, every file of the workspace is annotated
+    #[cfg_attr(feature = "cli", arg(long, value_delimiter = ','))]
+    pub file: Vec<PathBuf>,
+
+    /// Configuration file paths.
+    /// If not provided, both ".emmyrc.json" and ".luarc.json" will be searched in the workspace
+    /// directory
+    #[cfg_attr(feature = "cli", arg(short, long, value_delimiter = ','))]
+    pub config: Option<Vec<PathBuf>>,
+
+    /// Path to the workspace directory
+    #[cfg_attr(feature = "cli", arg(num_args = 1..))]
+    pub workspace: Vec<PathBuf>,
+
+    /// Comma separated list of ignore patterns.
+    /// Patterns must follow glob syntax
+    #[cfg_attr(feature = "cli", arg(short, long, value_delimiter = ','))]
+    pub ignore: Option<Vec<String>>,
+
+    /// Print the generated stubs without rewriting any file
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub dry_run: bool,
+
+    /// Verbose output
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub verbose: bool,
+}
+
+pub async fn run_annotate(args: AnnotateArgs) -> Result<(), Box<dyn Error + Sync + Send>> {
+    setup_logger(args.verbose);
+    let cwd = std::env::current_dir()?;
+    let files: Vec<_> = args
+        .file
+        .into_iter()
+        .map(|file| init::resolve_cli_path(&cwd, file))
+        .collect();
+    let analysis = init::load_cli_workspace(args.workspace, args.config, args.ignore).await?;
+
+    let summary = init::rewrite_workspace_files(
+        &analysis,
+        args.dry_run,
+        |semantic_model| {
+            let document = semantic_model.get_document();
+            if !files.is_empty() && !files.contains(document.get_file_path()) {
+                return Vec::new();
+            }
+            build_doc_stubs(semantic_model)
+                .into_iter()
+                .map(|edit| CliEdit {
+                    range: edit.range,
+                    new_text: Some(edit.new_text),
+                })
+                .collect()
+        },
+        init::print_inserted_tags,
+    )?;
+    summary.print("Generated", "stub(s)");
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use clap::Parser;
+use emmylua_check::{AnnotateArgs, run_annotate};
+use mimalloc::MiMalloc;
+use std::error::Error;
+
+#[global_allocator]
+static GLOBAL: MiMalloc = MiMalloc;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
+    let args = AnnotateArgs::parse();
+    run_annotate(args).await
+}
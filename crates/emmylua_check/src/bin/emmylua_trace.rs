@@ -0,0 +1,13 @@
+use clap::Parser;
+use emmylua_check::{TraceArgs, run_trace};
+use mimalloc::MiMalloc;
+use std::error::Error;
+
+#[global_allocator]
+static GLOBAL: MiMalloc = MiMalloc;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
+    let args = TraceArgs::parse();
+    run_trace(args).await
+}
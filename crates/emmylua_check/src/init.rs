@@ -1,10 +1,17 @@
 use emmylua_code_analysis::{
-    EmmyLuaAnalysis, WorkspaceFolder, build_workspace_folders, collect_workspace_files,
-    file_path_to_uri, load_configs, uri_to_file_path,
+    CONFIG_FILE_NAMES, EmmyLuaAnalysis, Emmyrc, LuaDocument, SemanticModel, WorkspaceFolder,
+    apply_text_edits, build_workspace_folders, collect_workspace_files,
+    extend_scoped_library_folders, file_path_to_uri, find_config_scope_dirs, load_configs,
+    uri_to_file_path,
 };
 use fern::Dispatch;
 use log::LevelFilter;
-use std::path::{Path, PathBuf};
+use rowan::TextRange;
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 fn root_from_configs(config_paths: &[PathBuf], fallback: &Path) -> PathBuf {
     if config_paths.len() != 1 {
@@ -103,9 +110,13 @@ pub async fn load_workspace(
         .collect::<Vec<WorkspaceFolder>>();
     let mut analysis = EmmyLuaAnalysis::new();
     analysis.update_config(emmyrc.clone().into());
-    analysis.init_std_lib(None);
 
-    let workspace_folders = build_workspace_folders(&workspace_folders, &emmyrc);
+    let mut workspace_folders = build_workspace_folders(&workspace_folders, &emmyrc);
+    // directories with their own config file use it instead of the workspace config
+    let scoped_emmyrcs = load_scoped_configs(&workspace_folders, &emmyrc, &config_root, &main_path);
+    extend_scoped_library_folders(&mut workspace_folders, &scoped_emmyrcs);
+    analysis.update_scoped_configs(scoped_emmyrcs);
+    analysis.init_std_lib(None);
     for workspace in &workspace_folders {
         if workspace.is_library {
             analysis.add_library_workspace(workspace);
@@ -123,9 +134,147 @@ pub async fn load_workspace(
     Some(analysis)
 }
 
+fn load_scoped_configs(
+    workspace_folders: &[WorkspaceFolder],
+    emmyrc: &Emmyrc,
+    config_root: &Path,
+    main_path: &Path,
+) -> Vec<(PathBuf, Arc<Emmyrc>)> {
+    find_config_scope_dirs(workspace_folders, emmyrc, Some(config_root))
+        .into_iter()
+        .filter(|dir| dir != main_path)
+        .map(|dir| {
+            let config_files = CONFIG_FILE_NAMES
+                .iter()
+                .map(|name| dir.join(name))
+                .filter(|path| path.exists())
+                .collect();
+            let mut scoped_emmyrc = load_configs(config_files, None);
+            log::info!("Using the config of \"{}\" for its files", dir.display());
+            scoped_emmyrc.pre_process_emmyrc(&dir);
+            (dir, Arc::new(scoped_emmyrc))
+        })
+        .collect()
+}
+
 pub(crate) fn normalize_local_path(path: PathBuf) -> PathBuf {
     let path = path.canonicalize().unwrap_or(path);
     file_path_to_uri(&path)
         .and_then(|uri| uri_to_file_path(&uri))
         .unwrap_or(path)
 }
+
+/// Resolves a path given on the command line against the current directory.
+pub fn resolve_cli_path(cwd: &Path, path: PathBuf) -> PathBuf {
+    let path = if path.is_absolute() {
+        path
+    } else {
+        cwd.join(path)
+    };
+    path.canonicalize().unwrap_or(path)
+}
+
+/// Loads the workspaces given on the command line, the first one being the main workspace.
+pub async fn load_cli_workspace(
+    workspace: Vec<PathBuf>,
+    config_paths: Option<Vec<PathBuf>>,
+    ignore: Option<Vec<String>>,
+) -> Result<EmmyLuaAnalysis, Box<dyn Error + Sync + Send>> {
+    let cwd = std::env::current_dir()?;
+    let workspaces: Vec<_> = workspace
+        .into_iter()
+        .map(|workspace| resolve_cli_path(&cwd, workspace))
+        .collect();
+    let main_path = workspaces
+        .first()
+        .ok_or("Failed to load workspace")?
+        .clone();
+    load_workspace(main_path, workspaces, config_paths, ignore)
+        .await
+        .ok_or_else(|| "Failed to load workspace".into())
+}
+
+/// An edit proposed by a command line tool. Without `new_text` the range is only reported.
+pub struct CliEdit {
+    pub range: TextRange,
+    pub new_text: Option<String>,
+}
+
+/// How many edits were found in how many files, and whether the files were rewritten.
+pub struct RewriteSummary {
+    pub edit_count: usize,
+    pub file_count: usize,
+    pub rewritten: bool,
+}
+
+impl RewriteSummary {
+    /// Prints e.g. `Found 3 match(es) in 2 file(s), files rewritten`.
+    pub fn print(&self, verb: &str, unit: &str) {
+        eprintln!(
+            "{} {} {} in {} file(s){}",
+            verb,
+            self.edit_count,
+            unit,
+            self.file_count,
+            if self.rewritten {
+                ", files rewritten"
+            } else {
+                ""
+            }
+        );
+    }
+}
+
+/// Computes the edits of every main workspace file, then either prints them with `print_edit`
+/// (`dry_run`) or rewrites the files.
+pub fn rewrite_workspace_files(
+    analysis: &EmmyLuaAnalysis,
+    dry_run: bool,
+    mut collect_edits: impl FnMut(&SemanticModel) -> Vec<CliEdit>,
+    print_edit: fn(&LuaDocument, &CliEdit),
+) -> Result<RewriteSummary, Box<dyn Error + Sync + Send>> {
+    let db = analysis.compilation.get_db();
+    let mut file_ids = db.get_module_index().get_main_workspace_file_ids();
+    file_ids.sort();
+    let mut summary = RewriteSummary {
+        edit_count: 0,
+        file_count: 0,
+        rewritten: !dry_run,
+    };
+    for file_id in file_ids {
+        let Some(semantic_model) = analysis.compilation.get_semantic_model(file_id) else {
+            continue;
+        };
+        let edits = collect_edits(&semantic_model);
+        if edits.is_empty() {
+            continue;
+        }
+
+        let document = semantic_model.get_document();
+        summary.edit_count += edits.len();
+        summary.file_count += 1;
+        if dry_run {
+            for edit in &edits {
+                print_edit(&document, edit);
+            }
+            continue;
+        }
+
+        let edits = edits
+            .into_iter()
+            .filter_map(|edit| Some((edit.range, edit.new_text?)))
+            .collect();
+        let new_text = apply_text_edits(document.get_text(), edits);
+        std::fs::write(document.get_file_path(), new_text)?;
+    }
+    Ok(summary)
+}
+
+/// Prints the doc tags an edit inserts, below the line they are inserted at.
+pub fn print_inserted_tags(document: &LuaDocument, edit: &CliEdit) {
+    let line = document.get_line(edit.range.start()).unwrap_or(0) + 1;
+    println!("{}:{}:", document.get_file_path().display(), line);
+    for tag in edit.new_text.iter().flat_map(|text| text.lines()) {
+        println!("    {}", tag.trim());
+    }
+}
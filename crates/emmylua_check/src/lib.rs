@@ -1,12 +1,18 @@
+mod annotate;
 pub mod cmd_args;
 mod init;
 mod output;
+mod ssr;
 mod terminal_display;
+mod trace;
 
+pub use annotate::{AnnotateArgs, run_annotate};
 pub use cmd_args::*;
 use output::output_result;
+pub use ssr::{SsrArgs, run_ssr};
 use std::{error::Error, sync::Arc};
 use tokio_util::sync::CancellationToken;
+pub use trace::{TraceArgs, run_trace};
 
 use crate::init::setup_logger;
 
@@ -0,0 +1,75 @@
+#[cfg(feature = "cli")]
+use clap::Parser;
+
+use emmylua_code_analysis::{LuaDocument, SsrRule};
+use std::{error::Error, path::PathBuf};
+
+use crate::init::{self, CliEdit, setup_logger};
+
+#[allow(unused)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "cli", derive(Parser))]
+#[cfg_attr(feature = "cli", command(version))]
+pub struct SsrArgs {
+    /// Structural search and replace rule, for example
+    /// `$obj:GetComponent("X") ==>> $obj.x where $obj: GameObject`
+    #[cfg_attr(feature = "cli", arg(short, long))]
+    pub rule: String,
+
+    /// Configuration file paths.
+    /// If not provided, both ".emmyrc.json" and ".luarc.json" will be searched in the workspace
+    /// directory
+    #[cfg_attr(feature = "cli", arg(short, long, value_delimiter = ','))]
+    pub config: Option<Vec<PathBuf>>,
+
+    /// Path to the workspace directory
+    #[cfg_attr(feature = "cli", arg(num_args = 1..))]
+    pub workspace: Vec<PathBuf>,
+
+    /// Comma separated list of ignore patterns.
+    /// Patterns must follow glob syntax
+    #[cfg_attr(feature = "cli", arg(short, long, value_delimiter = ','))]
+    pub ignore: Option<Vec<String>>,
+
+    /// Print the matches without rewriting any file
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub dry_run: bool,
+
+    /// Verbose output
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub verbose: bool,
+}
+
+pub async fn run_ssr(args: SsrArgs) -> Result<(), Box<dyn Error + Sync + Send>> {
+    setup_logger(args.verbose);
+    let rule = SsrRule::parse(&args.rule)?;
+    let analysis = init::load_cli_workspace(args.workspace, args.config, args.ignore).await?;
+
+    let dry_run = args.dry_run || !rule.has_replacement();
+    let summary = init::rewrite_workspace_files(
+        &analysis,
+        dry_run,
+        |semantic_model| {
+            rule.find_matches(semantic_model)
+                .into_iter()
+                .map(|ssr_match| CliEdit {
+                    range: ssr_match.range,
+                    new_text: ssr_match.replacement,
+                })
+                .collect()
+        },
+        print_match,
+    )?;
+    summary.print("Found", "match(es)");
+    Ok(())
+}
+
+fn print_match(document: &LuaDocument, edit: &CliEdit) {
+    let path = document.get_file_path();
+    let line = document.get_line(edit.range.start()).unwrap_or(0) + 1;
+    let text = document.get_text_slice(edit.range);
+    match &edit.new_text {
+        Some(replacement) => println!("{}:{}: {} => {}", path.display(), line, text, replacement),
+        None => println!("{}:{}: {}", path.display(), line, text),
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(feature = "cli")]
+use clap::Parser;
+
+use emmylua_code_analysis::{RuntimeTrace, build_trace_annotations};
+use std::{error::Error, path::PathBuf};
+
+use crate::init::{self, CliEdit, setup_logger};
+
+#[allow(unused)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "cli", derive(Parser))]
+#[cfg_attr(feature = "cli", command(version))]
+pub struct TraceArgs {
+    /// Comma separated list of runtime type trace files recorded with `trace_recorder.lua`.
+    /// The files listed in `codeAction.traceFiles` are used as well
+    #[cfg_attr(feature = "cli", arg(short, long, value_delimiter = ','))]
+    pub trace: Vec<PathBuf>,
+
+    /// Configuration file paths.
+    /// If not provided, both ".emmyrc.json" and ".luarc.json" will be searched in the workspace
+    /// directory
+    #[cfg_attr(feature = "cli", arg(short, long, value_delimiter = ','))]
+    pub config: Option<Vec<PathBuf>>,
+
+    /// Path to the workspace directory
+    #[cfg_attr(feature = "cli", arg(num_args = 1..))]
+    pub workspace: Vec<PathBuf>,
+
+    /// Comma separated list of ignore patterns.
+    /// Patterns must follow glob syntax
+    #[cfg_attr(feature = "cli", arg(short, long, value_delimiter = ','))]
+    pub ignore: Option<Vec<String>>,
+
+    /// Print the proposed annotations without rewriting any file
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub dry_run: bool,
+
+    /// Verbose output
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub verbose: bool,
+}
+
+pub async fn run_trace(args: TraceArgs) -> Result<(), Box<dyn Error + Sync + Send>> {
+    setup_logger(args.verbose);
+    let analysis = init::load_cli_workspace(args.workspace, args.config, args.ignore).await?;
+
+    let mut trace = RuntimeTrace::default();
+    for path in &args.trace {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        trace.merge(RuntimeTrace::from_json_str(&text)?);
+    }
+    trace.merge(RuntimeTrace::load_files(
+        &analysis.emmyrc.code_action.trace_files,
+    ));
+    if trace.is_empty() {
+        return Err("No runtime trace given, use --trace or codeAction.traceFiles".into());
+    }
+
+    let summary = init::rewrite_workspace_files(
+        &analysis,
+        args.dry_run,
+        |semantic_model| {
+            build_trace_annotations(semantic_model, &trace)
+                .into_iter()
+                .map(|edit| CliEdit {
+                    range: edit.range,
+                    new_text: Some(edit.new_text),
+                })
+                .collect()
+        },
+        init::print_inserted_tags,
+    )?;
+    summary.print("Annotated", "function(s)");
+    Ok(())
+}
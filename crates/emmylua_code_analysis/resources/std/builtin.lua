@@ -140,6 +140,18 @@
 
 --- @alias Language<T: string> string
 
+---
+--- Convert a string literal type to uppercase
+--- @alias Uppercase<T: string> string
+
+---
+--- Convert a string literal type to lowercase
+--- @alias Lowercase<T: string> string
+
+---
+--- Convert the first character of a string literal type to uppercase
+--- @alias Capitalize<T: string> string
+
 ---
 --- Get the parameters of a function as a tuple
 --- @alias Parameters<T extends function> T extends (fun(...: infer P): any) and P or never
@@ -163,6 +175,30 @@
 --- Extract from T those types that are assignable to U
 --- @alias Extract<T, U> T extends U and T or never
 
+---
+--- Make all properties in T required
+--- @alias Required<T> { [P in keyof T]-?: T[P]; }
+
+---
+--- Make all properties in T readonly
+--- @alias Readonly<T> { readonly [P in keyof T]: T[P]; }
+
+---
+--- From T, pick a set of properties whose keys are in the union K
+--- @alias Pick<T, K extends keyof T> { [P in K]: T[P]; }
+
+---
+--- Construct a type with the properties of T except for those in K
+--- @alias Omit<T, K> { [P in Exclude<keyof T, K>]: T[P]; }
+
+---
+--- Construct a type with a set of properties K of type V
+--- @alias Record<K, V> { [P in K]: V; }
+
+---
+--- Exclude nil from T
+--- @alias NonNullable<T> T -?
+
 --- attribute
 
 --- @class Attribute
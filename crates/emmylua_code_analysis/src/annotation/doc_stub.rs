@@ -0,0 +1,216 @@
+use std::{collections::HashSet, ops::Deref};
+
+use emmylua_parser::{
+    LuaAssignStat, LuaAstNode, LuaAstToken, LuaClosureExpr, LuaCommentOwner, LuaDocTag, LuaExpr,
+    LuaIndexKey, LuaStat, LuaTableExpr, LuaVarExpr,
+};
+use rowan::NodeOrToken;
+
+use crate::{
+    LuaSignatureId, LuaType, RenderLevel, SemanticDeclLevel, SemanticModel, SignatureReturnStatus,
+    VariadicType, humanize_type,
+};
+
+use super::{AnnotationEdit, make_annotation_edit, widen_literal_type};
+
+/// Propose doc stubs for the undocumented code of a file: the missing `---@param`/`---@return`
+/// tags of every function, typed from inference, and a `---@class` block for every table
+/// constructor assigned to a field of the module table. Existing tags are never touched.
+pub fn build_doc_stubs(semantic_model: &SemanticModel) -> Vec<AnnotationEdit> {
+    let root = semantic_model.get_root();
+    let mut edits = Vec::new();
+    let mut seen_stats = HashSet::new();
+    for closure_expr in root.descendants::<LuaClosureExpr>() {
+        if let Some(edit) = build_function_doc_stub(semantic_model, &closure_expr)
+            && seen_stats.insert(edit.stat_range)
+        {
+            edits.push(edit);
+        }
+    }
+
+    for assign_stat in root.descendants::<LuaAssignStat>() {
+        if let Some(edit) = build_module_field_class_stub(semantic_model, &assign_stat)
+            && seen_stats.insert(edit.stat_range)
+        {
+            edits.push(edit);
+        }
+    }
+
+    edits.sort_by_key(|edit| edit.range.start());
+    edits
+}
+
+/// Propose the missing `---@param`/`---@return` tags of one function.
+pub fn build_function_doc_stub(
+    semantic_model: &SemanticModel,
+    closure_expr: &LuaClosureExpr,
+) -> Option<AnnotationEdit> {
+    let stat = closure_expr.get_parent::<LuaStat>()?;
+    let signature_id = LuaSignatureId::from_closure(semantic_model.get_file_id(), closure_expr);
+    let signature = semantic_model
+        .get_db()
+        .get_signature_index()
+        .get(&signature_id)?;
+
+    let mut lines = Vec::new();
+    for param in closure_expr.get_params_list()?.get_params() {
+        if param.is_dots() {
+            if !signature
+                .find_param_idx("...")
+                .is_some_and(|idx| signature.param_docs.contains_key(&idx))
+            {
+                lines.push("---@param ... any".to_string());
+            }
+            continue;
+        }
+
+        let name_token = param.get_name_token()?;
+        let name = name_token.get_name_text();
+        // 与 incomplete-signature-doc 一致, `_` 不需要注解
+        if name == "_"
+            || signature
+                .find_param_idx(name)
+                .is_some_and(|idx| signature.param_docs.contains_key(&idx))
+        {
+            continue;
+        }
+        let typ = semantic_model
+            .get_semantic_info(NodeOrToken::Token(name_token.syntax().clone()))
+            .map(|info| info.typ)
+            .unwrap_or(LuaType::Unknown);
+        lines.push(format!(
+            "---@param {} {}",
+            name,
+            render_stub_type(semantic_model, typ)
+        ));
+    }
+
+    if signature.resolve_return == SignatureReturnStatus::InferResolve
+        && signature
+            .return_docs
+            .iter()
+            .any(|ret| !ret.type_ref.is_nil())
+    {
+        for ret in &signature.return_docs {
+            // 多返回值推断为 Variadic::Multi, 逐个展开
+            let types = match &ret.type_ref {
+                LuaType::Variadic(variadic) => match variadic.deref() {
+                    VariadicType::Multi(types) => types.clone(),
+                    VariadicType::Base(base) => {
+                        lines.push(format!(
+                            "---@return {} ...",
+                            render_stub_type(semantic_model, base.clone())
+                        ));
+                        continue;
+                    }
+                },
+                typ => vec![typ.clone()],
+            };
+            for typ in types {
+                lines.push(format!(
+                    "---@return {}",
+                    render_stub_type(semantic_model, typ)
+                ));
+            }
+        }
+    }
+
+    if lines.is_empty() {
+        return None;
+    }
+
+    Some(make_annotation_edit(
+        semantic_model,
+        stat.get_range(),
+        lines,
+    ))
+}
+
+/// `M.config = { ... }` in a module returning `M` -> `---@class mod.config` with a `---@field`
+/// for each named field of the constructor.
+fn build_module_field_class_stub(
+    semantic_model: &SemanticModel,
+    assign_stat: &LuaAssignStat,
+) -> Option<AnnotationEdit> {
+    let (vars, exprs) = assign_stat.get_var_and_expr_list();
+    let ([LuaVarExpr::IndexExpr(index_expr)], [LuaExpr::TableExpr(table_expr)]) =
+        (vars.as_slice(), exprs.as_slice())
+    else {
+        return None;
+    };
+    let has_type_tag = assign_stat
+        .get_comments()
+        .iter()
+        .flat_map(|comment| comment.get_doc_tags())
+        .any(|tag| matches!(tag, LuaDocTag::Class(_) | LuaDocTag::Type(_)));
+    if has_type_tag {
+        return None;
+    }
+
+    let module_info = semantic_model.get_module()?;
+    let LuaExpr::NameExpr(prefix_expr) = index_expr.get_prefix_expr()? else {
+        return None;
+    };
+    let prefix_decl = semantic_model.find_decl(
+        NodeOrToken::Node(prefix_expr.syntax().clone()),
+        SemanticDeclLevel::default(),
+    )?;
+    if module_info.semantic_id.as_ref() != Some(&prefix_decl) {
+        return None;
+    }
+    let field_name = match index_expr.get_index_key()? {
+        LuaIndexKey::Name(name) => name.get_name_text().to_string(),
+        LuaIndexKey::String(string) => string.get_value(),
+        _ => return None,
+    };
+
+    let class_name = format!("{}.{}", module_info.full_module_name, field_name);
+    let class_exists = semantic_model
+        .get_db()
+        .get_type_index()
+        .find_type_decl(semantic_model.get_file_id(), &class_name, None)
+        .is_some();
+    if class_exists {
+        return None;
+    }
+
+    let mut lines = vec![format!("---@class {}", class_name)];
+    lines.extend(build_table_field_stubs(semantic_model, table_expr));
+    Some(make_annotation_edit(
+        semantic_model,
+        assign_stat.get_range(),
+        lines,
+    ))
+}
+
+fn build_table_field_stubs(
+    semantic_model: &SemanticModel,
+    table_expr: &LuaTableExpr,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    for field in table_expr.get_fields() {
+        let name = match field.get_field_key() {
+            Some(LuaIndexKey::Name(name)) => name.get_name_text().to_string(),
+            Some(LuaIndexKey::String(string)) => string.get_value(),
+            _ => continue,
+        };
+        let typ = field
+            .get_value_expr()
+            .and_then(|expr| semantic_model.infer_expr(expr).ok())
+            .unwrap_or(LuaType::Unknown);
+        lines.push(format!(
+            "---@field {} {}",
+            name,
+            render_stub_type(semantic_model, typ)
+        ));
+    }
+    lines
+}
+
+fn render_stub_type(semantic_model: &SemanticModel, typ: LuaType) -> String {
+    let typ = match widen_literal_type(typ) {
+        LuaType::Unknown => LuaType::Any,
+        typ => typ,
+    };
+    humanize_type(semantic_model.get_db(), &typ, RenderLevel::Simple)
+}
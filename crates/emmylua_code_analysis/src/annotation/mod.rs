@@ -0,0 +1,238 @@
+mod doc_stub;
+mod runtime_trace;
+mod test;
+
+use std::collections::BTreeSet;
+
+use emmylua_parser::{LuaAstNode, LuaAstToken, LuaClosureExpr, LuaStat};
+use rowan::{NodeOrToken, TextRange, TextSize};
+
+pub use doc_stub::{build_doc_stubs, build_function_doc_stub};
+pub use runtime_trace::{RuntimeTrace, TraceFunction};
+
+use crate::{
+    LuaSignatureId, LuaType, RenderLevel, SemanticModel, SignatureReturnStatus, TypeOps,
+    humanize_type,
+};
+
+/// Doc tags to insert above the statement that defines a function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotationEdit {
+    /// Range of the statement defining the function.
+    pub stat_range: TextRange,
+    /// Empty range at the start of the statement.
+    pub range: TextRange,
+    pub new_text: String,
+}
+
+impl AnnotationEdit {
+    pub fn into_text_edit(self) -> (TextRange, String) {
+        (self.range, self.new_text)
+    }
+}
+
+/// Propose `---@param`/`---@return` tags from a runtime trace for every traced function of
+/// the file that lacks them.
+pub fn build_trace_annotations(
+    semantic_model: &SemanticModel,
+    trace: &RuntimeTrace,
+) -> Vec<AnnotationEdit> {
+    if trace.is_empty() {
+        return Vec::new();
+    }
+
+    semantic_model
+        .get_root()
+        .descendants::<LuaClosureExpr>()
+        .filter_map(|closure_expr| {
+            build_closure_trace_annotation(semantic_model, &closure_expr, trace)
+        })
+        .collect()
+}
+
+/// Propose the missing `---@param`/`---@return` tags of one function from a runtime trace. The
+/// traced types are merged with the types the semantic model infers.
+pub fn build_closure_trace_annotation(
+    semantic_model: &SemanticModel,
+    closure_expr: &LuaClosureExpr,
+    trace: &RuntimeTrace,
+) -> Option<AnnotationEdit> {
+    let stat = closure_expr.get_parent::<LuaStat>()?;
+    let document = semantic_model.get_document();
+    let line = document.get_line(closure_expr.get_position())? as u32 + 1;
+    let traced = trace.find_function(document.get_file_path(), line)?;
+    let signature_id = LuaSignatureId::from_closure(semantic_model.get_file_id(), closure_expr);
+    let signature = semantic_model
+        .get_db()
+        .get_signature_index()
+        .get(&signature_id)?;
+
+    let mut lines = Vec::new();
+    for param in closure_expr.get_params_list()?.get_params() {
+        let (name, inferred) = if param.is_dots() {
+            ("...".to_string(), None)
+        } else {
+            let name_token = param.get_name_token()?;
+            let inferred = semantic_model
+                .get_semantic_info(NodeOrToken::Token(name_token.syntax().clone()))
+                .map(|info| info.typ);
+            (name_token.get_name_text().to_string(), inferred)
+        };
+        // 已有 @param 的参数不再生成
+        if signature
+            .find_param_idx(&name)
+            .is_some_and(|idx| signature.param_docs.contains_key(&idx))
+        {
+            continue;
+        }
+        let Some(types) = traced.params.get(&name) else {
+            continue;
+        };
+
+        let (typ, optional) = merge_traced_types(semantic_model, types, inferred);
+        lines.push(format!(
+            "---@param {}{} {}",
+            name,
+            if optional { "?" } else { "" },
+            typ
+        ));
+    }
+
+    if signature.resolve_return != SignatureReturnStatus::DocResolve {
+        let inferred_return = (signature.resolve_return == SignatureReturnStatus::InferResolve)
+            .then(|| signature.get_return_type());
+        for (i, types) in traced.returns.iter().enumerate() {
+            let inferred = match &inferred_return {
+                Some(LuaType::Variadic(variadic)) => variadic.get_type(i).cloned(),
+                Some(typ) if i == 0 => Some(typ.clone()),
+                _ => None,
+            };
+            // 只观察到 nil 的返回值
+            if types.iter().all(|typ| typ == "nil") {
+                lines.push("---@return nil".to_string());
+                continue;
+            }
+            let (typ, optional) = merge_traced_types(semantic_model, types, inferred);
+            lines.push(format!(
+                "---@return {}{}",
+                typ,
+                if optional { "?" } else { "" }
+            ));
+        }
+    }
+
+    if lines.is_empty() {
+        return None;
+    }
+
+    Some(make_annotation_edit(
+        semantic_model,
+        stat.get_range(),
+        lines,
+    ))
+}
+
+/// Merge the traced type names with the inferred type. The inferred type wins when it accepts
+/// every traced type, since it is usually more precise (e.g. a class instead of `table`).
+/// Returns the rendered type and whether `nil` was seen.
+fn merge_traced_types(
+    semantic_model: &SemanticModel,
+    traced: &BTreeSet<String>,
+    inferred: Option<LuaType>,
+) -> (String, bool) {
+    let optional = traced.contains("nil");
+    let mut types = traced
+        .iter()
+        .filter(|name| *name != "nil")
+        .filter_map(|name| trace_type_to_lua_type(semantic_model, name))
+        .collect::<Vec<_>>();
+    if types.contains(&LuaType::Number) {
+        types.retain(|typ| *typ != LuaType::Integer);
+    }
+
+    if let Some(inferred) = inferred.map(widen_literal_type)
+        && !matches!(
+            inferred,
+            LuaType::Unknown | LuaType::Any | LuaType::Nil | LuaType::SelfInfer
+        )
+        && types
+            .iter()
+            .all(|typ| semantic_model.type_check(&inferred, typ).is_ok())
+    {
+        types = vec![TypeOps::Remove.apply(semantic_model.get_db(), &inferred, &LuaType::Nil)];
+    }
+
+    let db = semantic_model.get_db();
+    let typ = match types.len() {
+        0 => "any".to_string(),
+        _ => humanize_type(db, &LuaType::from_vec(types), RenderLevel::Simple),
+    };
+    (typ, optional)
+}
+
+/// `type()`/`math.type()` names, or the name of a class known to the workspace.
+fn trace_type_to_lua_type(semantic_model: &SemanticModel, name: &str) -> Option<LuaType> {
+    let typ = match name {
+        "integer" => LuaType::Integer,
+        "number" | "float" => LuaType::Number,
+        "string" => LuaType::String,
+        "boolean" => LuaType::Boolean,
+        "table" => LuaType::Table,
+        "function" => LuaType::Function,
+        "userdata" => LuaType::Userdata,
+        "thread" => LuaType::Thread,
+        _ => {
+            let type_decl = semantic_model.get_db().get_type_index().find_type_decl(
+                semantic_model.get_file_id(),
+                name,
+                None,
+            )?;
+            LuaType::Ref(type_decl.get_id())
+        }
+    };
+    Some(typ)
+}
+
+fn widen_literal_type(typ: LuaType) -> LuaType {
+    match typ {
+        LuaType::IntegerConst(_) | LuaType::DocIntegerConst(_) => LuaType::Integer,
+        LuaType::FloatConst(_) => LuaType::Number,
+        LuaType::StringConst(_) | LuaType::DocStringConst(_) => LuaType::String,
+        LuaType::BooleanConst(_) | LuaType::DocBooleanConst(_) => LuaType::Boolean,
+        LuaType::TableConst(_) => LuaType::Table,
+        typ => typ,
+    }
+}
+
+/// Insert `lines` above the statement at `stat_range`, keeping its indentation.
+fn make_annotation_edit(
+    semantic_model: &SemanticModel,
+    stat_range: TextRange,
+    lines: Vec<String>,
+) -> AnnotationEdit {
+    let indent = get_line_indent(semantic_model, stat_range.start());
+    let new_text = lines
+        .iter()
+        .map(|line| format!("{}\n{}", line, indent))
+        .collect();
+    AnnotationEdit {
+        stat_range,
+        range: TextRange::empty(stat_range.start()),
+        new_text,
+    }
+}
+
+fn get_line_indent(semantic_model: &SemanticModel, offset: TextSize) -> String {
+    let document = semantic_model.get_document();
+    let Some(line_range) = document
+        .get_line(offset)
+        .and_then(|line| document.get_line_range(line))
+    else {
+        return String::new();
+    };
+    document
+        .get_text_slice(line_range)
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect()
+}
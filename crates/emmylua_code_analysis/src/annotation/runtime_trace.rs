@@ -0,0 +1,178 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+};
+
+use serde::Deserialize;
+
+/// A trace entry as written by the recorder: one function with the types seen in one or more
+/// calls.
+#[derive(Deserialize, Debug)]
+struct TraceRecord {
+    /// `debug.getinfo(f).source`, e.g. `@src/legacy.lua`
+    source: String,
+    /// `debug.getinfo(f).linedefined`
+    line: u32,
+    #[serde(default)]
+    params: Vec<TraceParamRecord>,
+    #[serde(default)]
+    returns: Vec<TraceTypes>,
+}
+
+#[derive(Deserialize, Debug)]
+struct TraceParamRecord {
+    name: String,
+    types: TraceTypes,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum TraceTypes {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl TraceTypes {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            TraceTypes::One(typ) => vec![typ],
+            TraceTypes::Many(types) => types,
+        }
+    }
+}
+
+/// The types observed for one function.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceFunction {
+    pub source: String,
+    pub line: u32,
+    /// Observed types of each named parameter, `self` included.
+    pub params: HashMap<String, BTreeSet<String>>,
+    /// Observed types of each return value by position.
+    pub returns: Vec<BTreeSet<String>>,
+}
+
+/// Runtime type traces recorded from test runs, keyed by the line a function is defined on.
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeTrace {
+    functions: HashMap<u32, Vec<TraceFunction>>,
+}
+
+impl RuntimeTrace {
+    /// Parse a trace file: a JSON array of
+    /// `{ "source", "line", "params": [{ "name", "types" }], "returns": [types] }` entries,
+    /// where `types` is a type name or an array of type names.
+    pub fn from_json_str(text: &str) -> Result<Self, String> {
+        let records: Vec<TraceRecord> =
+            serde_json::from_str(text).map_err(|e| format!("Failed to parse trace file: {}", e))?;
+        let mut trace = Self::default();
+        for record in records {
+            trace.add_record(record);
+        }
+        Ok(trace)
+    }
+
+    /// Load and merge trace files, skipping the ones that cannot be read.
+    pub fn load_files(paths: &[String]) -> Self {
+        let mut trace = Self::default();
+        for path in paths {
+            let result = std::fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| Self::from_json_str(&text));
+            match result {
+                Ok(other) => trace.merge(other),
+                Err(e) => log::warn!("Failed to load trace file {}: {}", path, e),
+            }
+        }
+        trace
+    }
+
+    pub fn merge(&mut self, other: RuntimeTrace) {
+        for function in other.functions.into_values().flatten() {
+            self.add_function(function);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+
+    /// Find the traced function defined at `line` (1-based) of `file_path`.
+    pub fn find_function(&self, file_path: &Path, line: u32) -> Option<&TraceFunction> {
+        let file_path = normalize_source(&file_path.to_string_lossy());
+        self.functions
+            .get(&line)?
+            .iter()
+            .find(|function| is_same_source(&file_path, &function.source))
+    }
+
+    fn add_record(&mut self, record: TraceRecord) {
+        let mut function = TraceFunction {
+            source: normalize_source(&record.source),
+            line: record.line,
+            ..Default::default()
+        };
+        for param in record.params {
+            function
+                .params
+                .entry(param.name)
+                .or_default()
+                .extend(param.types.into_vec());
+        }
+        function.returns = record
+            .returns
+            .into_iter()
+            .map(|types| types.into_vec().into_iter().collect())
+            .collect();
+        self.add_function(function);
+    }
+
+    fn add_function(&mut self, function: TraceFunction) {
+        let functions = self.functions.entry(function.line).or_default();
+        let Some(existing) = functions
+            .iter_mut()
+            .find(|existing| existing.source == function.source)
+        else {
+            functions.push(function);
+            return;
+        };
+
+        for (name, types) in function.params {
+            existing.params.entry(name).or_default().extend(types);
+        }
+        // 返回值个数不同的调用, 缺少的位置视为 nil
+        let return_len = existing.returns.len().max(function.returns.len());
+        for i in 0..return_len {
+            if i >= existing.returns.len() {
+                existing.returns.push(BTreeSet::from(["nil".to_string()]));
+            }
+            match function.returns.get(i) {
+                Some(types) => existing.returns[i].extend(types.iter().cloned()),
+                None => {
+                    existing.returns[i].insert("nil".to_string());
+                }
+            }
+        }
+    }
+}
+
+/// `@./src\foo.lua` -> `src/foo.lua`
+fn normalize_source(source: &str) -> String {
+    let source = source
+        .strip_prefix('@')
+        .unwrap_or(source)
+        .replace('\\', "/");
+    let mut source = source.as_str();
+    while let Some(rest) = source.strip_prefix("./") {
+        source = rest;
+    }
+    source.to_string()
+}
+
+/// The trace usually holds a path relative to the directory the tests ran in.
+fn is_same_source(file_path: &str, source: &str) -> bool {
+    file_path == source
+        || file_path
+            .strip_suffix(source)
+            .is_some_and(|prefix| prefix.ends_with('/'))
+}
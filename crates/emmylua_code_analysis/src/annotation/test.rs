@@ -0,0 +1,281 @@
+#[cfg(test)]
+mod test {
+    use crate::{
+        RuntimeTrace, VirtualWorkspace, apply_text_edits, build_doc_stubs, build_trace_annotations,
+    };
+
+    fn annotate(ws: &mut VirtualWorkspace, trace: &str, code: &str) -> String {
+        let trace = RuntimeTrace::from_json_str(trace).unwrap();
+        let file_id = ws.def_file("src/legacy.lua", code);
+        let semantic_model = ws.analysis.compilation.get_semantic_model(file_id).unwrap();
+        let edits = build_trace_annotations(&semantic_model, &trace)
+            .into_iter()
+            .map(|edit| edit.into_text_edit())
+            .collect();
+        apply_text_edits(code, edits)
+    }
+
+    fn stub(ws: &mut VirtualWorkspace, code: &str) -> String {
+        let file_id = ws.def_file("mymod.lua", code);
+        let semantic_model = ws.analysis.compilation.get_semantic_model(file_id).unwrap();
+        let edits = build_doc_stubs(&semantic_model)
+            .into_iter()
+            .map(|edit| edit.into_text_edit())
+            .collect();
+        apply_text_edits(code, edits)
+    }
+
+    #[test]
+    fn test_trace_params_and_returns() {
+        let mut ws = VirtualWorkspace::new();
+        let result = annotate(
+            &mut ws,
+            r#"[
+                {
+                    "source": "@./src/legacy.lua",
+                    "line": 2,
+                    "params": [
+                        { "name": "name", "types": "string" },
+                        { "name": "count", "types": ["integer", "nil"] }
+                    ],
+                    "returns": [["integer", "float"]]
+                },
+                {
+                    "source": "@src/other.lua",
+                    "line": 6,
+                    "params": [{ "name": "x", "types": "string" }]
+                }
+            ]"#,
+            r#"
+            local function repeat_name(name, count)
+                return #name * (count or 1)
+            end
+
+            local function untraced(x)
+                return x
+            end
+            "#,
+        );
+        assert_eq!(
+            result,
+            r#"
+            ---@param name string
+            ---@param count? integer
+            ---@return number
+            local function repeat_name(name, count)
+                return #name * (count or 1)
+            end
+
+            local function untraced(x)
+                return x
+            end
+            "#
+        );
+    }
+
+    #[test]
+    fn test_trace_keeps_existing_docs() {
+        let mut ws = VirtualWorkspace::new();
+        let result = annotate(
+            &mut ws,
+            r#"[
+                {
+                    "source": "src/legacy.lua",
+                    "line": 4,
+                    "params": [
+                        { "name": "a", "types": "integer" },
+                        { "name": "b", "types": "string" }
+                    ],
+                    "returns": ["string"]
+                }
+            ]"#,
+            r#"
+            ---@param a number
+            ---@return string
+            local function f(a, b)
+                return tostring(a) .. b
+            end
+            "#,
+        );
+        assert_eq!(
+            result,
+            r#"
+            ---@param a number
+            ---@return string
+            ---@param b string
+            local function f(a, b)
+                return tostring(a) .. b
+            end
+            "#
+        );
+    }
+
+    #[test]
+    fn test_trace_class_names() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def(
+            r#"
+            ---@class Player
+            ---@field name string
+            "#,
+        );
+        let result = annotate(
+            &mut ws,
+            r#"[
+                {
+                    "source": "src/legacy.lua",
+                    "line": 2,
+                    "params": [
+                        { "name": "player", "types": "Player" },
+                        { "name": "opts", "types": ["Unknown.Class", "nil"] }
+                    ],
+                    "returns": []
+                }
+            ]"#,
+            r#"
+            function greet(player, opts)
+                print(player.name)
+            end
+            "#,
+        );
+        assert_eq!(
+            result,
+            r#"
+            ---@param player Player
+            ---@param opts? any
+            function greet(player, opts)
+                print(player.name)
+            end
+            "#
+        );
+    }
+
+    #[test]
+    fn test_merge_trace_files() {
+        let mut trace = RuntimeTrace::from_json_str(
+            r#"[{ "source": "@a.lua", "line": 1, "params": [{ "name": "x", "types": "string" }], "returns": ["string"] }]"#,
+        )
+        .unwrap();
+        trace.merge(
+            RuntimeTrace::from_json_str(
+                r#"[{ "source": "@a.lua", "line": 1, "params": [{ "name": "x", "types": "integer" }], "returns": ["string", "integer"] }]"#,
+            )
+            .unwrap(),
+        );
+        let function = trace
+            .find_function(std::path::Path::new("/work/a.lua"), 1)
+            .unwrap();
+        assert_eq!(function.params["x"].len(), 2);
+        assert_eq!(function.returns.len(), 2);
+        assert!(function.returns[1].contains("nil"));
+        assert!(
+            trace
+                .find_function(std::path::Path::new("/work/ba.lua"), 1)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_doc_stubs_for_functions() {
+        let mut ws = VirtualWorkspace::new();
+        let result = stub(
+            &mut ws,
+            r#"
+            local M = {}
+
+            function M.add(a, b, _)
+                return a + 1, "sum"
+            end
+
+            ---@param name string
+            function M.greet(name, ...)
+                print(name)
+            end
+
+            ---@return integer
+            local function count(t)
+                return #t
+            end
+
+            return M
+            "#,
+        );
+        assert_eq!(
+            result,
+            r#"
+            local M = {}
+
+            ---@param a any
+            ---@param b any
+            ---@return integer
+            ---@return string
+            function M.add(a, b, _)
+                return a + 1, "sum"
+            end
+
+            ---@param name string
+            ---@param ... any
+            function M.greet(name, ...)
+                print(name)
+            end
+
+            ---@return integer
+            ---@param t any
+            local function count(t)
+                return #t
+            end
+
+            return M
+            "#
+        );
+    }
+
+    #[test]
+    fn test_doc_stubs_for_module_tables() {
+        let mut ws = VirtualWorkspace::new();
+        let result = stub(
+            &mut ws,
+            r#"
+            local M = {}
+
+            M.config = {
+                name = "default",
+                retries = 3,
+                verbose = false,
+            }
+
+            ---@type table<string, number>
+            M.weights = { a = 1 }
+
+            local other = {}
+            other.data = { x = 1 }
+
+            return M
+            "#,
+        );
+        assert_eq!(
+            result,
+            r#"
+            local M = {}
+
+            ---@class mymod.config
+            ---@field name string
+            ---@field retries integer
+            ---@field verbose boolean
+            M.config = {
+                name = "default",
+                retries = 3,
+                verbose = false,
+            }
+
+            ---@type table<string, number>
+            M.weights = { a = 1 }
+
+            local other = {}
+            other.data = { x = 1 }
+
+            return M
+            "#
+        );
+    }
+}
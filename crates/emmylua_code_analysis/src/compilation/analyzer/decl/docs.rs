@@ -55,6 +55,9 @@ fn get_type_flag_value(
                 "constructor" => {
                     attr |= LuaTypeFlag::Constructor;
                 }
+                "opaque" => {
+                    attr |= LuaTypeFlag::Opaque;
+                }
                 "public" => {
                     attr |= LuaTypeFlag::Public;
                 }
@@ -4,9 +4,9 @@ use emmylua_parser::{
     LuaAst, LuaAstNode, LuaComment, LuaDocBinaryType, LuaDocConditionalType,
     LuaDocDescriptionOwner, LuaDocFuncType, LuaDocGenericDecl, LuaDocGenericDeclList,
     LuaDocGenericType, LuaDocIndexAccessType, LuaDocMappedType, LuaDocMultiLineUnionType,
-    LuaDocObjectFieldKey, LuaDocObjectType, LuaDocStrTplType, LuaDocType, LuaDocUnaryType,
-    LuaDocVariadicType, LuaLiteralToken, LuaSyntaxKind, LuaTypeBinaryOperator,
-    LuaTypeUnaryOperator, LuaVarExpr, NumberResult,
+    LuaDocObjectFieldKey, LuaDocObjectType, LuaDocStrTplType, LuaDocTemplateLiteralPart,
+    LuaDocTemplateLiteralType, LuaDocType, LuaDocUnaryType, LuaDocVariadicType, LuaLiteralToken,
+    LuaSyntaxKind, LuaTypeBinaryOperator, LuaTypeUnaryOperator, LuaVarExpr, NumberResult,
 };
 use rowan::TextRange;
 use smol_str::SmolStr;
@@ -193,6 +193,9 @@ pub fn infer_type(analyzer: &mut DocTypeAnalyzeContext<'_>, node: LuaDocType) ->
         LuaDocType::StrTpl(str_tpl) => {
             return infer_str_tpl(analyzer, str_tpl, &node);
         }
+        LuaDocType::TemplateLiteral(template_literal) => {
+            return infer_template_literal_type(analyzer, template_literal);
+        }
         LuaDocType::Variadic(variadic_type) => {
             return infer_variadic_type(analyzer, variadic_type).unwrap_or(LuaType::Unknown);
         }
@@ -496,6 +499,23 @@ fn infer_special_generic_type(
                 LuaAliasCallType::new(LuaAliasCallKind::Merge, params).into(),
             ));
         }
+        "Uppercase" | "Lowercase" | "Capitalize" => {
+            let mut params = Vec::new();
+            for param in generic_type.get_generic_types()?.get_types() {
+                params.push(infer_type(analyzer, param));
+            }
+            if params.len() != 1 {
+                return Some(LuaType::Unknown);
+            }
+            let call_kind = match name {
+                "Uppercase" => LuaAliasCallKind::Uppercase,
+                "Lowercase" => LuaAliasCallKind::Lowercase,
+                _ => LuaAliasCallKind::Capitalize,
+            };
+            return Some(LuaType::Call(
+                LuaAliasCallType::new(call_kind, params).into(),
+            ));
+        }
         _ => {}
     }
 
@@ -862,6 +882,25 @@ fn infer_str_tpl(
     LuaType::Unknown
 }
 
+fn infer_template_literal_type(
+    analyzer: &mut DocTypeAnalyzeContext<'_>,
+    template_literal: &LuaDocTemplateLiteralType,
+) -> LuaType {
+    let mut operands = Vec::new();
+    for part in template_literal.get_parts() {
+        match part {
+            LuaDocTemplateLiteralPart::Text(text) => {
+                operands.push(LuaType::DocStringConst(SmolStr::new(text).into()));
+            }
+            LuaDocTemplateLiteralPart::Type(doc_type) => {
+                operands.push(infer_type(analyzer, doc_type));
+            }
+        }
+    }
+
+    LuaType::Call(LuaAliasCallType::new(LuaAliasCallKind::Template, operands).into())
+}
+
 fn infer_variadic_type(
     analyzer: &mut DocTypeAnalyzeContext<'_>,
     variadic_type: &LuaDocVariadicType,
@@ -991,6 +1030,7 @@ fn infer_mapped_type(
             value_type,
             mapped_type.is_readonly(),
             mapped_type.is_optional(),
+            mapped_type.is_required(),
         )
         .into(),
     ))
@@ -1,8 +1,8 @@
-use emmylua_parser::{LuaAstNode, LuaDocGenericDeclList, LuaDocType};
+use emmylua_parser::{LuaAstNode, LuaDocGenericDecl, LuaDocGenericDeclList, LuaDocType};
 use smol_str::SmolStr;
 
 use crate::{
-    FileId, GenericParam,
+    FileId, GenericParam, GenericVariance,
     compilation::analyzer::AnalyzeContext,
     db_index::{DbIndex, LuaType, WorkspaceId},
     semantic::complete_type_generic_args_in_type,
@@ -68,6 +68,7 @@ fn normalize_generic_params(db: &DbIndex, params: &[GenericParam]) -> Vec<Generi
                 param.is_const,
                 param.attributes.clone(),
             )
+            .with_variance(param.variance)
         })
         .collect()
 }
@@ -117,7 +118,8 @@ fn resolve_generic_params(
             default_type,
             generic_decl.has_const_modifier(),
             None,
-        );
+        )
+        .with_variance(get_generic_variance(&generic_decl));
         let _ = generic_index.update_generic_param(tpl_id, param.clone());
         params.push(param);
     }
@@ -125,6 +127,16 @@ fn resolve_generic_params(
     params
 }
 
+fn get_generic_variance(generic_decl: &LuaDocGenericDecl) -> GenericVariance {
+    if generic_decl.has_out_modifier() {
+        GenericVariance::Covariant
+    } else if generic_decl.has_in_modifier() {
+        GenericVariance::Contravariant
+    } else {
+        GenericVariance::Default
+    }
+}
+
 fn infer_header_type(
     db: &mut DbIndex,
     file_id: FileId,
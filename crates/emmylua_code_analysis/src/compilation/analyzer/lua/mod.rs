@@ -6,6 +6,8 @@ mod metatable;
 mod module;
 mod stats;
 
+use std::sync::Arc;
+
 use hashbrown::HashMap;
 
 use closure::analyze_closure;
@@ -111,8 +113,8 @@ impl LuaAnalyzer<'_> {
     }
 
     #[allow(unused)]
-    pub fn get_emmyrc(&self) -> &Emmyrc {
-        self.db.get_emmyrc()
+    pub fn get_emmyrc(&self) -> Arc<Emmyrc> {
+        self.db.get_file_emmyrc(&self.file_id)
     }
 }
 
@@ -1,7 +1,7 @@
 mod analyzer;
 mod test;
 
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 pub(crate) use analyzer::{analyze_func_body_returns_with, analyze_return_point};
 
@@ -45,7 +45,7 @@ impl LuaCompilation {
             file_id,
             &self.db,
             cache,
-            self.emmyrc.clone(),
+            self.db.get_file_emmyrc(&file_id),
             tree.get_chunk_node(),
         ))
     }
@@ -89,4 +89,8 @@ impl LuaCompilation {
         self.emmyrc = config.clone();
         self.db.update_config(config);
     }
+
+    pub fn update_scoped_configs(&mut self, scoped_configs: Vec<(PathBuf, Arc<Emmyrc>)>) {
+        self.db.update_scoped_configs(scoped_configs);
+    }
 }
@@ -4611,4 +4611,62 @@ _2 = a[1]
             "#,
         ));
     }
+
+    #[test]
+    fn test_enum_eq_false_branch_removes_member() {
+        let mut ws = VirtualWorkspace::new();
+
+        ws.def(
+            r#"
+            ---@enum Color
+            Color = { Red = 1, Green = 2, Blue = 3 }
+
+            ---@param color Color
+            function test(color)
+                if color == Color.Red then
+                    return
+                end
+                remaining = color
+
+                if color == Color.Green then
+                else
+                    last = color
+                end
+
+                if color == Color.Green then
+                elseif color == Color.Blue then
+                else
+                    exhausted = color
+                end
+            end
+            "#,
+        );
+
+        // 剩余成员仍然显示为枚举类型
+        assert_eq!(ws.expr_ty("remaining"), ws.ty("Color"));
+        assert_eq!(ws.expr_ty("last"), ws.ty("Color"));
+        assert_eq!(ws.expr_ty("exhausted"), ws.ty("never"));
+    }
+
+    #[test]
+    fn test_enum_eq_unknown_value_keeps_enum() {
+        let mut ws = VirtualWorkspace::new();
+
+        ws.def(
+            r#"
+            ---@enum Color
+            Color = { Red = 1, Green = 2, Blue = 3 }
+
+            ---@param color Color
+            function test(color)
+                if color == 5 then
+                    return
+                end
+                kept = color
+            end
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("kept"), ws.ty("Color"));
+    }
 }
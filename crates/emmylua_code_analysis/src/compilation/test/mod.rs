@@ -18,6 +18,7 @@ mod mathlib_test;
 mod member_infer_test;
 mod metatable_test;
 mod module_test;
+mod moniker_test;
 mod multi_return;
 mod out_of_order;
 mod overload_field;
@@ -28,6 +29,8 @@ mod return_overload_generic_test;
 mod return_unwrap_test;
 mod static_cal_cmp;
 mod syntax_error_test;
+mod template_literal_test;
 mod tuple_test;
 mod type_check_test;
 mod unpack_test;
+mod utility_type_test;
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod test {
+    use crate::{
+        LuaMemberKey, LuaMemberOwner, LuaSemanticDeclId, LuaType, LuaTypeDeclId, VirtualWorkspace,
+        get_moniker_identifier,
+    };
+
+    #[test]
+    fn test_moniker_identifier() {
+        let mut ws = VirtualWorkspace::new();
+        let file_id = ws.def_file(
+            "sdk/shape.lua",
+            r#"
+            ---@class Shape
+            local Shape = {}
+
+            function Shape:area()
+            end
+
+            local M = {}
+
+            function M.new()
+            end
+
+            CONFIG = 1
+
+            return M
+            "#,
+        );
+        let db = ws.analysis.compilation.get_db();
+        let member_moniker = |owner: LuaMemberOwner, name: &str| {
+            let members = db.get_member_index().get_members(&owner)?;
+            let member = members
+                .iter()
+                .find(|member| member.get_key() == &LuaMemberKey::Name(name.into()))?;
+            get_moniker_identifier(db, &LuaSemanticDeclId::Member(member.get_id()))
+        };
+
+        let shape_id = LuaTypeDeclId::global("Shape");
+        assert_eq!(
+            get_moniker_identifier(db, &shape_id.clone().into()).as_deref(),
+            Some("sdk.shape:Shape")
+        );
+        assert_eq!(
+            member_moniker(LuaMemberOwner::Type(shape_id), "area").as_deref(),
+            Some("sdk.shape:Shape.area")
+        );
+
+        let Some(LuaType::TableConst(module_table)) = db
+            .get_module_index()
+            .get_module(file_id)
+            .and_then(|module| module.export_type.clone())
+        else {
+            panic!("module should export a table");
+        };
+        assert_eq!(
+            member_moniker(LuaMemberOwner::Element(module_table), "new").as_deref(),
+            Some("sdk.shape:new")
+        );
+
+        let config_decl = db.get_global_index().get_global_decl_ids("CONFIG").unwrap()[0];
+        assert_eq!(
+            get_moniker_identifier(db, &config_decl.into()).as_deref(),
+            Some("sdk.shape:CONFIG")
+        );
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod test {
-    use crate::{DiagnosticCode, VirtualWorkspace};
+    use crate::{DiagnosticCode, LuaType, VirtualWorkspace};
 
     #[test]
     fn test_cmp() {
@@ -62,4 +62,30 @@ mod test {
         "#,
         ));
     }
+
+    #[test]
+    fn test_const_fold() {
+        let mut ws = VirtualWorkspace::new();
+
+        ws.def(
+            r#"
+        local A <const> = 3
+        local NAME = "emmy"
+        a = 60 * 5
+        b = A << 2
+        c = NAME .. 1
+        d = 1 .. 2
+        e = #NAME
+        f = -(A + 4)
+        "#,
+        );
+        assert_eq!(ws.expr_ty("a"), LuaType::IntegerConst(300));
+        assert_eq!(ws.expr_ty("b"), LuaType::IntegerConst(12));
+        let c = ws.expr_ty("c");
+        assert_eq!(ws.humanize_type(c), "\"emmy1\"");
+        let d = ws.expr_ty("d");
+        assert_eq!(ws.humanize_type(d), "\"12\"");
+        assert_eq!(ws.expr_ty("e"), LuaType::IntegerConst(4));
+        assert_eq!(ws.expr_ty("f"), LuaType::IntegerConst(-7));
+    }
 }
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod test {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    #[test]
+    fn test_template_literal_over_literal_union() {
+        let mut ws = VirtualWorkspace::new();
+
+        ws.def(
+            r#"
+            ---@alias EventKey "click" | "hover"
+
+            ---@alias EventName `on${Capitalize<EventKey>}`
+
+            ---@param name EventName
+            function listen(name)
+            end
+            "#,
+        );
+
+        assert!(ws.has_no_diagnostic(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            listen("onClick")
+            listen("onHover")
+            "#
+        ));
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            listen("onclick")
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_template_literal_generic_instantiate() {
+        let mut ws = VirtualWorkspace::new();
+
+        ws.def(
+            r#"
+            ---@generic K: string
+            ---@param key K
+            ---@return `on${K}_changed`
+            function event_of(key)
+            end
+
+            ---@generic T: string
+            ---@param s T
+            ---@return Uppercase<T>
+            function upper(s)
+            end
+
+            A = event_of("name")
+            B = upper("abc")
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("A"), ws.ty("'onname_changed'"));
+        assert_eq!(ws.expr_ty("B"), ws.ty("'ABC'"));
+    }
+
+    #[test]
+    fn test_template_literal_pattern() {
+        let mut ws = VirtualWorkspace::new();
+
+        ws.def(
+            r#"
+            ---@param id `user_${integer}`
+            function find_user(id)
+            end
+            "#,
+        );
+
+        assert!(ws.has_no_diagnostic(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            find_user("user_42")
+            "#
+        ));
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            find_user("user_abc")
+            "#
+        ));
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            ---@type string
+            local id
+            find_user(id)
+            "#
+        ));
+    }
+}
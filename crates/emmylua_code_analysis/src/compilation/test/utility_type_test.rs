@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod test {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    #[test]
+    fn test_pick_omit_on_class() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r#"
+            ---@class User
+            ---@field id integer
+            ---@field name string
+            ---@field email? string
+
+            ---@type Pick<User, 'id' | 'name'>
+            Picked = nil
+
+            ---@type Omit<User, 'email'>
+            Omitted = nil
+            "#,
+        );
+
+        let picked = ws.expr_ty("Picked");
+        assert_eq!(
+            ws.humanize_type_detailed(picked),
+            "Pick<User,(\"id\"|\"name\")> = { id: integer, name: string }"
+        );
+        let omitted = ws.expr_ty("Omitted");
+        assert_eq!(
+            ws.humanize_type_detailed(omitted),
+            "Omit<User,\"email\"> = { id: integer, name: string }"
+        );
+    }
+
+    #[test]
+    fn test_pick_inherited_class_field() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r#"
+            ---@class Entity
+            ---@field id integer
+
+            ---@class Admin: Entity
+            ---@field level integer
+
+            ---@type Pick<Admin, 'id'>
+            Picked = nil
+
+            ---@type Partial<Admin>
+            Part = nil
+            "#,
+        );
+
+        let picked = ws.expr_ty("Picked");
+        assert_eq!(
+            ws.humanize_type_detailed(picked),
+            "Pick<Admin,\"id\"> = { id: integer }"
+        );
+        let part = ws.expr_ty("Part");
+        assert_eq!(
+            ws.humanize_type_detailed(part),
+            "Partial<Admin> = { id: integer?, level: integer? }"
+        );
+    }
+
+    #[test]
+    fn test_required_record_non_nullable() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r#"
+            ---@class Options
+            ---@field timeout? integer
+            ---@field retries? integer
+
+            ---@type Required<Options>
+            Req = nil
+
+            ---@type Record<'a' | 'b', number>
+            Rec = nil
+
+            ---@type Record<string, Options>
+            Dict = nil
+
+            ---@type NonNullable<string?>
+            NN = nil
+            "#,
+        );
+
+        let req = ws.expr_ty("Req");
+        assert_eq!(
+            ws.humanize_type_detailed(req),
+            "Required<Options> = { retries: integer, timeout: integer }"
+        );
+        let rec = ws.expr_ty("Rec");
+        assert_eq!(
+            ws.humanize_type_detailed(rec),
+            "Record<(\"a\"|\"b\"),number> = { a: number, b: number }"
+        );
+        let dict = ws.expr_ty("Dict");
+        assert_eq!(
+            ws.humanize_type_detailed(dict),
+            "Record<string,Options> = { [string]: Options }"
+        );
+        let non_nullable = ws.expr_ty("NN");
+        assert_eq!(
+            ws.humanize_type_detailed(non_nullable),
+            "NonNullable<string?> = string"
+        );
+    }
+
+    #[test]
+    fn test_readonly_mapped_type() {
+        let mut ws = VirtualWorkspace::new_with_init_std_lib();
+
+        ws.def(
+            r#"
+            ---@class Point
+            ---@field x number
+            ---@field y number
+            "#,
+        );
+
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::ReadOnly,
+            r#"
+            ---@type Readonly<Point>
+            local p = { x = 1, y = 2 }
+            p.x = 3
+            "#
+        ));
+        assert!(ws.has_no_diagnostic(
+            DiagnosticCode::ReadOnly,
+            r#"
+            ---@type Readonly<Point>
+            local p = { x = 1, y = 2 }
+            local x = p.x
+            "#
+        ));
+    }
+}
@@ -0,0 +1,492 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use walkdir::WalkDir;
+
+use crate::config::{Emmyrc, EmmyrcWorkspacePathItem};
+
+// 自动检测时最多扫描的工作区文件数量
+const DETECT_FILE_LIMIT: usize = 2000;
+// 自动检测 `words` 时跳过过大的文件
+const DETECT_FILE_SIZE_LIMIT: u64 = 512 * 1024;
+
+/// `config.json` of an addon in the LLS-Addons layout.
+#[derive(Deserialize, Debug, Default)]
+struct LlsAddonConfig {
+    #[serde(default)]
+    name: Option<String>,
+    /// Lua patterns matched against the content of workspace files
+    #[serde(default)]
+    words: Vec<String>,
+    /// Lua patterns matched against the relative path or name of workspace files
+    #[serde(default)]
+    files: Vec<String>,
+    #[serde(default)]
+    settings: HashMap<String, Value>,
+}
+
+struct LlsAddon {
+    /// directory containing `config.json` and `library/`
+    module_dir: PathBuf,
+    /// directory that `${addons}` refers to
+    addons_root: PathBuf,
+    config: LlsAddonConfig,
+}
+
+impl LlsAddon {
+    /// `addon/config.json` or the LLS-Addons repository layout `addon/module/config.json`
+    fn load(dir: &Path) -> Option<Self> {
+        let (module_dir, addon_dir) = if dir.join("config.json").is_file() {
+            let addon_dir = if dir.file_name().is_some_and(|name| name == "module") {
+                dir.parent().unwrap_or(dir)
+            } else {
+                dir
+            };
+            (dir.to_path_buf(), addon_dir)
+        } else if dir.join("module").join("config.json").is_file() {
+            (dir.join("module"), dir)
+        } else {
+            return None;
+        };
+
+        let config_path = module_dir.join("config.json");
+        let content = fs::read_to_string(&config_path).ok()?;
+        let config = match serde_json::from_str::<LlsAddonConfig>(&content) {
+            Ok(config) => config,
+            Err(e) => {
+                log::error!("Failed to parse addon config {:?}: {}", config_path, e);
+                return None;
+            }
+        };
+
+        Some(Self {
+            addons_root: addon_dir.parent().unwrap_or(addon_dir).to_path_buf(),
+            module_dir,
+            config,
+        })
+    }
+
+    fn get_name(&self) -> String {
+        match &self.config.name {
+            Some(name) => name.clone(),
+            None => self.module_dir.to_string_lossy().to_string(),
+        }
+    }
+
+    fn apply(&self, emmyrc: &mut Emmyrc) {
+        log::info!("apply addon: {}", self.get_name());
+        let library_dir = self.module_dir.join("library");
+        if library_dir.is_dir() {
+            emmyrc
+                .workspace
+                .library
+                .push(EmmyrcWorkspacePathItem::Path(path_to_string(&library_dir)));
+        }
+
+        for (key, value) in &self.config.settings {
+            let key = key.strip_prefix("Lua.").unwrap_or(key);
+            match key {
+                "runtime.version" => {
+                    // 用户未指定版本时才使用 addon 的版本
+                    if !emmyrc.runtime.version_configured
+                        && let Ok(version) = serde_json::from_value(value.clone())
+                    {
+                        emmyrc.runtime.version = version;
+                        emmyrc.runtime.version_configured = true;
+                    }
+                }
+                "diagnostics.globals" => {
+                    for global in get_string_list(value) {
+                        if !emmyrc.diagnostics.globals.contains(&global) {
+                            emmyrc.diagnostics.globals.push(global);
+                        }
+                    }
+                }
+                "workspace.library" => {
+                    for library in get_string_list(value) {
+                        let path = self.resolve_path(&library);
+                        emmyrc
+                            .workspace
+                            .library
+                            .push(EmmyrcWorkspacePathItem::Path(path));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn resolve_path(&self, path: &str) -> String {
+        let path = path.replace("${addons}", &path_to_string(&self.addons_root));
+        let path_buf = PathBuf::from(&path);
+        if path_buf.is_absolute() {
+            path
+        } else {
+            path_to_string(&self.module_dir.join(path_buf))
+        }
+    }
+}
+
+/// Apply the addons listed in `workspace.addons`.
+///
+/// An entry is either a single addon directory, which is always applied, or a directory of
+/// addons (such as an LLS-Addons checkout), where an addon is applied only when its `words`
+/// or `files` rules match the workspace.
+pub fn apply_addons(emmyrc: &mut Emmyrc, workspace_root: &Path) {
+    let mut detector = AddonDetector::new(workspace_root, &emmyrc.workspace.ignore_dir);
+    for addon_path in emmyrc.workspace.addons.clone() {
+        let addon_path = PathBuf::from(addon_path);
+        if let Some(addon) = LlsAddon::load(&addon_path) {
+            addon.apply(emmyrc);
+            continue;
+        }
+
+        let Ok(entries) = fs::read_dir(&addon_path) else {
+            log::warn!("addon path {:?} is not a directory", addon_path);
+            continue;
+        };
+        let mut addon_dirs = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_dir())
+            .collect::<Vec<_>>();
+        addon_dirs.sort();
+        for addon_dir in addon_dirs {
+            if let Some(addon) = LlsAddon::load(&addon_dir)
+                && detector.is_match(&addon.config)
+            {
+                addon.apply(emmyrc);
+            }
+        }
+    }
+}
+
+struct AddonDetector {
+    workspace_root: PathBuf,
+    ignore_dir: Vec<PathBuf>,
+    files: Option<Vec<DetectFile>>,
+}
+
+struct DetectFile {
+    path: PathBuf,
+    relative_path: String,
+    file_name: String,
+    content: Option<Option<String>>,
+}
+
+impl AddonDetector {
+    fn new(workspace_root: &Path, ignore_dir: &[String]) -> Self {
+        Self {
+            workspace_root: workspace_root.to_path_buf(),
+            ignore_dir: ignore_dir.iter().map(PathBuf::from).collect(),
+            files: None,
+        }
+    }
+
+    fn is_match(&mut self, config: &LlsAddonConfig) -> bool {
+        let file_patterns = compile_lua_patterns(&config.files);
+        let word_patterns = compile_lua_patterns(&config.words);
+        if file_patterns.is_empty() && word_patterns.is_empty() {
+            return false;
+        }
+
+        let files = self.get_files();
+        if files.iter().any(|file| {
+            file_patterns
+                .iter()
+                .any(|re| re.is_match(&file.relative_path) || re.is_match(&file.file_name))
+        }) {
+            return true;
+        }
+
+        if word_patterns.is_empty() {
+            return false;
+        }
+        for file in files.iter_mut() {
+            let content = file.content.get_or_insert_with(|| {
+                let size = fs::metadata(&file.path).map(|m| m.len()).unwrap_or(0);
+                if size > DETECT_FILE_SIZE_LIMIT {
+                    return None;
+                }
+                fs::read_to_string(&file.path).ok()
+            });
+            if let Some(content) = content
+                && word_patterns.iter().any(|re| re.is_match(content))
+            {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn get_files(&mut self) -> &mut Vec<DetectFile> {
+        let workspace_root = &self.workspace_root;
+        let ignore_dir = &self.ignore_dir;
+        self.files.get_or_insert_with(|| {
+            WalkDir::new(workspace_root)
+                .into_iter()
+                .filter_entry(|entry| {
+                    let is_hidden =
+                        entry.depth() > 0 && entry.file_name().to_string_lossy().starts_with('.');
+                    !is_hidden && !ignore_dir.iter().any(|dir| entry.path().starts_with(dir))
+                })
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| {
+                    entry.file_type().is_file()
+                        && entry.path().extension().is_some_and(|ext| ext == "lua")
+                })
+                .take(DETECT_FILE_LIMIT)
+                .map(|entry| {
+                    let path = entry.path().to_path_buf();
+                    let relative_path = path
+                        .strip_prefix(workspace_root)
+                        .unwrap_or(&path)
+                        .to_string_lossy()
+                        .replace('\\', "/");
+                    DetectFile {
+                        file_name: entry.file_name().to_string_lossy().to_string(),
+                        relative_path,
+                        path,
+                        content: None,
+                    }
+                })
+                .collect()
+        })
+    }
+}
+
+fn compile_lua_patterns(patterns: &[String]) -> Vec<Regex> {
+    patterns
+        .iter()
+        .filter_map(|pattern| {
+            let regex = lua_pattern_to_regex(pattern);
+            if regex.is_none() {
+                log::warn!("unsupported addon pattern: {}", pattern);
+            }
+            regex
+        })
+        .collect()
+}
+
+/// Translate a Lua pattern into an equivalent regex. `%b` and `%f` are not supported.
+fn lua_pattern_to_regex(pattern: &str) -> Option<Regex> {
+    let mut regex = String::new();
+    let chars = pattern.chars().collect::<Vec<_>>();
+    let mut i = 0;
+    while i < chars.len() {
+        let ch = chars[i];
+        match ch {
+            '^' if i == 0 => regex.push('^'),
+            '$' if i == chars.len() - 1 => regex.push('$'),
+            '%' => {
+                let next = *chars.get(i + 1)?;
+                if matches!(next, 'b' | 'f') {
+                    return None;
+                }
+                regex.push_str(&lua_class_to_regex(next, false));
+                i += 1;
+            }
+            '[' => {
+                regex.push('[');
+                i += 1;
+                if chars.get(i) == Some(&'^') {
+                    regex.push('^');
+                    i += 1;
+                }
+                let start = i;
+                while i < chars.len() && (chars[i] != ']' || i == start) {
+                    if chars[i] == '%' {
+                        regex.push_str(&lua_class_to_regex(*chars.get(i + 1)?, true));
+                        i += 2;
+                        continue;
+                    }
+                    if matches!(chars[i], '\\' | '[' | '&' | '~') {
+                        regex.push('\\');
+                    }
+                    regex.push(chars[i]);
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return None;
+                }
+                regex.push(']');
+            }
+            '-' => regex.push_str("*?"),
+            '.' | '*' | '+' | '?' | '(' | ')' => regex.push(ch),
+            _ => regex.push_str(&regex::escape(&ch.to_string())),
+        }
+        i += 1;
+    }
+
+    Regex::new(&regex).ok()
+}
+
+fn lua_class_to_regex(class: char, in_set: bool) -> String {
+    let (positive, negative) = match class.to_ascii_lowercase() {
+        'a' => ("A-Za-z", "^A-Za-z"),
+        'd' => ("0-9", "^0-9"),
+        'l' => ("a-z", "^a-z"),
+        's' => (r"\s", r"\S"),
+        'u' => ("A-Z", "^A-Z"),
+        'w' => ("A-Za-z0-9", "^A-Za-z0-9"),
+        'x' => ("0-9A-Fa-f", "^0-9A-Fa-f"),
+        'p' => ("[:punct:]", "^[:punct:]"),
+        'c' => ("[:cntrl:]", "^[:cntrl:]"),
+        _ => return regex::escape(&class.to_string()),
+    };
+
+    let is_negative = class.is_ascii_uppercase();
+    match (in_set, is_negative) {
+        (_, false) if positive.starts_with('\\') => positive.to_string(),
+        (_, true) if negative.starts_with('\\') => negative.to_string(),
+        (true, false) => positive.to_string(),
+        // 集合内无法表达取反的类, 退化为任意字符
+        (true, true) => r"\s\S".to_string(),
+        (false, false) => format!("[{positive}]"),
+        (false, true) => format!("[{negative}]"),
+    }
+}
+
+fn get_string_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) => vec![s.clone()],
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(|s| s.to_string()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn path_to_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{EmmyrcLuaVersion, load_configs};
+
+    fn create_temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "emmylua_addon_test_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_addon(dir: &Path, config: &str) {
+        fs::create_dir_all(dir.join("module").join("library")).unwrap();
+        fs::write(dir.join("module").join("config.json"), config).unwrap();
+    }
+
+    #[test]
+    fn test_lua_pattern_to_regex() {
+        let re = lua_pattern_to_regex(r#"require[%s%(\"']+love"#).unwrap();
+        assert!(re.is_match(r#"local x = require("love.graphics")"#));
+        assert!(!re.is_match("require_love"));
+
+        let re = lua_pattern_to_regex("^main%.lua$").unwrap();
+        assert!(re.is_match("main.lua"));
+        assert!(!re.is_match("mainxlua"));
+
+        let re = lua_pattern_to_regex("ngx%.%w+").unwrap();
+        assert!(re.is_match("ngx.say('hi')"));
+        assert!(lua_pattern_to_regex("%b()").is_none());
+    }
+
+    #[test]
+    fn test_apply_single_addon() {
+        let root = create_temp_dir("single");
+        let addon_dir = root.join("addons").join("love2d");
+        write_addon(
+            &addon_dir,
+            r#"{
+                "name": "LÖVE",
+                "words": ["love%.%w+"],
+                "settings": {
+                    "Lua.runtime.version": "LuaJIT",
+                    "Lua.diagnostics.globals": ["love"],
+                    "Lua.workspace.library": ["${addons}/shared/library"]
+                }
+            }"#,
+        );
+        let workspace = root.join("workspace");
+        fs::create_dir_all(&workspace).unwrap();
+
+        let mut emmyrc = Emmyrc::default();
+        emmyrc.workspace.addons = vec![path_to_string(&addon_dir)];
+        apply_addons(&mut emmyrc, &workspace);
+
+        assert_eq!(emmyrc.runtime.version, EmmyrcLuaVersion::LuaJIT);
+        assert_eq!(emmyrc.diagnostics.globals, vec!["love".to_string()]);
+        let libraries = emmyrc
+            .workspace
+            .library
+            .iter()
+            .map(|it| PathBuf::from(it.get_path()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            libraries,
+            vec![
+                addon_dir.join("module").join("library"),
+                root.join("addons").join("shared").join("library"),
+            ]
+        );
+
+        // 用户显式配置的版本即使等于默认值也不会被 addon 覆盖
+        let mut emmyrc = load_configs(
+            Vec::new(),
+            Some(vec![
+                serde_json::json!({ "runtime": { "version": "LuaLatest" } }),
+            ]),
+        );
+        emmyrc.workspace.addons = vec![path_to_string(&addon_dir)];
+        apply_addons(&mut emmyrc, &workspace);
+        assert_eq!(emmyrc.runtime.version, EmmyrcLuaVersion::LuaLatest);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_detect_addons_in_collection() {
+        let root = create_temp_dir("collection");
+        let addons = root.join("addons");
+        write_addon(
+            &addons.join("love2d"),
+            r#"{ "words": ["love%.%w+"], "settings": { "Lua.diagnostics.globals": ["love"] } }"#,
+        );
+        write_addon(
+            &addons.join("openresty"),
+            r#"{ "words": ["ngx%.%w+"], "settings": { "Lua.diagnostics.globals": ["ngx"] } }"#,
+        );
+        write_addon(
+            &addons.join("busted"),
+            r#"{ "files": ["_spec%.lua$"], "settings": { "Lua.diagnostics.globals": ["describe"] } }"#,
+        );
+        let workspace = root.join("workspace");
+        fs::create_dir_all(workspace.join("spec")).unwrap();
+        fs::write(workspace.join("main.lua"), "function love.draw() end\n").unwrap();
+        fs::write(workspace.join("spec").join("main_spec.lua"), "").unwrap();
+
+        let mut emmyrc = Emmyrc::default();
+        emmyrc.workspace.addons = vec![path_to_string(&addons)];
+        apply_addons(&mut emmyrc, &workspace);
+
+        assert_eq!(
+            emmyrc.diagnostics.globals,
+            vec!["describe".to_string(), "love".to_string()]
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -83,10 +83,14 @@ pub fn load_configs_raw(config_files: Vec<PathBuf>, partial_emmyrcs: Option<Vec<
 
 pub fn load_configs(config_files: Vec<PathBuf>, partial_emmyrcs: Option<Vec<Value>>) -> Emmyrc {
     let emmyrc_json_value = load_configs_raw(config_files, partial_emmyrcs);
-    serde_json::from_value(emmyrc_json_value).unwrap_or_else(|err| {
+    // 记录用户是否显式指定了版本, addon 和 rockspec 只在未指定时才设置版本
+    let version_configured = emmyrc_json_value.pointer("/runtime/version").is_some();
+    let mut emmyrc: Emmyrc = serde_json::from_value(emmyrc_json_value).unwrap_or_else(|err| {
         log::error!("Failed to parse config: error: {:?}", err);
         Emmyrc::default()
-    })
+    });
+    emmyrc.runtime.version_configured = version_configured;
+    emmyrc
 }
 
 fn merge_values(base: &mut Value, overlay: Value) {
@@ -11,4 +11,8 @@ pub struct EmmyrcCodeAction {
     #[serde(default)]
     #[schemars(extend("x-vscode-setting" = true))]
     pub insert_space: Option<bool>,
+    /// Runtime type trace files (JSON) used by the "Add annotations from runtime trace" code
+    /// action.
+    #[serde(default)]
+    pub trace_files: Vec<String>,
 }
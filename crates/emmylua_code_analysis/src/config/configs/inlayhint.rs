@@ -59,6 +59,54 @@ pub struct EmmyrcInlayHint {
     #[serde(default = "default_false")]
     #[schemars(extend("x-vscode-setting" = true))]
     pub enum_param_hint: bool,
+    /// Show the evaluated value of constant expressions.
+    ///
+    /// Example:
+    ///
+    /// ```lua
+    /// local TIMEOUT = 60 * 5 --[[ Hint: = 300 ]]
+    /// ```
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-vscode-setting" = true))]
+    pub const_value_hint: bool,
+    /// Label the `end` or `until` of long blocks with the statement that opens them.
+    ///
+    /// Example:
+    ///
+    /// ```lua
+    /// function Player:update(dt)
+    ///     -- ...
+    /// end --[[ Hint: -- function Player:update ]]
+    /// ```
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-vscode-setting" = true))]
+    pub block_end_hint: bool,
+    /// The minimum number of lines a block must span before `blockEndHint` labels its end.
+    #[serde(default = "default_block_end_hint_min_lines")]
+    #[schemars(extend("x-vscode-setting" = true))]
+    pub block_end_hint_min_lines: usize,
+    /// Show the return type after each call of a call chain that spans multiple lines.
+    ///
+    /// Example:
+    ///
+    /// ```lua
+    /// query:where("id", 1) --[[ Hint: Query ]]
+    ///     :limit(10) --[[ Hint: Query ]]
+    /// ```
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-vscode-setting" = true))]
+    pub chain_call_hint: bool,
+    /// Show the inferred type of `self` in methods.
+    ///
+    /// Example:
+    ///
+    /// ```lua
+    /// function Player:update(--[[ Hint: self: Player, ]] dt) end
+    /// function Player.draw(self --[[ Hint: : Player ]]) end
+    /// ```
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-vscode-setting" = true))]
+    pub self_hint: bool,
 }
 
 impl Default for EmmyrcInlayHint {
@@ -71,6 +119,11 @@ impl Default for EmmyrcInlayHint {
             override_hint: default_true(),
             meta_call_hint: default_true(),
             enum_param_hint: default_false(),
+            const_value_hint: default_false(),
+            block_end_hint: default_false(),
+            block_end_hint_min_lines: default_block_end_hint_min_lines(),
+            chain_call_hint: default_false(),
+            self_hint: default_false(),
         }
     }
 }
@@ -82,3 +135,7 @@ fn default_true() -> bool {
 fn default_false() -> bool {
     false
 }
+
+fn default_block_end_hint_min_lines() -> usize {
+    20
+}
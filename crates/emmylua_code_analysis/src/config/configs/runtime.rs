@@ -12,6 +12,9 @@ pub struct EmmyrcRuntime {
     /// Lua version.
     #[serde(default)]
     pub version: EmmyrcLuaVersion,
+    /// Whether `version` was set by a config file or an addon rather than left at its default.
+    #[serde(skip)]
+    pub version_configured: bool,
     #[serde(default)]
     /// Functions that like require.
     pub require_like_function: Vec<String>,
@@ -27,6 +27,9 @@ pub struct EmmyrcStrict {
     /// Base constant types defined in doc can match base types, allowing int to match `---@alias id 1|2|3`, same for string.
     #[serde(default = "default_false")]
     pub doc_base_const_match_base_type: bool,
+    /// Check function types by position: parameters are contravariant and return types are covariant.
+    #[serde(default = "default_false")]
+    pub function_variance: bool,
 }
 
 impl Default for EmmyrcStrict {
@@ -37,6 +40,7 @@ impl Default for EmmyrcStrict {
             array_index: true,
             meta_override_file_define: true,
             doc_base_const_match_base_type: true,
+            function_variance: false,
         }
     }
 }
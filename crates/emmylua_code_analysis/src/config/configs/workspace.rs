@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +22,11 @@ pub struct EmmyrcWorkspace {
     /// eg: ["/usr/local/share/lua/5.1/module"] or [{"path": "/usr/local/share/lua/5.1/module", "ignoreDir": ["test"], "ignoreGlobs": ["**/*.spec.lua"]}]
     pub packages: Vec<EmmyrcWorkspacePathItem>,
     #[serde(default)]
+    /// Addon directories in the LLS-Addons layout. Each entry is a single addon, which is always
+    /// applied, or a directory of addons, which are applied when their `words`/`files` rules match.
+    /// eg: ["./addons/love2d", "~/LLS-Addons/addons"]
+    pub addons: Vec<String>,
+    #[serde(default)]
     /// Workspace roots. eg: ["src", "test"]
     pub workspace_roots: Vec<String>,
     // unused
@@ -35,6 +42,14 @@ pub struct EmmyrcWorkspace {
     /// }
     #[serde(default)]
     pub module_map: Vec<EmmyrcWorkspaceModuleMap>,
+    /// Explicit module names for files, key is module name, value is file path.
+    /// Takes precedence over `runtime.requirePattern`.
+    /// eg: { "foo.bar": "src/foo/bar.lua" }
+    #[serde(default)]
+    pub module_files: HashMap<String, String>,
+    /// Read the `.rockspec` and the `lua_modules` tree of a LuaRocks project in the workspace root.
+    #[serde(default = "luarocks_default")]
+    pub luarocks: bool,
     /// Delay between changing a file and full project reindex, in milliseconds.
     #[serde(default = "reindex_duration_default")]
     #[schemars(extend("x-vscode-setting" = true))]
@@ -52,10 +67,13 @@ impl Default for EmmyrcWorkspace {
             ignore_globs: Vec::new(),
             library: Vec::new(),
             packages: Vec::new(),
+            addons: Vec::new(),
             workspace_roots: Vec::new(),
             preload_file_size: 0,
             encoding: encoding_default(),
             module_map: Vec::new(),
+            module_files: HashMap::new(),
+            luarocks: luarocks_default(),
             reindex_duration: 5000,
             enable_reindex: false,
         }
@@ -109,6 +127,10 @@ fn encoding_default() -> String {
     "utf-8".to_string()
 }
 
+fn luarocks_default() -> bool {
+    true
+}
+
 fn reindex_duration_default() -> u64 {
     5000
 }
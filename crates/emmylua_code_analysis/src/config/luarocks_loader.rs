@@ -0,0 +1,376 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use emmylua_parser::{
+    LuaAstToken, LuaExpr, LuaIndexKey, LuaLiteralToken, LuaParser, LuaStat, LuaTableExpr,
+    LuaVarExpr, NumberResult, ParserConfig,
+};
+use serde_json::{Map, Number, Value};
+
+use crate::{
+    config::{Emmyrc, EmmyrcLuaVersion, EmmyrcWorkspacePathItem},
+    read_file_with_encoding,
+};
+
+// rockspec 中需要读取的全局字段
+const ROCKSPEC_FIELDS: [&str; 3] = ["package", "dependencies", "build"];
+
+// rockspec 中可能出现的 Lua 版本, 从高到低排列
+const ROCKSPEC_LUA_VERSIONS: [(u32, u32, EmmyrcLuaVersion); 5] = [
+    (5, 5, EmmyrcLuaVersion::Lua55),
+    (5, 4, EmmyrcLuaVersion::Lua54),
+    (5, 3, EmmyrcLuaVersion::Lua53),
+    (5, 2, EmmyrcLuaVersion::Lua52),
+    (5, 1, EmmyrcLuaVersion::Lua51),
+];
+
+/// Apply the `.rockspec` files and the `lua_modules` tree of a LuaRocks project.
+///
+/// Modules listed in `build.modules` (and `build.install.lua`) are added to
+/// `workspace.moduleFiles`, the `lua` dependency picks the runtime version when none is
+/// configured, and the rocks installed under `lua_modules` are added as libraries.
+pub fn apply_luarocks(emmyrc: &mut Emmyrc, workspace_root: &Path) {
+    if !emmyrc.workspace.luarocks {
+        return;
+    }
+
+    for rockspec_path in find_rockspecs(workspace_root) {
+        let Some(rockspec) = load_rockspec(&rockspec_path) else {
+            continue;
+        };
+        log::info!("apply rockspec: {:?}", rockspec_path);
+        apply_rockspec(emmyrc, &rockspec);
+    }
+
+    for library in find_installed_rock_dirs(&workspace_root.join("lua_modules")) {
+        let library = EmmyrcWorkspacePathItem::Path(library.to_string_lossy().to_string());
+        if !emmyrc.workspace.library.contains(&library) {
+            emmyrc.workspace.library.push(library);
+        }
+    }
+}
+
+fn find_rockspecs(workspace_root: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(workspace_root) else {
+        return Vec::new();
+    };
+    let mut rockspecs = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "rockspec"))
+        .collect::<Vec<_>>();
+    rockspecs.sort();
+    rockspecs
+}
+
+/// Read the fields of a `.rockspec` from its syntax tree, without executing it.
+fn load_rockspec(path: &Path) -> Option<Value> {
+    let content = read_file_with_encoding(path, "utf-8")?;
+    let tree = LuaParser::parse(&content, ParserConfig::default());
+    let chunk = tree.get_chunk_node();
+    let block = chunk.get_block()?;
+
+    // rockspec 通过全局变量声明字段, 只取顶层赋值中的字面量, 局部变量用于解析引用
+    let mut locals = HashMap::new();
+    let mut rockspec = Map::new();
+    for stat in block.get_stats() {
+        match stat {
+            LuaStat::LocalStat(local_stat) => {
+                let names = local_stat.get_local_name_list();
+                for (name, expr) in names.zip(local_stat.get_value_exprs()) {
+                    let Some(name) = name.get_name_token() else {
+                        continue;
+                    };
+                    if let Some(value) = expr_to_value(&expr, &locals) {
+                        locals.insert(name.get_name_text().to_string(), value);
+                    }
+                }
+            }
+            LuaStat::AssignStat(assign_stat) => {
+                let (vars, exprs) = assign_stat.get_var_and_expr_list();
+                for (var, expr) in vars.iter().zip(exprs.iter()) {
+                    let LuaVarExpr::NameExpr(name_expr) = var else {
+                        continue;
+                    };
+                    let Some(name) = name_expr.get_name_text() else {
+                        continue;
+                    };
+                    if !ROCKSPEC_FIELDS.contains(&name.as_str()) {
+                        continue;
+                    }
+                    if let Some(value) = expr_to_value(expr, &locals) {
+                        rockspec.insert(name, value);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if rockspec.is_empty() {
+        log::warn!("No rockspec fields found in {:?}", path);
+        return None;
+    }
+    Some(Value::Object(rockspec))
+}
+
+fn expr_to_value(expr: &LuaExpr, locals: &HashMap<String, Value>) -> Option<Value> {
+    match expr {
+        LuaExpr::LiteralExpr(literal_expr) => match literal_expr.get_literal()? {
+            LuaLiteralToken::String(string_token) => Some(Value::String(string_token.get_value())),
+            LuaLiteralToken::Number(number_token) => match number_token.get_number_value() {
+                NumberResult::Int(i) => Some(Value::Number(i.into())),
+                NumberResult::Uint(u) => Some(Value::Number(u.into())),
+                NumberResult::Float(f) => Number::from_f64(f).map(Value::Number),
+                NumberResult::Number => None,
+            },
+            LuaLiteralToken::Bool(bool_token) => Some(Value::Bool(bool_token.is_true())),
+            LuaLiteralToken::Nil(_) => Some(Value::Null),
+            _ => None,
+        },
+        LuaExpr::ParenExpr(paren_expr) => expr_to_value(&paren_expr.get_expr()?, locals),
+        LuaExpr::NameExpr(name_expr) => locals.get(&name_expr.get_name_text()?).cloned(),
+        LuaExpr::TableExpr(table_expr) => Some(table_to_value(table_expr, locals)),
+        _ => None,
+    }
+}
+
+fn table_to_value(table_expr: &LuaTableExpr, locals: &HashMap<String, Value>) -> Value {
+    let fields = table_expr.get_fields().collect::<Vec<_>>();
+    if fields.iter().all(|field| field.is_value_field()) {
+        return Value::Array(
+            fields
+                .iter()
+                .filter_map(|field| expr_to_value(&field.get_value_expr()?, locals))
+                .collect(),
+        );
+    }
+
+    let mut map = Map::new();
+    for field in fields {
+        let key = match field.get_field_key() {
+            Some(LuaIndexKey::Name(name)) => name.get_name_text().to_string(),
+            Some(LuaIndexKey::String(string)) => string.get_value(),
+            Some(LuaIndexKey::Integer(number)) => number.syntax().text().to_string(),
+            Some(LuaIndexKey::Idx(idx)) => idx.to_string(),
+            _ => continue,
+        };
+        let Some(value) = field
+            .get_value_expr()
+            .and_then(|expr| expr_to_value(&expr, locals))
+        else {
+            continue;
+        };
+        map.insert(key, value);
+    }
+    Value::Object(map)
+}
+
+fn apply_rockspec(emmyrc: &mut Emmyrc, rockspec: &Value) {
+    if let Some(build) = rockspec.get("build") {
+        let install_lua = build.get("install").and_then(|install| install.get("lua"));
+        for modules in [build.get("modules"), install_lua].into_iter().flatten() {
+            let Some(modules) = modules.as_object() else {
+                continue;
+            };
+            for (module_name, source) in modules {
+                // C 模块的 source 是文件列表或表, 只处理 Lua 源文件
+                let Some(source) = source.as_str() else {
+                    continue;
+                };
+                if source.ends_with(".c") {
+                    continue;
+                }
+                emmyrc
+                    .workspace
+                    .module_files
+                    .entry(module_name.clone())
+                    .or_insert_with(|| source.to_string());
+            }
+        }
+    }
+
+    // 用户未指定版本时才使用 rockspec 的版本
+    if !emmyrc.runtime.version_configured
+        && let Some(dependencies) = rockspec.get("dependencies")
+        && let Some(version) = get_lua_version_from_dependencies(dependencies)
+    {
+        emmyrc.runtime.version = version;
+    }
+}
+
+fn get_lua_version_from_dependencies(dependencies: &Value) -> Option<EmmyrcLuaVersion> {
+    let dependencies = match dependencies {
+        Value::Array(items) => items.iter().collect::<Vec<_>>(),
+        Value::Object(map) => map.values().collect(),
+        _ => return None,
+    };
+    let constraints = dependencies.into_iter().find_map(|dependency| {
+        let dependency = dependency.as_str()?.trim();
+        let constraints = dependency.strip_prefix("lua")?;
+        if constraints.is_empty() || constraints.starts_with(char::is_whitespace) {
+            Some(constraints.trim())
+        } else {
+            None
+        }
+    })?;
+
+    let matched = ROCKSPEC_LUA_VERSIONS
+        .iter()
+        .position(|(major, minor, _)| is_version_match(constraints, (*major, *minor)))?;
+    // 没有上限时保留默认的最新版本
+    if matched == 0 {
+        return None;
+    }
+    Some(ROCKSPEC_LUA_VERSIONS[matched].2)
+}
+
+/// Check a LuaRocks version constraint such as `>= 5.1, < 5.4` or `~> 5.3`.
+fn is_version_match(constraints: &str, version: (u32, u32)) -> bool {
+    constraints
+        .split(',')
+        .map(str::trim)
+        .filter(|constraint| !constraint.is_empty())
+        .all(|constraint| {
+            let op_len = constraint
+                .find(|c: char| c.is_ascii_digit())
+                .unwrap_or(constraint.len());
+            let (op, target) = constraint.split_at(op_len);
+            let Some((major, minor)) = parse_version(target) else {
+                return true;
+            };
+            match op.trim() {
+                "" | "==" => version == (major, minor),
+                "~=" => version != (major, minor),
+                ">=" => version >= (major, minor),
+                ">" => version > (major, minor),
+                "<=" => version <= (major, minor),
+                "<" => version < (major, minor),
+                // `~> 5.1` 表示 5.1.x, `~> 5` 表示 5.x
+                "~>" => version.0 == major && (!target.contains('.') || version.1 == minor),
+                _ => true,
+            }
+        })
+}
+
+fn parse_version(text: &str) -> Option<(u32, u32)> {
+    let mut parts = text.trim().split(['.', '-']);
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().and_then(|part| part.parse().ok()).unwrap_or(0);
+    Some((major, minor))
+}
+
+/// `lua_modules/share/lua/<ver>`, where LuaRocks installs the Lua modules of every rock.
+///
+/// `lua_modules/lib/luarocks/rocks` is not registered: it only holds the rock manifests, copied
+/// rockspecs and `doc` folders, so it has no requirable modules and would index example scripts.
+fn find_installed_rock_dirs(lua_modules: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Ok(entries) = fs::read_dir(lua_modules.join("share").join("lua")) {
+        dirs.extend(
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_dir()),
+        );
+    }
+    dirs.sort();
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::load_configs;
+
+    #[test]
+    fn test_version_constraints() {
+        let deps = |dep: &str| Value::Array(vec![Value::String(dep.to_string())]);
+        assert_eq!(
+            get_lua_version_from_dependencies(&deps("lua >= 5.1, < 5.4")),
+            Some(EmmyrcLuaVersion::Lua53)
+        );
+        assert_eq!(
+            get_lua_version_from_dependencies(&deps("lua ~> 5.1")),
+            Some(EmmyrcLuaVersion::Lua51)
+        );
+        assert_eq!(
+            get_lua_version_from_dependencies(&deps("lua == 5.2")),
+            Some(EmmyrcLuaVersion::Lua52)
+        );
+        assert_eq!(get_lua_version_from_dependencies(&deps("lua >= 5.1")), None);
+        assert_eq!(get_lua_version_from_dependencies(&deps("luasocket")), None);
+    }
+
+    #[test]
+    fn test_apply_rockspec_project() {
+        let root =
+            std::env::temp_dir().join(format!("emmylua_luarocks_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(
+            root.join("lua_modules")
+                .join("share")
+                .join("lua")
+                .join("5.3"),
+        )
+        .unwrap();
+        let marker = root.join("executed");
+        fs::write(
+            root.join("mylib-1.0-1.rockspec"),
+            format!(
+                r#"
+io.open({marker:?}, "w"):close()
+package = "mylib"
+version = "1.0-1"
+local util = "src/mylib/util.lua"
+dependencies = {{ "lua >= 5.1, < 5.4", "luasocket" }}
+build = {{
+   type = "builtin",
+   modules = {{
+      ["mylib"] = "src/init.lua",
+      ["mylib.util"] = util,
+      ["mylib.core"] = {{ "src/core.c" }},
+   }},
+}}
+"#
+            ),
+        )
+        .unwrap();
+
+        let mut emmyrc = Emmyrc::default();
+        apply_luarocks(&mut emmyrc, &root);
+
+        // rockspec 只做静态读取, 其中的代码不会被执行
+        assert!(!marker.exists());
+        assert_eq!(emmyrc.runtime.version, EmmyrcLuaVersion::Lua53);
+        assert_eq!(emmyrc.workspace.module_files.len(), 2);
+        assert_eq!(
+            emmyrc.workspace.module_files.get("mylib.util"),
+            Some(&"src/mylib/util.lua".to_string())
+        );
+        assert_eq!(
+            emmyrc.workspace.library,
+            vec![EmmyrcWorkspacePathItem::Path(
+                root.join("lua_modules")
+                    .join("share")
+                    .join("lua")
+                    .join("5.3")
+                    .to_string_lossy()
+                    .to_string()
+            )]
+        );
+
+        // 用户显式配置的版本即使等于默认值也不会被 rockspec 覆盖
+        let mut emmyrc = load_configs(
+            Vec::new(),
+            Some(vec![
+                serde_json::json!({ "runtime": { "version": "LuaLatest" } }),
+            ]),
+        );
+        apply_luarocks(&mut emmyrc, &root);
+        assert_eq!(emmyrc.runtime.version, EmmyrcLuaVersion::LuaLatest);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
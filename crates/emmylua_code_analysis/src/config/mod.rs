@@ -1,7 +1,9 @@
+mod addon_loader;
 mod config_loader;
 mod configs;
 mod flatten_config;
 mod lua_loader;
+mod luarocks_loader;
 mod pre_process;
 
 use std::{collections::HashMap, path::Path};
@@ -103,15 +105,23 @@ impl Emmyrc {
         self.workspace.workspace_roots =
             context.process_and_dedup_string(self.workspace.workspace_roots.iter());
 
+        self.workspace.ignore_dir =
+            context.process_and_dedup_string(self.workspace.ignore_dir.iter());
+
+        self.workspace.addons = context.process_and_dedup_string(self.workspace.addons.iter());
+        addon_loader::apply_addons(self, workspace_root);
+        luarocks_loader::apply_luarocks(self, workspace_root);
+
         self.workspace.library =
             context.process_and_dedup_workspace_path_items(self.workspace.library.iter());
 
         self.workspace.packages =
             context.process_and_dedup_workspace_path_items(self.workspace.packages.iter());
 
-        self.workspace.ignore_dir =
-            context.process_and_dedup_string(self.workspace.ignore_dir.iter());
+        self.workspace.module_files = context.process_module_files(&self.workspace.module_files);
 
         self.resource.paths = context.process_and_dedup_string(self.resource.paths.iter());
+        self.code_action.trace_files =
+            context.process_and_dedup_string(self.code_action.trace_files.iter());
     }
 }
@@ -1,5 +1,9 @@
 use regex::Regex;
-use std::{collections::HashSet, path::PathBuf, process::Command};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    process::Command,
+};
 
 use crate::config::configs::{EmmyrcWorkspacePathConfig, EmmyrcWorkspacePathItem};
 
@@ -59,6 +63,17 @@ impl PreProcessContext {
             .collect()
     }
 
+    /// moduleFiles
+    pub fn process_module_files(
+        &self,
+        module_files: &HashMap<String, String>,
+    ) -> HashMap<String, String> {
+        module_files
+            .iter()
+            .map(|(module_name, path)| (module_name.clone(), self.pre_process_path(path)))
+            .collect()
+    }
+
     fn pre_process_path(&self, path: &str) -> String {
         let mut path = path.to_string();
         path = self.replace_env_var(&path);
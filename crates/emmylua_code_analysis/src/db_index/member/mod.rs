@@ -228,6 +228,22 @@ impl LuaMemberIndex {
     pub fn get_current_owner(&self, id: &LuaMemberId) -> Option<&LuaMemberOwner> {
         self.member_current_owner.get(id)
     }
+
+    /// Returns the members defined in the file, sorted by position.
+    pub fn get_file_members(&self, file_id: &FileId) -> Vec<&LuaMember> {
+        let mut members = self
+            .in_filed
+            .get(file_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|item| match item {
+                MemberOrOwner::Member(id) => self.get_member(id),
+                MemberOrOwner::Owner(_) => None,
+            })
+            .collect::<Vec<_>>();
+        members.sort_by_key(|member| member.get_sort_key());
+        members
+    }
 }
 
 impl LuaIndex for LuaMemberIndex {
@@ -15,7 +15,7 @@ mod signature;
 mod traits;
 mod r#type;
 
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use crate::{Emmyrc, FileId, Vfs};
 pub use declaration::*;
@@ -220,6 +220,17 @@ impl DbIndex {
         &self.emmyrc
     }
 
+    pub fn update_scoped_configs(&mut self, scoped_configs: Vec<(PathBuf, Arc<Emmyrc>)>) {
+        self.vfs.update_scoped_configs(scoped_configs);
+    }
+
+    /// Returns the config of the directory the file belongs to, or the workspace config.
+    pub fn get_file_emmyrc(&self, file_id: &FileId) -> Arc<Emmyrc> {
+        self.vfs
+            .get_file_emmyrc(file_id)
+            .unwrap_or_else(|| self.emmyrc.clone())
+    }
+
     pub fn resolve_workspace_id(&self, file_id: FileId) -> Option<WorkspaceId> {
         self.modules_index.get_workspace_id(file_id).or_else(|| {
             if self.vfs.is_remote_file(&file_id) {
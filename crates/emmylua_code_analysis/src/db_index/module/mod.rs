@@ -14,7 +14,7 @@ use super::traits::LuaIndex;
 use crate::{Emmyrc, FileId, WorkspaceImport};
 use hashbrown::{HashMap, HashSet};
 use std::{
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
     sync::Arc,
 };
 
@@ -29,6 +29,7 @@ pub struct LuaModuleIndex {
     id_counter: u32,
     fuzzy_search: bool,
     module_replace_vec: Vec<(Regex, String)>,
+    module_file_map: HashMap<PathBuf, String>,
 }
 
 impl Default for LuaModuleIndex {
@@ -49,6 +50,7 @@ impl LuaModuleIndex {
             id_counter: 1,
             fuzzy_search: false,
             module_replace_vec: Vec::new(),
+            module_file_map: HashMap::new(),
         };
 
         let root_node = ModuleNode::default();
@@ -368,6 +370,10 @@ impl LuaModuleIndex {
 
     pub fn extract_module_path(&self, path: &str) -> Option<(String, WorkspaceId)> {
         let path = Path::new(path);
+        if let Some(module_path) = self.find_module_file(path) {
+            return Some((module_path.clone(), self.get_workspace_id_by_path(path)));
+        }
+
         let mut matched_module_path: Option<(String, WorkspaceId)> = None;
         for workspace in &self.workspaces {
             if let Ok(relative_path) = path.strip_prefix(&workspace.root) {
@@ -409,6 +415,31 @@ impl LuaModuleIndex {
         matched_module_path
     }
 
+    /// 按规范化后的路径查找显式指定的模块名, 相对路径按工作区根目录匹配
+    fn find_module_file(&self, path: &Path) -> Option<&String> {
+        let path = normalize_module_file_path(path);
+        self.module_file_map.get(&path).or_else(|| {
+            self.workspaces.iter().find_map(|workspace| {
+                let root = normalize_module_file_path(&workspace.root);
+                let relative_path = path.strip_prefix(&root).ok()?;
+                self.module_file_map.get(relative_path)
+            })
+        })
+    }
+
+    /// 显式指定模块名的文件优先归属到库工作区, 不在任何工作区内时归属主工作区
+    fn get_workspace_id_by_path(&self, path: &Path) -> WorkspaceId {
+        self.workspaces
+            .iter()
+            .filter(|workspace| {
+                path.strip_prefix(&workspace.root)
+                    .is_ok_and(|relative_path| workspace.import.includes_path(relative_path))
+            })
+            .map(|workspace| workspace.id)
+            .max()
+            .unwrap_or(WorkspaceId::MAIN)
+    }
+
     fn replace_module_path(&self, module_path: &str) -> String {
         let mut module_path = module_path.to_owned();
         for (key, value) in &self.module_replace_vec {
@@ -465,11 +496,15 @@ impl LuaModuleIndex {
         candidate
     }
 
-    #[allow(unused)]
     pub fn remove_workspace_root(&mut self, root: &Path) {
         self.workspaces.retain(|r| r.root != root);
     }
 
+    pub fn remove_workspace(&mut self, root: &Path, import: &WorkspaceImport) {
+        self.workspaces
+            .retain(|w| !(w.root == root && w.import == *import));
+    }
+
     pub fn update_config(&mut self, config: Arc<Emmyrc>) {
         let mut extension_names = Vec::new();
 
@@ -513,6 +548,18 @@ impl LuaModuleIndex {
                 .collect(),
         );
 
+        self.module_file_map = config
+            .workspace
+            .module_files
+            .iter()
+            .map(|(module_name, path)| {
+                (
+                    normalize_module_file_path(Path::new(path)),
+                    module_name.clone(),
+                )
+            })
+            .collect();
+
         self.fuzzy_search = !config.strict.require_path;
     }
 
@@ -676,3 +723,20 @@ impl LuaIndex for LuaModuleIndex {
         self.module_nodes.insert(self.module_root_id, root_node);
     }
 }
+
+// 去掉路径中的 `.` 并折叠 `..`, 使 `./src/x.lua` 与 `src/x.lua` 指向同一个键
+fn normalize_module_file_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
@@ -254,6 +254,75 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_module_files_take_precedence_over_require_pattern() {
+        let mut config = Emmyrc::default();
+        config.workspace.module_files.insert(
+            "mylib".to_string(),
+            "C:/Users/username/Documents/rock/src/init.lua".to_string(),
+        );
+        config.workspace.module_files.insert(
+            "mylib.util".to_string(),
+            "C:/Users/username/Documents/rock/src/mylib/util.lua".to_string(),
+        );
+
+        let mut m = LuaModuleIndex::new();
+        m.update_config(Arc::new(config));
+        m.add_workspace_root(
+            Path::new("C:/Users/username/Documents/rock").into(),
+            WorkspaceId::MAIN,
+        );
+
+        let init_id = FileId { id: 1 };
+        m.add_module_by_path(init_id, "C:/Users/username/Documents/rock/src/init.lua");
+        let util_id = FileId { id: 2 };
+        m.add_module_by_path(
+            util_id,
+            "C:/Users/username/Documents/rock/src/mylib/util.lua",
+        );
+        let other_id = FileId { id: 3 };
+        m.add_module_by_path(other_id, "C:/Users/username/Documents/rock/src/other.lua");
+
+        assert_eq!(m.find_module("mylib").unwrap().file_id, init_id);
+        assert_eq!(m.find_module("mylib.util").unwrap().file_id, util_id);
+        assert_eq!(
+            m.get_module(util_id).unwrap().workspace_id,
+            WorkspaceId::MAIN
+        );
+        assert_eq!(
+            m.get_module(other_id).unwrap().full_module_name,
+            "src.other"
+        );
+    }
+
+    #[test]
+    fn test_module_files_normalize_paths() {
+        let mut config = Emmyrc::default();
+        config
+            .workspace
+            .module_files
+            .insert("mylib".to_string(), "./src/init.lua".to_string());
+        config.workspace.module_files.insert(
+            "mylib.util".to_string(),
+            "src/mylib/../util.lua".to_string(),
+        );
+
+        let mut m = LuaModuleIndex::new();
+        m.update_config(Arc::new(config));
+        m.add_workspace_root(
+            Path::new("C:/Users/username/Documents/rock").into(),
+            WorkspaceId::MAIN,
+        );
+
+        let init_id = FileId { id: 1 };
+        m.add_module_by_path(init_id, "C:/Users/username/Documents/rock/src/init.lua");
+        let util_id = FileId { id: 2 };
+        m.add_module_by_path(util_id, "C:/Users/username/Documents/rock/./src/util.lua");
+
+        assert_eq!(m.find_module("mylib").unwrap().file_id, init_id);
+        assert_eq!(m.find_module("mylib.util").unwrap().file_id, util_id);
+    }
+
     #[test]
     fn test_module_map_keeps_configured_rule_order() {
         let mut config = Emmyrc::default();
@@ -9,6 +9,19 @@ pub struct GenericParam {
     pub default: Option<LuaType>,
     pub is_const: bool,
     pub attributes: Option<Vec<LuaAttributeUse>>,
+    pub variance: GenericVariance,
+}
+
+/// 泛型参数的变体, 由 `---@class Foo<in T, out R>` 中的修饰符声明
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GenericVariance {
+    /// 未声明修饰符, 沿用宽松的参数检查
+    #[default]
+    Default,
+    /// `out T`, 只出现在输出位置, `Foo<Dog>` 可以赋值给 `Foo<Animal>`
+    Covariant,
+    /// `in T`, 只出现在输入位置, `Foo<Animal>` 可以赋值给 `Foo<Dog>`
+    Contravariant,
 }
 
 impl GenericParam {
@@ -25,6 +38,12 @@ impl GenericParam {
             default,
             is_const,
             attributes,
+            variance: GenericVariance::Default,
         }
     }
+
+    pub fn with_variance(mut self, variance: GenericVariance) -> Self {
+        self.variance = variance;
+        self
+    }
 }
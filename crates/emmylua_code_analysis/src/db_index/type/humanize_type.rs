@@ -576,6 +576,22 @@ impl<'a> TypeHumanizer<'a> {
                 }
                 result
             }
+            LuaAliasCallKind::Template => {
+                let mut result = w.write_char('`');
+                for ty in operands {
+                    if result.is_err() {
+                        break;
+                    }
+                    result = match ty {
+                        LuaType::DocStringConst(s) => w.write_str(s),
+                        _ => w
+                            .write_str("${")
+                            .and_then(|_| self.write_type(ty, w))
+                            .and_then(|_| w.write_char('}')),
+                    };
+                }
+                result.and_then(|_| w.write_char('`'))
+            }
             call_kind => {
                 let basic = match call_kind {
                     LuaAliasCallKind::Sub => "sub",
@@ -587,6 +603,10 @@ impl<'a> TypeHumanizer<'a> {
                     LuaAliasCallKind::Index => "index",
                     LuaAliasCallKind::RawGet => "rawget",
                     LuaAliasCallKind::Merge => "Merge",
+                    LuaAliasCallKind::Template => "template",
+                    LuaAliasCallKind::Uppercase => "Uppercase",
+                    LuaAliasCallKind::Lowercase => "Lowercase",
+                    LuaAliasCallKind::Capitalize => "Capitalize",
                 };
                 let mut result = w.write_str(basic);
                 if result.is_ok() {
@@ -1104,6 +1124,8 @@ impl<'a> TypeHumanizer<'a> {
         w.write_char(']')?;
         if mapped.is_optional {
             w.write_char('?')?;
+        } else if mapped.is_required {
+            w.write_str("-?")?;
         }
         w.write_str(": ")?;
         self.write_type(&mapped.value, w)?;
@@ -11,7 +11,7 @@ mod types;
 use super::traits::LuaIndex;
 use crate::{DbIndex, FileId, InFiled, db_index::WorkspaceId};
 pub use basic_union::{BasicTypeKind, BasicTypeUnion};
-pub use generic_param::GenericParam;
+pub use generic_param::{GenericParam, GenericVariance};
 use hashbrown::{HashMap, HashSet};
 pub use humanize_type::{RenderLevel, TypeHumanizer, format_union_type, humanize_type};
 pub use type_decl::{
@@ -613,6 +613,37 @@ fn get_real_type_with_depth<'a>(
     }
 }
 
+/// 获取 `---@class (opaque)` 类型的基础类型, 非 opaque 类型返回 `None`
+pub fn get_opaque_base_type<'a>(db: &'a DbIndex, typ: &'a LuaType) -> Option<&'a LuaType> {
+    get_opaque_base_type_with_depth(db, typ, 0)
+}
+
+fn get_opaque_base_type_with_depth<'a>(
+    db: &'a DbIndex,
+    typ: &'a LuaType,
+    depth: u32,
+) -> Option<&'a LuaType> {
+    const MAX_RECURSION_DEPTH: u32 = 10;
+
+    if depth >= MAX_RECURSION_DEPTH {
+        return None;
+    }
+
+    let (LuaType::Ref(type_decl_id) | LuaType::Def(type_decl_id)) = typ else {
+        return None;
+    };
+    let type_decl = db.get_type_index().get_type_decl(type_decl_id)?;
+    if !type_decl.is_opaque() {
+        return None;
+    }
+
+    let base_type = db
+        .get_type_index()
+        .get_super_types_iter(type_decl_id)?
+        .next()?;
+    get_opaque_base_type_with_depth(db, base_type, depth + 1).or(Some(base_type))
+}
+
 // 第一个参数是否不应该视为 self
 pub fn first_param_may_not_self(typ: &LuaType) -> bool {
     if typ.is_table()
@@ -21,7 +21,7 @@ pub enum LuaDeclTypeKind {
 }
 
 flags! {
-    pub enum LuaTypeFlag: u8 {
+    pub enum LuaTypeFlag: u16 {
         Key,
         Partial,
         Exact,
@@ -29,7 +29,8 @@ flags! {
         Constructor,
         Public,
         Internal,
-        File
+        File,
+        Opaque
     }
 }
 
@@ -103,6 +104,12 @@ impl LuaTypeDecl {
             .any(|l| l.flag.contains(LuaTypeFlag::Partial))
     }
 
+    pub fn is_opaque(&self) -> bool {
+        self.locations
+            .iter()
+            .any(|l| l.flag.contains(LuaTypeFlag::Opaque))
+    }
+
     pub fn is_enum_key(&self) -> bool {
         self.locations
             .iter()
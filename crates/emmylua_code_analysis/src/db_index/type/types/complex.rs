@@ -533,6 +533,10 @@ pub enum LuaAliasCallKind {
     Unpack,
     RawGet,
     Merge,
+    Template,
+    Uppercase,
+    Lowercase,
+    Capitalize,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -989,6 +993,7 @@ pub struct LuaMappedType {
     pub value: LuaType,
     pub is_readonly: bool,
     pub is_optional: bool,
+    pub is_required: bool,
 }
 
 impl LuaMappedType {
@@ -997,12 +1002,14 @@ impl LuaMappedType {
         value: LuaType,
         is_readonly: bool,
         is_optional: bool,
+        is_required: bool,
     ) -> Self {
         Self {
             param,
             value,
             is_readonly,
             is_optional,
+            is_required,
         }
     }
 
@@ -2,7 +2,7 @@ use hashbrown::HashMap;
 
 use emmylua_parser::{LuaAstNode, LuaIndexKey, LuaTableExpr};
 
-use crate::{DiagnosticCode, SemanticModel};
+use crate::{DiagnosticCode, LuaType, SemanticModel};
 
 use super::{Checker, DiagnosticContext};
 
@@ -21,7 +21,7 @@ impl Checker for DuplicateIndexChecker {
 
 fn check_table_duplicate_index(
     context: &mut DiagnosticContext,
-    _: &SemanticModel,
+    semantic_model: &SemanticModel,
     table: LuaTableExpr,
 ) -> Option<()> {
     let fields = table.get_fields_with_keys();
@@ -33,7 +33,10 @@ fn check_table_duplicate_index(
     let mut index_map: HashMap<String, Vec<LuaIndexKey>> = HashMap::new();
 
     for (_, key) in fields {
-        index_map.entry(key.get_path_part()).or_default().push(key);
+        index_map
+            .entry(get_key_path_part(semantic_model, &key))
+            .or_default()
+            .push(key);
     }
 
     for (name, keys) in index_map {
@@ -56,3 +59,19 @@ fn check_table_duplicate_index(
 
     Some(())
 }
+
+/// Expression keys that evaluate to a constant are compared by their value, so that
+/// `[1 << 3]` and `[8]` are detected as the same index.
+fn get_key_path_part(semantic_model: &SemanticModel, key: &LuaIndexKey) -> String {
+    if let LuaIndexKey::Expr(expr) = key {
+        match semantic_model.infer_expr(expr.clone()) {
+            Ok(LuaType::IntegerConst(i)) | Ok(LuaType::DocIntegerConst(i)) => {
+                return format!("[{}]", i);
+            }
+            Ok(LuaType::StringConst(s)) | Ok(LuaType::DocStringConst(s)) => return s.to_string(),
+            _ => {}
+        }
+    }
+
+    key.get_path_part()
+}
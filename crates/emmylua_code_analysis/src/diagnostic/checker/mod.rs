@@ -23,6 +23,7 @@ mod incomplete_signature_doc;
 mod local_const_reassign;
 mod missing_fields;
 mod need_check_nil;
+mod non_exhaustive_branches;
 mod param_check;
 mod readonly_check;
 mod redefined_local;
@@ -82,6 +83,7 @@ pub fn check_file(context: &mut DiagnosticContext, semantic_model: &SemanticMode
     run_check::<undefined_global::UndefinedGlobalChecker>(context, semantic_model);
     run_check::<unnecessary_assert::UnnecessaryAssertChecker>(context, semantic_model);
     run_check::<unnecessary_if::UnnecessaryIfChecker>(context, semantic_model);
+    run_check::<non_exhaustive_branches::NonExhaustiveBranchesChecker>(context, semantic_model);
     run_check::<access_invisible::AccessInvisibleChecker>(context, semantic_model);
     run_check::<local_const_reassign::LocalConstReassignChecker>(context, semantic_model);
     run_check::<discard_returns::DiscardReturnsChecker>(context, semantic_model);
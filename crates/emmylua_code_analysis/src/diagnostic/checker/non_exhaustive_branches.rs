@@ -0,0 +1,334 @@
+use emmylua_parser::{
+    BinaryOperator, LuaAstNode, LuaAstToken, LuaBinaryExpr, LuaExpr, LuaIfStat, LuaTableExpr,
+    LuaTokenKind,
+};
+
+use crate::{
+    DiagnosticCode, LuaMemberKey, LuaMemberOwner, LuaType, LuaTypeDeclId, SemanticModel,
+    diagnostic::checker::humanize_lint_type,
+};
+
+use super::{Checker, DiagnosticContext};
+
+pub struct NonExhaustiveBranchesChecker;
+
+impl Checker for NonExhaustiveBranchesChecker {
+    const CODES: &[DiagnosticCode] = &[DiagnosticCode::NonExhaustiveBranches];
+
+    fn check(context: &mut DiagnosticContext, semantic_model: &SemanticModel) {
+        let root = semantic_model.get_root().clone();
+        for if_stat in root.descendants::<LuaIfStat>() {
+            check_if_stat(context, semantic_model, if_stat);
+        }
+        for table_expr in root.descendants::<LuaTableExpr>() {
+            check_dispatch_table(context, semantic_model, table_expr);
+        }
+    }
+}
+
+/// 需要覆盖的一个成员, `label` 为写入源码时使用的表达式
+struct BranchMember {
+    label: String,
+    typ: LuaType,
+}
+
+/// 检查 `if x == A elseif x == B` 形式的分支链是否覆盖了 `x` 的所有可能值,
+/// 带 `else` 的分支链视为已经处理了剩余的值
+fn check_if_stat(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    if_stat: LuaIfStat,
+) -> Option<()> {
+    if if_stat.get_else_clause().is_some() {
+        return Some(());
+    }
+
+    let mut conditions = vec![if_stat.get_condition_expr()?];
+    for clause in if_stat.get_else_if_clause_list() {
+        conditions.push(clause.get_condition_expr()?);
+    }
+    if conditions.len() < 2 {
+        return Some(());
+    }
+
+    let mut subject: Option<LuaExpr> = None;
+    let mut tested_types = Vec::new();
+    for condition in conditions {
+        collect_eq_tests(semantic_model, condition, &mut subject, &mut tested_types)?;
+    }
+
+    let subject = subject?;
+    let subject_type = semantic_model.infer_expr(subject.clone()).ok()?;
+    let members = get_branch_members(semantic_model, &subject_type)?;
+    let missing = members
+        .into_iter()
+        .filter(|member| {
+            !tested_types
+                .iter()
+                .any(|tested| is_same_literal(&member.typ, tested))
+        })
+        .map(|member| member.label)
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return Some(());
+    }
+
+    let if_token = if_stat.token_by_kind(LuaTokenKind::TkIf)?;
+    context.add_diagnostic(
+        DiagnosticCode::NonExhaustiveBranches,
+        if_token.get_range(),
+        t!(
+            "Non-exhaustive branches, missing: %{members}",
+            members = missing.join(", ")
+        )
+        .to_string(),
+        Some(serde_json::json!({
+            "subject": normalize_text(&subject.syntax().text().to_string()),
+            "missing": missing,
+        })),
+    );
+    Some(())
+}
+
+/// 收集条件中对同一变量的等值比较, 条件中出现其他形式的表达式时返回 `None`
+fn collect_eq_tests(
+    semantic_model: &SemanticModel,
+    condition: LuaExpr,
+    subject: &mut Option<LuaExpr>,
+    tested_types: &mut Vec<LuaType>,
+) -> Option<()> {
+    match condition {
+        LuaExpr::ParenExpr(paren_expr) => collect_eq_tests(
+            semantic_model,
+            paren_expr.get_expr()?,
+            subject,
+            tested_types,
+        ),
+        LuaExpr::BinaryExpr(binary_expr) => match binary_expr.get_op_token()?.get_op() {
+            BinaryOperator::OpOr => {
+                let (left, right) = binary_expr.get_exprs()?;
+                collect_eq_tests(semantic_model, left, subject, tested_types)?;
+                collect_eq_tests(semantic_model, right, subject, tested_types)
+            }
+            BinaryOperator::OpEq => {
+                let tested = get_eq_test_value(semantic_model, &binary_expr, subject)?;
+                tested_types.push(tested);
+                Some(())
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn get_eq_test_value(
+    semantic_model: &SemanticModel,
+    binary_expr: &LuaBinaryExpr,
+    subject: &mut Option<LuaExpr>,
+) -> Option<LuaType> {
+    let (left, right) = binary_expr.get_exprs()?;
+    if let Some(current) = subject {
+        let current_text = normalize_text(&current.syntax().text().to_string());
+        let value_expr = if normalize_text(&left.syntax().text().to_string()) == current_text {
+            right
+        } else if normalize_text(&right.syntax().text().to_string()) == current_text {
+            left
+        } else {
+            return None;
+        };
+        let value_type = semantic_model.infer_expr(value_expr).ok()?;
+        return is_literal(&value_type).then_some(value_type);
+    }
+
+    let left_type = semantic_model.infer_expr(left.clone()).ok()?;
+    let right_type = semantic_model.infer_expr(right.clone()).ok()?;
+    let (subject_expr, value_type) = if is_literal(&right_type) {
+        (left, right_type)
+    } else if is_literal(&left_type) {
+        (right, left_type)
+    } else {
+        return None;
+    };
+    if !matches!(subject_expr, LuaExpr::NameExpr(_) | LuaExpr::IndexExpr(_)) {
+        return None;
+    }
+
+    *subject = Some(subject_expr);
+    Some(value_type)
+}
+
+/// 获取类型的全部可能取值, 只支持枚举与字面量联合
+fn get_branch_members(semantic_model: &SemanticModel, typ: &LuaType) -> Option<Vec<BranchMember>> {
+    let members = match typ {
+        LuaType::Ref(type_decl_id) => get_enum_members(semantic_model, type_decl_id)?,
+        LuaType::Union(union) => {
+            let mut members = Vec::new();
+            for typ in union.into_vec() {
+                if typ.is_nil() {
+                    continue;
+                }
+                if !is_literal(&typ) {
+                    return None;
+                }
+                members.push(BranchMember {
+                    label: humanize_lint_type(semantic_model.get_db(), &typ),
+                    typ,
+                });
+            }
+            members
+        }
+        _ => return None,
+    };
+
+    (members.len() > 1).then_some(members)
+}
+
+fn get_enum_members(
+    semantic_model: &SemanticModel,
+    type_decl_id: &LuaTypeDeclId,
+) -> Option<Vec<BranchMember>> {
+    let db = semantic_model.get_db();
+    let type_decl = db.get_type_index().get_type_decl(type_decl_id)?;
+    if !type_decl.is_enum() {
+        return None;
+    }
+
+    let enum_name = type_decl.get_name();
+    let is_enum_key = type_decl.is_enum_key();
+    let mut members = db
+        .get_member_index()
+        .get_sorted_members(&LuaMemberOwner::Type(type_decl_id.clone()))?;
+    members.retain(|member| !matches!(member.get_key(), LuaMemberKey::None));
+
+    let mut result = Vec::new();
+    for member in members {
+        let key = member.get_key();
+        let typ = if is_enum_key {
+            match key {
+                LuaMemberKey::Name(name) => LuaType::DocStringConst(name.clone().into()),
+                LuaMemberKey::Integer(i) => LuaType::DocIntegerConst(*i),
+                _ => return None,
+            }
+        } else {
+            db.get_type_index()
+                .get_type_cache(&member.get_id().into())?
+                .as_type()
+                .clone()
+        };
+        if !is_literal(&typ) {
+            return None;
+        }
+
+        let label = if is_enum_key {
+            humanize_lint_type(db, &typ)
+        } else {
+            match key {
+                LuaMemberKey::Name(name) => format!("{}.{}", enum_name, name),
+                LuaMemberKey::Integer(i) => format!("{}[{}]", enum_name, i),
+                _ => humanize_lint_type(db, &typ),
+            }
+        };
+        result.push(BranchMember { label, typ });
+    }
+
+    Some(result)
+}
+
+/// 检查以 `---@enum` 为键类型的分派表是否为每个枚举成员都提供了值
+fn check_dispatch_table(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    table_expr: LuaTableExpr,
+) -> Option<()> {
+    if table_expr.is_empty() || table_expr.is_array() {
+        return Some(());
+    }
+
+    let LuaType::TableGeneric(params) = semantic_model.infer_table_should_be(table_expr.clone())?
+    else {
+        return Some(());
+    };
+    let LuaType::Ref(key_type_id) = params.first()? else {
+        return Some(());
+    };
+    let members = get_enum_members(semantic_model, key_type_id)?;
+
+    let mut key_types = Vec::new();
+    for field in table_expr.get_fields() {
+        let key = field.get_field_key()?;
+        let key_type = match semantic_model.get_member_key(&key)? {
+            LuaMemberKey::Name(name) => LuaType::DocStringConst(name.into()),
+            LuaMemberKey::Integer(i) => LuaType::DocIntegerConst(i),
+            LuaMemberKey::TypeKey(typ) => typ,
+            LuaMemberKey::None => return Some(()),
+        };
+        key_types.push(key_type);
+    }
+
+    let missing = members
+        .into_iter()
+        .filter(|member| {
+            !key_types
+                .iter()
+                .any(|key_type| is_same_literal(&member.typ, key_type))
+        })
+        .map(|member| member.label)
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return Some(());
+    }
+
+    // 只有值为函数时才能补出一个空实现, 其他类型的值无法给出合理的默认值, 不提供快速修复
+    let data = params.get(1).filter(|typ| typ.is_function()).map(|_| {
+        serde_json::json!({
+            "missing": missing,
+            "value": "function() end",
+        })
+    });
+    let open_token = table_expr.token_by_kind(LuaTokenKind::TkLeftBrace)?;
+    context.add_diagnostic(
+        DiagnosticCode::NonExhaustiveBranches,
+        open_token.get_range(),
+        t!(
+            "Non-exhaustive dispatch table, missing keys: %{members}",
+            members = missing.join(", ")
+        )
+        .to_string(),
+        data,
+    );
+    Some(())
+}
+
+fn is_literal(typ: &LuaType) -> bool {
+    matches!(
+        typ,
+        LuaType::StringConst(_)
+            | LuaType::DocStringConst(_)
+            | LuaType::IntegerConst(_)
+            | LuaType::DocIntegerConst(_)
+            | LuaType::BooleanConst(_)
+            | LuaType::DocBooleanConst(_)
+    )
+}
+
+fn is_same_literal(left: &LuaType, right: &LuaType) -> bool {
+    match (left, right) {
+        (
+            LuaType::StringConst(l) | LuaType::DocStringConst(l),
+            LuaType::StringConst(r) | LuaType::DocStringConst(r),
+        ) => l == r,
+        (
+            LuaType::IntegerConst(l) | LuaType::DocIntegerConst(l),
+            LuaType::IntegerConst(r) | LuaType::DocIntegerConst(r),
+        ) => l == r,
+        (
+            LuaType::BooleanConst(l) | LuaType::DocBooleanConst(l),
+            LuaType::BooleanConst(r) | LuaType::DocBooleanConst(r),
+        ) => l == r,
+        _ => false,
+    }
+}
+
+fn normalize_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
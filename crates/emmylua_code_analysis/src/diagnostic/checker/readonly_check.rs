@@ -1,9 +1,11 @@
-use emmylua_parser::{LuaAssignStat, LuaAst, LuaAstNode, LuaExpr, LuaSyntaxId, LuaSyntaxKind};
+use emmylua_parser::{
+    LuaAssignStat, LuaAst, LuaAstNode, LuaExpr, LuaIndexExpr, LuaSyntaxId, LuaSyntaxKind,
+};
 use rowan::{NodeOrToken, TextRange};
 
 use crate::{
-    DiagnosticCode, LuaDeclId, LuaMemberId, LuaSemanticDeclId, PropertyDeclFeature,
-    SemanticDeclLevel, SemanticModel,
+    DbIndex, DiagnosticCode, LuaDeclId, LuaMemberId, LuaSemanticDeclId, LuaType,
+    PropertyDeclFeature, SemanticDeclLevel, SemanticModel,
 };
 
 use super::{Checker, DiagnosticContext};
@@ -88,7 +90,14 @@ fn check_assign_stat(
             }
             match var {
                 LuaExpr::IndexExpr(index_expr) => {
-                    var = index_expr.get_prefix_expr()?;
+                    let prefix_expr = index_expr.get_prefix_expr()?;
+                    check_readonly_mapped_prefix(
+                        context,
+                        semantic_model,
+                        &prefix_expr,
+                        &index_expr,
+                    );
+                    var = prefix_expr;
                 }
                 _ => {
                     break;
@@ -99,3 +108,54 @@ fn check_assign_stat(
 
     Some(())
 }
+
+// Readonly<T> 等 readonly 映射类型的字段不允许赋值
+fn check_readonly_mapped_prefix(
+    context: &mut DiagnosticContext,
+    semantic_model: &SemanticModel,
+    prefix_expr: &LuaExpr,
+    index_expr: &LuaIndexExpr,
+) -> Option<()> {
+    let prefix_type = semantic_model.infer_expr(prefix_expr.clone()).ok()?;
+    if !is_readonly_mapped_type(semantic_model.get_db(), &prefix_type, 0) {
+        return Some(());
+    }
+
+    let name = index_expr.get_index_key()?.get_path_part();
+    context.add_diagnostic(
+        DiagnosticCode::ReadOnly,
+        index_expr.get_range(),
+        t!(
+            "The field `%{name}` is readonly and cannot be assigned to.",
+            name = name
+        )
+        .to_string(),
+        None,
+    );
+    Some(())
+}
+
+fn is_readonly_mapped_type(db: &DbIndex, typ: &LuaType, depth: usize) -> bool {
+    if depth > 10 {
+        return false;
+    }
+
+    match typ {
+        LuaType::Mapped(mapped) => mapped.is_readonly,
+        LuaType::Ref(type_id) => db
+            .get_type_index()
+            .get_type_decl(type_id)
+            .and_then(|decl| decl.get_alias_ref())
+            .is_some_and(|origin| is_readonly_mapped_type(db, origin, depth + 1)),
+        LuaType::Generic(generic) => db
+            .get_type_index()
+            .get_type_decl(generic.get_base_type_id_ref())
+            .and_then(|decl| decl.get_alias_ref())
+            .is_some_and(|origin| is_readonly_mapped_type(db, origin, depth + 1)),
+        LuaType::Union(union) => union
+            .into_vec()
+            .iter()
+            .any(|typ| is_readonly_mapped_type(db, typ, depth + 1)),
+        _ => false,
+    }
+}
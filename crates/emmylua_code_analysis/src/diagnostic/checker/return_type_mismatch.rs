@@ -7,6 +7,7 @@ use crate::{
     DiagnosticCode, LuaSemanticDeclId, LuaSignatureId, LuaType, SemanticDeclLevel, SemanticModel,
     SignatureReturnStatus, TypeCheckFailReason, TypeCheckResult,
     diagnostic::checker::{assign_type_mismatch::check_table_expr, humanize_lint_type},
+    get_opaque_base_type,
 };
 
 use super::{Checker, DiagnosticContext, get_return_stats};
@@ -87,7 +88,14 @@ fn check_return_stat(
                 }
 
                 let result = semantic_model.type_check_detail(check_type, return_expr_type);
-                if result.is_err() {
+                if result.is_err()
+                    && !is_opaque_constructor_return(
+                        semantic_model,
+                        self_type,
+                        check_type,
+                        return_expr_type,
+                    )
+                {
                     if return_expr_type.is_table()
                         && let Some(return_expr) = return_exprs.get(index)
                     {
@@ -124,7 +132,14 @@ fn check_return_stat(
             let return_expr_type = &return_expr_types[0];
             let return_expr_range = return_expr_ranges[0];
             let result = semantic_model.type_check_detail(check_type, return_expr_type);
-            if result.is_err() {
+            if result.is_err()
+                && !is_opaque_constructor_return(
+                    semantic_model,
+                    self_type,
+                    check_type,
+                    return_expr_type,
+                )
+            {
                 if return_expr_type.is_table()
                     && let Some(return_expr) = return_exprs.first()
                 {
@@ -156,6 +171,29 @@ fn check_return_stat(
     Some(())
 }
 
+/// opaque 类型自身的成员函数视为其构造函数, 允许直接返回基础类型的值
+fn is_opaque_constructor_return(
+    semantic_model: &SemanticModel,
+    self_type: &Option<LuaType>,
+    check_type: &LuaType,
+    return_expr_type: &LuaType,
+) -> bool {
+    let (Some(LuaType::Def(self_id) | LuaType::Ref(self_id)), LuaType::Ref(return_id)) =
+        (self_type, check_type)
+    else {
+        return false;
+    };
+    if self_id != return_id {
+        return false;
+    }
+
+    get_opaque_base_type(semantic_model.get_db(), check_type).is_some_and(|base_type| {
+        semantic_model
+            .type_check(base_type, return_expr_type)
+            .is_ok()
+    })
+}
+
 fn add_type_check_diagnostic(
     context: &mut DiagnosticContext,
     semantic_model: &SemanticModel,
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 pub use super::checker::DiagnosticContext;
 use super::{checker::check_file, lua_diagnostic_config::LuaDiagnosticConfig};
@@ -10,6 +10,7 @@ use tokio_util::sync::CancellationToken;
 pub struct LuaDiagnostic {
     enable: bool,
     config: Arc<LuaDiagnosticConfig>,
+    scoped_configs: Vec<(PathBuf, bool, Arc<LuaDiagnosticConfig>)>,
 }
 
 impl Default for LuaDiagnostic {
@@ -23,6 +24,7 @@ impl LuaDiagnostic {
         Self {
             enable: true,
             config: Arc::new(LuaDiagnosticConfig::default()),
+            scoped_configs: Vec::new(),
         }
     }
 
@@ -31,6 +33,17 @@ impl LuaDiagnostic {
         self.config = LuaDiagnosticConfig::new(&emmyrc).into();
     }
 
+    pub fn update_scoped_configs(&mut self, mut scoped_configs: Vec<(PathBuf, Arc<Emmyrc>)>) {
+        scoped_configs.sort_by_key(|(root, _)| std::cmp::Reverse(root.components().count()));
+        self.scoped_configs = scoped_configs
+            .into_iter()
+            .map(|(root, emmyrc)| {
+                let enable = emmyrc.diagnostics.enable;
+                (root, enable, LuaDiagnosticConfig::new(&emmyrc).into())
+            })
+            .collect();
+    }
+
     // 只开启指定的诊断
     pub fn enable_only(&mut self, code: DiagnosticCode) {
         let mut emmyrc = Emmyrc::default();
@@ -41,6 +54,7 @@ impl LuaDiagnostic {
             }
         }
         self.config = LuaDiagnosticConfig::new(&emmyrc).into();
+        self.scoped_configs.clear();
     }
 
     pub fn diagnose_file(
@@ -49,7 +63,9 @@ impl LuaDiagnostic {
         file_id: FileId,
         cancel_token: CancellationToken,
     ) -> Option<Vec<Diagnostic>> {
-        if !self.enable {
+        let db = compilation.get_db();
+        let (enable, config) = self.get_file_config(compilation, file_id);
+        if !enable {
             return None;
         }
 
@@ -57,7 +73,6 @@ impl LuaDiagnostic {
             return None;
         }
 
-        let db = compilation.get_db();
         if let Some(module_info) = db.get_module_index().get_workspace_id(file_id)
             && !module_info.is_main()
         {
@@ -65,10 +80,27 @@ impl LuaDiagnostic {
         }
 
         let semantic_model = compilation.get_semantic_model(file_id)?;
-        let mut context = DiagnosticContext::new(file_id, db, self.config.clone());
+        let mut context = DiagnosticContext::new(file_id, db, config);
 
         check_file(&mut context, &semantic_model);
 
         Some(context.get_diagnostics())
     }
+
+    fn get_file_config(
+        &self,
+        compilation: &LuaCompilation,
+        file_id: FileId,
+    ) -> (bool, Arc<LuaDiagnosticConfig>) {
+        if let Some(path) = compilation.get_db().get_vfs().get_file_path(&file_id)
+            && let Some((_, enable, config)) = self
+                .scoped_configs
+                .iter()
+                .find(|(root, _, _)| path.starts_with(root))
+        {
+            return (*enable, config.clone());
+        }
+
+        (self.enable, self.config.clone())
+    }
 }
@@ -123,6 +123,8 @@ pub enum DiagnosticCode {
     InconsistentTypeAccessModifier,
     /// missing-type-argument
     MissingTypeArgument,
+    /// non-exhaustive-branches
+    NonExhaustiveBranches,
     #[serde(other)]
     None,
 }
@@ -163,6 +165,7 @@ pub fn is_code_default_enable(code: &DiagnosticCode, level: LuaLanguageLevel) ->
         DiagnosticCode::IncompleteSignatureDoc => false,
         DiagnosticCode::MissingGlobalDoc => false,
         DiagnosticCode::UnknownDocTag => false,
+        DiagnosticCode::NonExhaustiveBranches => false,
         // ... handle other variants
 
         // neovim-code-style
@@ -26,4 +26,40 @@ mod test {
             "#
         ));
     }
+
+    #[test]
+    fn test_duplicate_const_index() {
+        let mut ws = VirtualWorkspace::new();
+
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::DuplicateIndex,
+            r#"
+                local a = {
+                    [8] = 1,
+                    [1 << 3] = 2,
+                }
+            "#
+        ));
+
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::DuplicateIndex,
+            r#"
+                local key <const> = "b"
+                local a = {
+                    b = 1,
+                    [key] = 2,
+                }
+            "#
+        ));
+
+        assert!(ws.has_no_diagnostic(
+            DiagnosticCode::DuplicateIndex,
+            r#"
+                local a = {
+                    [1 << 2] = 1,
+                    [1 << 3] = 2,
+                }
+            "#
+        ));
+    }
 }
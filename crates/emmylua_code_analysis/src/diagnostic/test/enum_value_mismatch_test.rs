@@ -142,4 +142,80 @@ mod tests {
             "#,
         ));
     }
+
+    #[test]
+    fn test_enum_value_mismatch_const_expr() {
+        let mut ws = VirtualWorkspace::new();
+
+        ws.def(
+            r#"
+                ---@enum Flags
+                Flags = {
+                    READ = 1 << 0,
+                    WRITE = 1 << 1,
+                    EXEC = 1 << 2,
+                }
+            "#,
+        );
+
+        assert!(ws.has_no_diagnostic(
+            DiagnosticCode::EnumValueMismatch,
+            r#"
+                ---@type Flags
+                local flag
+
+                if flag == 4 then
+                end
+                "#,
+        ));
+
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::EnumValueMismatch,
+            r#"
+                ---@type Flags
+                local flag
+
+                if flag == 3 then
+                end
+                "#,
+        ));
+    }
+
+    #[test]
+    fn test_enum_value_mismatch_after_narrowed_branches() {
+        let mut ws = VirtualWorkspace::new();
+
+        ws.def(
+            r#"
+                ---@enum Color
+                Color = { Red = 1, Green = 2, Blue = 3 }
+            "#,
+        );
+
+        // 前面的分支把 color 收窄为剩余成员, 后面与其他成员比较仍然合法
+        assert!(ws.has_no_diagnostic(
+            DiagnosticCode::EnumValueMismatch,
+            r#"
+                ---@param color Color
+                local function paint(color)
+                    if color == Color.Red then
+                    elseif color == Color.Green then
+                    elseif color == Color.Blue then
+                    end
+                end
+                "#,
+        ));
+
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::EnumValueMismatch,
+            r#"
+                ---@param color Color
+                local function paint(color)
+                    if color == Color.Red then
+                    elseif color == 5 then
+                    end
+                end
+                "#,
+        ));
+    }
 }
@@ -18,6 +18,7 @@ mod inject_field_test;
 mod missing_fields_test;
 mod missing_parameter_test;
 mod need_check_nil_test;
+mod non_exhaustive_branches_test;
 mod param_type_check_test;
 mod readonly_check;
 mod redefined_local_test;
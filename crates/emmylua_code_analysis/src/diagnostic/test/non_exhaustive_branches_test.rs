@@ -0,0 +1,209 @@
+#[cfg(test)]
+mod tests {
+    use crate::{DiagnosticCode, VirtualWorkspace};
+
+    #[test]
+    fn test_literal_union_branches() {
+        let mut ws = VirtualWorkspace::new();
+
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::NonExhaustiveBranches,
+            r#"
+            ---@param mode 'read'|'write'|'append'
+            local function open(mode)
+                if mode == 'read' then
+                elseif mode == 'write' then
+                end
+            end
+            "#,
+        ));
+
+        assert!(ws.has_no_diagnostic(
+            DiagnosticCode::NonExhaustiveBranches,
+            r#"
+            ---@param mode 'read'|'write'|'append'
+            local function open(mode)
+                if mode == 'read' then
+                elseif mode == 'write' or mode == 'append' then
+                end
+            end
+            "#,
+        ));
+
+        // `else` 分支处理了剩余的值
+        assert!(ws.has_no_diagnostic(
+            DiagnosticCode::NonExhaustiveBranches,
+            r#"
+            ---@param mode 'read'|'write'|'append'
+            local function open(mode)
+                if mode == 'read' then
+                elseif mode == 'write' then
+                else
+                end
+            end
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_enum_branches() {
+        let mut ws = VirtualWorkspace::new();
+
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::NonExhaustiveBranches,
+            r#"
+            ---@enum Direction
+            Direction = { Up = 1, Down = 2, Left = 3 }
+
+            ---@param dir Direction
+            local function move(dir)
+                if dir == Direction.Up then
+                elseif dir == Direction.Down then
+                end
+            end
+            "#,
+        ));
+
+        assert!(ws.has_no_diagnostic(
+            DiagnosticCode::NonExhaustiveBranches,
+            r#"
+            ---@enum Direction2
+            Direction2 = { Up = 1, Down = 2 }
+
+            ---@param dir Direction2
+            local function move(dir)
+                if dir == Direction2.Up then
+                elseif dir == Direction2.Down then
+                end
+            end
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_discriminated_field_branches() {
+        let mut ws = VirtualWorkspace::new();
+
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::NonExhaustiveBranches,
+            r#"
+            ---@class Shape
+            ---@field kind 'circle'|'square'|'triangle'
+
+            ---@param shape Shape
+            local function area(shape)
+                if shape.kind == 'circle' then
+                elseif shape.kind == 'square' then
+                end
+            end
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_mixed_conditions_are_ignored() {
+        let mut ws = VirtualWorkspace::new();
+
+        assert!(ws.has_no_diagnostic(
+            DiagnosticCode::NonExhaustiveBranches,
+            r#"
+            ---@param mode 'read'|'write'|'append'
+            ---@param force boolean
+            local function open(mode, force)
+                if mode == 'read' then
+                elseif force then
+                end
+            end
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_dispatch_table() {
+        let mut ws = VirtualWorkspace::new();
+
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::NonExhaustiveBranches,
+            r#"
+            ---@enum Event
+            Event = { Open = 1, Close = 2, Error = 3 }
+
+            ---@type table<Event, fun()>
+            local handlers = {
+                [Event.Open] = function() end,
+                [Event.Close] = function() end,
+            }
+            "#,
+        ));
+
+        assert!(ws.has_no_diagnostic(
+            DiagnosticCode::NonExhaustiveBranches,
+            r#"
+            ---@enum Event2
+            Event2 = { Open = 1, Close = 2 }
+
+            ---@type table<Event2, fun()>
+            local handlers = {
+                [Event2.Open] = function() end,
+                [Event2.Close] = function() end,
+            }
+            "#,
+        ));
+    }
+
+    #[test]
+    fn test_never_assertion() {
+        let mut ws = VirtualWorkspace::new();
+
+        assert!(ws.has_no_diagnostic(
+            DiagnosticCode::AssignTypeMismatch,
+            r#"
+            ---@enum Suit
+            Suit = { Hearts = 1, Spades = 2 }
+
+            ---@param suit Suit
+            local function name(suit)
+                if suit == Suit.Hearts then
+                elseif suit == Suit.Spades then
+                else
+                    ---@type never
+                    local unreachable = suit
+                end
+            end
+            "#,
+        ));
+
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::AssignTypeMismatch,
+            r#"
+            ---@param mode 'read'|'write'|'append'
+            local function open(mode)
+                if mode == 'read' then
+                elseif mode == 'write' then
+                else
+                    ---@type never
+                    local unreachable = mode
+                end
+            end
+            "#,
+        ));
+
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            ---@param value never
+            local function assert_never(value)
+            end
+
+            ---@param mode 'read'|'write'|'append'
+            local function open(mode)
+                if mode == 'read' then
+                elseif mode == 'write' then
+                else
+                    assert_never(mode)
+                end
+            end
+            "#,
+        ));
+    }
+}
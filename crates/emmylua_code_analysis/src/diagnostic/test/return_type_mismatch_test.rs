@@ -864,4 +864,36 @@ mod tests {
             "#
         ));
     }
+
+    #[test]
+    fn test_opaque_type_constructor_return() {
+        let mut ws = VirtualWorkspace::new();
+
+        assert!(ws.has_no_diagnostic(
+            DiagnosticCode::ReturnTypeMismatch,
+            r#"
+            ---@class (opaque) EntityId: integer
+            local EntityId = {}
+
+            ---@param raw integer
+            ---@return EntityId
+            function EntityId.new(raw)
+                return raw
+            end
+            "#
+        ));
+
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::ReturnTypeMismatch,
+            r#"
+            ---@class (opaque) ItemId: integer
+
+            ---@param raw integer
+            ---@return ItemId
+            local function make_item_id(raw)
+                return raw
+            end
+            "#
+        ));
+    }
 }
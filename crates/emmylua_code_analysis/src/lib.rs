@@ -8,6 +8,7 @@
     )
 )]
 
+mod annotation;
 mod compilation;
 mod config;
 mod db_index;
@@ -16,13 +17,21 @@ mod locale;
 mod profile;
 mod resources;
 mod semantic;
+mod ssr;
 mod test_lib;
 mod vfs;
 
+pub use annotation::{
+    AnnotationEdit, RuntimeTrace, TraceFunction, build_closure_trace_annotation, build_doc_stubs,
+    build_function_doc_stub, build_trace_annotations,
+};
 pub use compilation::*;
 pub use config::*;
 pub use db_index::*;
 pub use diagnostic::*;
+use emmylua_parser::{
+    LuaAstNode, LuaCallExpr, LuaDocTagModule, LuaExpr, LuaLiteralToken, LuaSyntaxNode,
+};
 pub use locale::get_locale_code;
 use lsp_types::Uri;
 pub use profile::Profile;
@@ -30,7 +39,12 @@ pub use resources::get_best_resources_dir;
 pub use resources::load_resource_from_include_dir;
 use resources::load_resource_std;
 pub use semantic::*;
-use std::{collections::HashSet, path::PathBuf, sync::Arc};
+pub use ssr::{SsrMatch, SsrRule, apply_text_edits};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 pub use test_lib::VirtualWorkspace;
 use tokio_util::sync::CancellationToken;
 pub use vfs::*;
@@ -65,8 +79,15 @@ impl EmmyLuaAnalysis {
         }
     }
 
+    /// Loads the std library. Its LuaJIT modules are loaded when the workspace config or a
+    /// scoped config targets LuaJIT, so scoped configs must be set before, see
+    /// `update_scoped_configs`. Their `---@version` tags hide them from the other files.
     pub fn init_std_lib(&mut self, create_resources_dir: Option<String>) {
-        let is_jit = self.emmyrc.runtime.version.is_luajit();
+        let is_jit = self.emmyrc.runtime.version.is_luajit()
+            || self
+                .get_scoped_configs()
+                .iter()
+                .any(|(_, emmyrc)| emmyrc.runtime.version.is_luajit());
         let (std_root, files) = load_resource_std(create_resources_dir, is_jit);
         self.compilation
             .get_db_mut()
@@ -113,6 +134,141 @@ impl EmmyLuaAnalysis {
         );
     }
 
+    /// Adds the files of a workspace folder added at runtime and re-indexes the files whose
+    /// `require`s resolve to one of them.
+    pub fn add_workspace_files(&mut self, files: Vec<(PathBuf, Option<String>)>) -> Vec<FileId> {
+        let file_ids = self.update_files_by_path(files);
+        let added = file_ids.iter().copied().collect::<HashSet<_>>();
+        let dependents = self.get_requiring_files(&added);
+        self.reindex_files(dependents);
+        file_ids
+    }
+
+    /// Removes the workspace folder `root` together with the workspaces and scoped configs that
+    /// the config files below it contributed, see `update_scoped_configs`. A workspace still
+    /// contributed by the workspace config or by another scoped config is kept. The files no
+    /// remaining workspace contains are removed and the files that required them are
+    /// re-indexed. Returns the uris of the removed files.
+    pub fn remove_workspace_folder(&mut self, root: &Path) -> Vec<Uri> {
+        let (removed_configs, kept_configs): (Vec<_>, Vec<_>) = self
+            .get_scoped_configs()
+            .into_iter()
+            .partition(|(dir, _)| dir.starts_with(root));
+        let mut kept_workspaces = build_workspace_folders(&[], &self.emmyrc);
+        for (_, emmyrc) in &kept_configs {
+            kept_workspaces.extend(build_workspace_folders(&[], emmyrc));
+        }
+
+        let mut removed_workspaces = vec![WorkspaceFolder::new(root.to_path_buf(), false)];
+        for (_, emmyrc) in &removed_configs {
+            for workspace in build_workspace_folders(&[], emmyrc) {
+                let is_same = |other: &WorkspaceFolder| {
+                    other.root == workspace.root && other.import == workspace.import
+                };
+                if !kept_workspaces.iter().any(is_same) && !removed_workspaces.iter().any(is_same) {
+                    removed_workspaces.push(workspace);
+                }
+            }
+        }
+        if !removed_configs.is_empty() {
+            self.update_scoped_configs(kept_configs);
+        }
+
+        self.remove_workspaces(&removed_workspaces)
+    }
+
+    fn remove_workspaces(&mut self, workspaces: &[WorkspaceFolder]) -> Vec<Uri> {
+        let module_index = self.compilation.get_db_mut().get_module_index_mut();
+        for workspace in workspaces {
+            module_index.remove_workspace(&workspace.root, &workspace.import);
+        }
+
+        let mut removed_files = HashSet::new();
+        let mut moved_files = Vec::new();
+        {
+            let db = self.compilation.get_db();
+            let vfs = db.get_vfs();
+            let module_index = db.get_module_index();
+            for file_id in vfs.get_all_local_file_ids() {
+                if module_index.is_std(&file_id) {
+                    continue;
+                }
+                let Some(path) = vfs.get_file_path(&file_id) else {
+                    continue;
+                };
+                if !workspaces
+                    .iter()
+                    .any(|workspace| path.starts_with(&workspace.root))
+                {
+                    continue;
+                }
+                let in_other_workspace = path
+                    .to_str()
+                    .and_then(|path| module_index.extract_module_path(path))
+                    .is_some();
+                if in_other_workspace {
+                    moved_files.push(file_id);
+                } else {
+                    removed_files.insert(file_id);
+                }
+            }
+        }
+
+        let mut dependents = self.get_requiring_files(&removed_files);
+        let removed_uris = removed_files
+            .iter()
+            .filter_map(|file_id| self.get_uri(*file_id))
+            .collect::<Vec<_>>();
+        for uri in &removed_uris {
+            self.remove_file_by_uri(uri);
+        }
+        dependents.extend(moved_files);
+        self.reindex_files(dependents);
+        removed_uris
+    }
+
+    /// Returns the files outside `file_ids` that depend on one of `file_ids` through a
+    /// `require` call, including the require-like functions of the config, or a `---@module`
+    /// tag. Besides the dependencies recorded when the files were indexed, the module paths
+    /// are resolved again, since a module added since then was unresolved at that time.
+    fn get_requiring_files(&self, file_ids: &HashSet<FileId>) -> Vec<FileId> {
+        if file_ids.is_empty() {
+            return Vec::new();
+        }
+
+        let db = self.compilation.get_db();
+        let vfs = db.get_vfs();
+        let module_index = db.get_module_index();
+        let dependency_index = db.get_file_dependencies_index();
+        vfs.get_all_file_ids()
+            .into_iter()
+            .filter(|file_id| !file_ids.contains(file_id) && !module_index.is_std(file_id))
+            .filter(|file_id| {
+                if dependency_index
+                    .get_required_files(file_id)
+                    .is_some_and(|required| required.iter().any(|id| file_ids.contains(id)))
+                {
+                    return true;
+                }
+                let Some(tree) = vfs.get_syntax_tree(file_id) else {
+                    return false;
+                };
+                get_module_references(tree.get_red_root())
+                    .filter_map(|module_path| module_index.find_module(&module_path))
+                    .any(|module_info| file_ids.contains(&module_info.file_id))
+            })
+            .collect()
+    }
+
+    fn reindex_files(&mut self, file_ids: Vec<FileId>) {
+        if file_ids.is_empty() {
+            return;
+        }
+
+        self.compilation.remove_index(file_ids.clone());
+        self.compilation.update_index(file_ids);
+    }
+
     pub fn clear_non_std_workspaces(&mut self) {
         self.compilation
             .get_db_mut()
@@ -290,10 +446,32 @@ impl EmmyLuaAnalysis {
         self.diagnostic.update_config(config);
     }
 
+    /// Sets the configs of directories that have their own config file. Files below such a
+    /// directory are parsed, analyzed and diagnosed with its config instead of the workspace
+    /// config. Must be called before the files are loaded.
+    pub fn update_scoped_configs(&mut self, scoped_configs: Vec<(PathBuf, Arc<Emmyrc>)>) {
+        self.compilation
+            .update_scoped_configs(scoped_configs.clone());
+        self.diagnostic.update_scoped_configs(scoped_configs);
+    }
+
+    pub fn get_scoped_configs(&self) -> Vec<(PathBuf, Arc<Emmyrc>)> {
+        self.compilation
+            .get_db()
+            .get_vfs()
+            .get_scoped_configs()
+            .to_vec()
+    }
+
     pub fn get_emmyrc(&self) -> Arc<Emmyrc> {
         self.emmyrc.clone()
     }
 
+    /// Returns the config that applies to the file.
+    pub fn get_file_emmyrc(&self, file_id: &FileId) -> Arc<Emmyrc> {
+        self.compilation.get_db().get_file_emmyrc(file_id)
+    }
+
     pub fn diagnose_file(
         &self,
         file_id: FileId,
@@ -342,6 +520,29 @@ impl EmmyLuaAnalysis {
     }
 }
 
+/// Module paths referenced by the `require` calls and `---@module` tags below `root`.
+fn get_module_references(root: LuaSyntaxNode) -> impl Iterator<Item = String> {
+    root.descendants().filter_map(|node| {
+        if let Some(call_expr) = LuaCallExpr::cast(node.clone()) {
+            if !call_expr.is_require() {
+                return None;
+            }
+            let LuaExpr::LiteralExpr(literal_expr) =
+                call_expr.get_args_list()?.get_args().next()?
+            else {
+                return None;
+            };
+            let LuaLiteralToken::String(string_token) = literal_expr.get_literal()? else {
+                return None;
+            };
+            return Some(string_token.get_value());
+        }
+
+        let module_tag = LuaDocTagModule::cast(node)?;
+        Some(module_tag.get_string_token()?.get_value())
+    })
+}
+
 impl Default for EmmyLuaAnalysis {
     fn default() -> Self {
         Self::new()
@@ -400,6 +601,177 @@ mod tests {
         assert_eq!(analysis.reindex_count, 1);
     }
 
+    #[test]
+    fn add_and_remove_workspace_folder_updates_dependents() {
+        let mut analysis = EmmyLuaAnalysis::new();
+        analysis.update_config(Arc::new(Emmyrc::default()));
+        let current_dir = std::env::current_dir().unwrap();
+        let main_root = current_dir.join("__workspace_folder_main");
+        let added_root = current_dir.join("__workspace_folder_added");
+        analysis.add_main_workspace(main_root.clone());
+        let main_file = analysis.update_files_by_path(vec![(
+            main_root.join("main.lua"),
+            Some("local util = require(\"util\")\nreturn util\n".to_string()),
+        )])[0];
+        let required_files = |analysis: &EmmyLuaAnalysis| {
+            analysis
+                .compilation
+                .get_db()
+                .get_file_dependencies_index()
+                .get_required_files(&main_file)
+                .cloned()
+                .unwrap_or_default()
+        };
+        assert!(required_files(&analysis).is_empty());
+
+        analysis.add_main_workspace(added_root.clone());
+        let util_file = analysis.add_workspace_files(vec![(
+            added_root.join("util.lua"),
+            Some("return {}\n".to_string()),
+        )])[0];
+        assert!(required_files(&analysis).contains(&util_file));
+
+        let removed = analysis.remove_workspace_folder(&added_root);
+        assert_eq!(removed.len(), 1);
+        assert!(analysis.get_file_id(&removed[0]).is_none());
+        assert!(required_files(&analysis).is_empty());
+    }
+
+    #[test]
+    fn remove_workspace_folder_removes_its_libraries_and_updates_module_tags() {
+        let mut analysis = EmmyLuaAnalysis::new();
+        analysis.update_config(Arc::new(Emmyrc::default()));
+        let current_dir = std::env::current_dir().unwrap();
+        let main_root = current_dir.join("__workspace_folder_tag_main");
+        let added_root = current_dir.join("__workspace_folder_tag_added");
+        let library_root = current_dir.join("__workspace_folder_tag_library");
+        analysis.add_main_workspace(main_root.clone());
+        let main_file = analysis.update_files_by_path(vec![(
+            main_root.join("main.lua"),
+            Some("---@module \"util\"\nlocal util\nreturn util\n".to_string()),
+        )])[0];
+        let util_type = |analysis: &EmmyLuaAnalysis| {
+            analysis
+                .compilation
+                .get_db()
+                .get_type_index()
+                .get_type_cache(&LuaDeclId::new(main_file, 24.into()).into())
+                .map(|cache| cache.as_type().clone())
+        };
+        assert!(!matches!(util_type(&analysis), Some(LuaType::ModuleRef(_))));
+
+        // the config of the added folder contributes a library
+        let folder_emmyrc: Emmyrc = serde_json::from_value(serde_json::json!({
+            "workspace": { "library": [library_root.to_string_lossy()] }
+        }))
+        .unwrap();
+        let folder_workspaces = build_workspace_folders(
+            &[WorkspaceFolder::new(added_root.clone(), false)],
+            &folder_emmyrc,
+        );
+        analysis.update_scoped_configs(vec![(added_root.clone(), Arc::new(folder_emmyrc))]);
+        for workspace in &folder_workspaces {
+            if workspace.is_library {
+                analysis.add_library_workspace(workspace);
+            } else {
+                analysis.add_main_workspace(workspace.root.clone());
+            }
+        }
+        let file_ids = analysis.add_workspace_files(vec![
+            (added_root.join("util.lua"), Some("return {}\n".to_string())),
+            (
+                library_root.join("lib.lua"),
+                Some("return {}\n".to_string()),
+            ),
+        ]);
+        let util_file = analysis
+            .get_file_id(&file_path_to_uri(&added_root.join("util.lua")).unwrap())
+            .unwrap();
+        assert_eq!(util_type(&analysis), Some(LuaType::ModuleRef(util_file)));
+
+        let removed = analysis.remove_workspace_folder(&added_root);
+        assert_eq!(removed.len(), 2);
+        assert!(
+            file_ids
+                .iter()
+                .all(|file_id| analysis.get_uri(*file_id).is_none())
+        );
+        assert!(analysis.get_scoped_configs().is_empty());
+        assert!(!matches!(util_type(&analysis), Some(LuaType::ModuleRef(_))));
+        let module_index = analysis.compilation.get_db().get_module_index();
+        assert!(
+            module_index
+                .extract_module_path(&library_root.join("lib.lua").to_string_lossy())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn scoped_config_applies_to_files_below_its_dir() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let counter = TEST_ANALYSIS_WORKSPACE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let temp_root = std::env::temp_dir().join(format!(
+            "emmylua-analysis-scoped-config-{}-{}-{}",
+            std::process::id(),
+            unique,
+            counter,
+        ));
+        let scope_root = temp_root.join("sub");
+        fs::create_dir_all(&scope_root).unwrap();
+        fs::create_dir_all(temp_root.join(".hidden")).unwrap();
+        fs::write(temp_root.join(".emmyrc.json"), "{}").unwrap();
+        fs::write(scope_root.join(".emmyrc.json"), "{}").unwrap();
+        fs::write(temp_root.join(".hidden").join(".emmyrc.json"), "{}").unwrap();
+
+        let workspaces = vec![WorkspaceFolder::new(temp_root.clone(), false)];
+        let scope_dirs = find_config_scope_dirs(&workspaces, &Emmyrc::default(), Some(&temp_root));
+        assert_eq!(scope_dirs, vec![scope_root.clone()]);
+
+        let mut analysis = EmmyLuaAnalysis::new();
+        analysis.update_config(Arc::new(Emmyrc::default()));
+        let mut scoped_emmyrc = Emmyrc::default();
+        scoped_emmyrc
+            .diagnostics
+            .disable
+            .push(DiagnosticCode::UndefinedGlobal);
+        let scoped_emmyrc = Arc::new(scoped_emmyrc);
+        analysis.update_scoped_configs(vec![(scope_root.clone(), scoped_emmyrc.clone())]);
+        analysis.add_main_workspace(temp_root.clone());
+        let file_ids = [temp_root.join("a.lua"), scope_root.join("b.lua")].map(|path| {
+            analysis
+                .update_file_by_path(&path, Some("print(foo)\n".to_string()))
+                .unwrap()
+        });
+
+        let has_undefined_global = |file_id: FileId| {
+            analysis
+                .diagnose_file(file_id, CancellationToken::new())
+                .unwrap_or_default()
+                .iter()
+                .any(|diagnostic| {
+                    diagnostic.code
+                        == Some(lsp_types::NumberOrString::String(
+                            DiagnosticCode::UndefinedGlobal.get_name().to_string(),
+                        ))
+                })
+        };
+        assert!(has_undefined_global(file_ids[0]));
+        assert!(!has_undefined_global(file_ids[1]));
+        assert!(Arc::ptr_eq(
+            &analysis.get_file_emmyrc(&file_ids[1]),
+            &scoped_emmyrc
+        ));
+        assert!(!Arc::ptr_eq(
+            &analysis.get_file_emmyrc(&file_ids[0]),
+            &scoped_emmyrc
+        ));
+
+        let _ = fs::remove_dir_all(&temp_root);
+    }
+
     #[test]
     fn sibling_package_workspace_folders_keep_distinct_workspace_ids() {
         let unique = SystemTime::now()
@@ -600,6 +600,7 @@ fn complete_mapped_type(
                 value.ty,
                 mapped.is_readonly,
                 mapped.is_optional,
+                mapped.is_required,
             )
             .into(),
         ),
@@ -11,7 +11,9 @@ use hashbrown::HashMap;
 use std::{ops::Deref, vec};
 
 use super::{
-    GenericInstantiateContext, GenericResolveMode, TypeSubstitutor, instantiate_type_generic_inner,
+    GenericInstantiateContext, GenericResolveMode, TypeSubstitutor,
+    instantiate_template_literal::{instantiate_string_case_call, instantiate_template_call},
+    instantiate_type_generic_inner,
 };
 
 pub(super) fn instantiate_alias_call(
@@ -117,6 +119,16 @@ pub(super) fn instantiate_alias_call(
             instantiate_index_call(context.db, &operands[0], &key)
         }
         LuaAliasCallKind::Merge => instantiate_merge_call(context.db, &operands),
+        LuaAliasCallKind::Template => {
+            let operands = resolve_literal_operands(context, operand_exprs, operands);
+            instantiate_template_call(context.db, operands)
+        }
+        kind @ (LuaAliasCallKind::Uppercase
+        | LuaAliasCallKind::Lowercase
+        | LuaAliasCallKind::Capitalize) => {
+            let operands = resolve_literal_operands(context, operand_exprs, operands);
+            instantiate_string_case_call(context.db, kind, operands)
+        }
     }
 }
 
@@ -165,6 +177,21 @@ fn instantiate_merge_call(db: &DbIndex, operands: &[LuaType]) -> LuaType {
     LuaType::Object(LuaObjectType::new_with_fields(fields, Vec::new()).into())
 }
 
+// 字符串操作需要保留泛型实参的字面量, 而不是拓宽后的 string
+fn resolve_literal_operands(
+    context: &GenericInstantiateContext,
+    operand_exprs: &[LuaType],
+    operands: Vec<LuaType>,
+) -> Vec<LuaType> {
+    operand_exprs
+        .iter()
+        .zip(operands)
+        .map(|(expr, operand)| {
+            resolve_literal_operand(Some(expr), context.substitutor).unwrap_or(operand)
+        })
+        .collect()
+}
+
 fn resolve_literal_operand(
     operand: Option<&LuaType>,
     substitutor: &TypeSubstitutor,
@@ -0,0 +1,200 @@
+use smol_str::SmolStr;
+
+use crate::{DbIndex, LuaAliasCallKind, LuaAliasCallType, LuaType, LuaTypeNode};
+
+// 模板字面量展开后的字面量数量上限, 超出时退化为 string
+const TEMPLATE_LITERAL_EXPAND_LIMIT: usize = 1024;
+
+pub(super) fn instantiate_template_call(db: &DbIndex, operands: Vec<LuaType>) -> LuaType {
+    if operands.iter().any(LuaType::contains_tpl_node) {
+        return LuaType::Call(LuaAliasCallType::new(LuaAliasCallKind::Template, operands).into());
+    }
+
+    let mut results = vec![String::new()];
+    for operand in &operands {
+        // 存在非字面量的部分时保留为模式, 由类型检查按模式匹配
+        let Some(parts) = get_literal_strings(db, operand) else {
+            return LuaType::Call(
+                LuaAliasCallType::new(LuaAliasCallKind::Template, operands).into(),
+            );
+        };
+        if results.len() * parts.len() > TEMPLATE_LITERAL_EXPAND_LIMIT {
+            return LuaType::String;
+        }
+
+        results = results
+            .iter()
+            .flat_map(|prefix| parts.iter().map(move |part| format!("{prefix}{part}")))
+            .collect();
+    }
+
+    to_string_literal_union(results)
+}
+
+pub(super) fn instantiate_string_case_call(
+    db: &DbIndex,
+    kind: LuaAliasCallKind,
+    operands: Vec<LuaType>,
+) -> LuaType {
+    if operands.len() != 1 {
+        return LuaType::Unknown;
+    }
+
+    if operands[0].contains_tpl_node() {
+        return LuaType::Call(LuaAliasCallType::new(kind, operands).into());
+    }
+
+    let Some(parts) = get_literal_strings(db, &operands[0]) else {
+        return LuaType::String;
+    };
+
+    let results = parts
+        .iter()
+        .map(|part| match kind {
+            LuaAliasCallKind::Uppercase => part.to_uppercase(),
+            LuaAliasCallKind::Lowercase => part.to_lowercase(),
+            _ => capitalize(part),
+        })
+        .collect();
+
+    to_string_literal_union(results)
+}
+
+/// 判断字符串字面量是否匹配未能展开的模板字面量, 如 `on${string}`
+pub fn is_template_literal_match(db: &DbIndex, template: &LuaAliasCallType, text: &str) -> bool {
+    if template.get_call_kind() != LuaAliasCallKind::Template {
+        return false;
+    }
+
+    let segments = template
+        .get_operands()
+        .iter()
+        .map(|operand| match get_literal_strings(db, operand) {
+            Some(parts) => TemplateSegment::Literal(parts),
+            None => TemplateSegment::from_type(db, operand),
+        })
+        .collect::<Vec<_>>();
+
+    match_segments(&segments, text)
+}
+
+/// 获取类型可能取到的全部字面量文本, 只要存在非字面量部分就返回 None
+fn get_literal_strings(db: &DbIndex, typ: &LuaType) -> Option<Vec<String>> {
+    match typ {
+        LuaType::StringConst(s) | LuaType::DocStringConst(s) => Some(vec![s.to_string()]),
+        LuaType::IntegerConst(i) | LuaType::DocIntegerConst(i) => Some(vec![i.to_string()]),
+        LuaType::BooleanConst(b) | LuaType::DocBooleanConst(b) => Some(vec![b.to_string()]),
+        LuaType::Never => Some(Vec::new()),
+        LuaType::Union(union) => {
+            let mut results = Vec::new();
+            for typ in union.into_vec() {
+                results.extend(get_literal_strings(db, &typ)?);
+            }
+            Some(results)
+        }
+        LuaType::MultiLineUnion(multi_union) => get_literal_strings(db, &multi_union.to_union()),
+        LuaType::Ref(type_id) => {
+            let type_decl = db.get_type_index().get_type_decl(type_id)?;
+            if !type_decl.is_alias() {
+                return None;
+            }
+            let origin = type_decl.get_alias_origin(db, None)?;
+            get_literal_strings(db, &origin)
+        }
+        LuaType::Call(alias_call)
+            if matches!(
+                alias_call.get_call_kind(),
+                LuaAliasCallKind::Template
+                    | LuaAliasCallKind::Uppercase
+                    | LuaAliasCallKind::Lowercase
+                    | LuaAliasCallKind::Capitalize
+            ) && !typ.contain_tpl() =>
+        {
+            let operands = alias_call.get_operands().clone();
+            let resolved = match alias_call.get_call_kind() {
+                LuaAliasCallKind::Template => instantiate_template_call(db, operands),
+                kind => instantiate_string_case_call(db, kind, operands),
+            };
+            if matches!(resolved, LuaType::Call(_)) {
+                return None;
+            }
+            get_literal_strings(db, &resolved)
+        }
+        _ => None,
+    }
+}
+
+fn to_string_literal_union(results: Vec<String>) -> LuaType {
+    if results.is_empty() {
+        return LuaType::Never;
+    }
+
+    LuaType::from_vec(
+        results
+            .into_iter()
+            .map(|it| LuaType::DocStringConst(SmolStr::new(it).into()))
+            .collect(),
+    )
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+enum TemplateSegment {
+    Literal(Vec<String>),
+    Integer,
+    Number,
+    Any,
+}
+
+impl TemplateSegment {
+    fn from_type(db: &DbIndex, typ: &LuaType) -> Self {
+        match typ {
+            LuaType::Integer => TemplateSegment::Integer,
+            LuaType::Number => TemplateSegment::Number,
+            LuaType::Ref(type_id) => {
+                let origin = db
+                    .get_type_index()
+                    .get_type_decl(type_id)
+                    .filter(|decl| decl.is_alias())
+                    .and_then(|decl| decl.get_alias_origin(db, None));
+                match origin {
+                    Some(origin) => TemplateSegment::from_type(db, &origin),
+                    None => TemplateSegment::Any,
+                }
+            }
+            _ => TemplateSegment::Any,
+        }
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        match self {
+            TemplateSegment::Literal(parts) => parts.iter().any(|part| part == text),
+            TemplateSegment::Integer => text.parse::<i64>().is_ok(),
+            TemplateSegment::Number => text.parse::<f64>().is_ok(),
+            TemplateSegment::Any => true,
+        }
+    }
+}
+
+fn match_segments(segments: &[TemplateSegment], text: &str) -> bool {
+    let Some((first, rest)) = segments.split_first() else {
+        return text.is_empty();
+    };
+
+    if let TemplateSegment::Literal(parts) = first {
+        return parts.iter().any(|part| {
+            text.strip_prefix(part.as_str())
+                .is_some_and(|tail| match_segments(rest, tail))
+        });
+    }
+
+    (0..=text.len())
+        .filter(|i| text.is_char_boundary(*i))
+        .any(|i| first.is_match(&text[..i]) && match_segments(rest, &text[i..]))
+}
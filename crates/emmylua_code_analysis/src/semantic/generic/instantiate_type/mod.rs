@@ -1,6 +1,7 @@
 mod complete_generic_args;
 mod instantiate_conditional_generic;
 mod instantiate_special_generic;
+mod instantiate_template_literal;
 
 use hashbrown::{HashMap, HashSet};
 use std::ops::Deref;
@@ -25,6 +26,7 @@ pub use complete_generic_args::{
     GenericArgumentCompletion, complete_type_generic_args, complete_type_generic_args_in_type,
 };
 pub use instantiate_special_generic::get_keyof_members;
+pub use instantiate_template_literal::is_template_literal_match;
 
 pub fn instantiate_type_generic(
     db: &DbIndex,
@@ -773,8 +775,10 @@ fn instantiate_mapped_value(
     // 根据 readonly 和 optional 属性进行处理
     if mapped.is_optional {
         result = TypeOps::Union.apply(context.db, &result, &LuaType::Nil);
+    } else if mapped.is_required {
+        result = TypeOps::Remove.apply(context.db, &result, &LuaType::Nil);
     }
-    // TODO: 处理 readonly, 但目前 readonly 的实现存在问题, 这里我们先跳过
+    // readonly 不体现在实例化结果上, 由 ReadOnlyChecker 根据映射类型本身检查
 
     result
 }
@@ -9,7 +9,7 @@ use smol_str::SmolStr;
 use crate::{
     LuaInferCache, TypeOps, check_type_compact,
     db_index::{DbIndex, LuaOperatorMetaMethod, LuaType},
-    get_real_type,
+    get_opaque_base_type, get_real_type,
 };
 
 use super::{InferFailReason, InferResult, get_custom_type_operator, infer_expr};
@@ -54,9 +54,39 @@ pub fn infer_binary_expr(
         (false, true) => (left_type, right_type_ref.clone()),
         (false, false) => (left_type, right_type),
     };
+    let left_ty = get_opaque_operand_type(db, left_ty, op);
+    let right_ty = get_opaque_operand_type(db, right_ty, op);
     infer_binary_expr_type(db, left_ty, right_ty, op)
 }
 
+/// opaque 类型没有声明对应的运算符时, 按其基础类型参与运算
+fn get_opaque_operand_type(db: &DbIndex, typ: LuaType, op: BinaryOperator) -> LuaType {
+    let meta_method = match op {
+        BinaryOperator::OpAdd => LuaOperatorMetaMethod::Add,
+        BinaryOperator::OpSub => LuaOperatorMetaMethod::Sub,
+        BinaryOperator::OpMul => LuaOperatorMetaMethod::Mul,
+        BinaryOperator::OpDiv => LuaOperatorMetaMethod::Div,
+        BinaryOperator::OpIDiv => LuaOperatorMetaMethod::IDiv,
+        BinaryOperator::OpMod => LuaOperatorMetaMethod::Mod,
+        BinaryOperator::OpPow => LuaOperatorMetaMethod::Pow,
+        BinaryOperator::OpBAnd => LuaOperatorMetaMethod::BAnd,
+        BinaryOperator::OpBOr => LuaOperatorMetaMethod::BOr,
+        BinaryOperator::OpBXor => LuaOperatorMetaMethod::BXor,
+        BinaryOperator::OpShl => LuaOperatorMetaMethod::Shl,
+        BinaryOperator::OpShr => LuaOperatorMetaMethod::Shr,
+        BinaryOperator::OpConcat => LuaOperatorMetaMethod::Concat,
+        _ => return typ,
+    };
+
+    let Some(base_type) = get_opaque_base_type(db, &typ) else {
+        return typ;
+    };
+    if get_custom_type_operator(db, typ.clone(), meta_method).is_some() {
+        return typ;
+    }
+    base_type.clone()
+}
+
 fn infer_union_binary_expr(
     db: &DbIndex,
     op: BinaryOperator,
@@ -424,29 +454,26 @@ fn infer_binary_expr_shr(db: &DbIndex, left: LuaType, right: LuaType) -> InferRe
 
 fn infer_binary_expr_concat(db: &DbIndex, left: LuaType, right: LuaType) -> InferResult {
     if left.is_number() || left.is_string() || right.is_number() || right.is_string() {
-        match (&left, &right) {
-            (LuaType::StringConst(s1), LuaType::StringConst(s2)) => {
-                return Ok(LuaType::StringConst(
-                    SmolStr::new(format!("{}{}", s1.as_str(), s2.as_str())).into(),
-                ));
-            }
-            (LuaType::StringConst(s1), LuaType::IntegerConst(i)) => {
-                return Ok(LuaType::StringConst(
-                    SmolStr::new(format!("{}{}", s1.as_str(), i)).into(),
-                ));
-            }
-            (LuaType::IntegerConst(i), LuaType::StringConst(s2)) => {
-                return Ok(LuaType::StringConst(
-                    SmolStr::new(format!("{}{}", i, s2.as_str())).into(),
-                ));
-            }
-            _ => return Ok(LuaType::String),
+        if let (Some(s1), Some(s2)) = (concat_const_string(&left), concat_const_string(&right)) {
+            return Ok(LuaType::StringConst(
+                SmolStr::new(format!("{}{}", s1, s2)).into(),
+            ));
         }
+        return Ok(LuaType::String);
     }
 
     infer_binary_custom_operator(db, &left, &right, LuaOperatorMetaMethod::Concat)
 }
 
+/// Returns the string a constant operand of `..` is converted to.
+fn concat_const_string(typ: &LuaType) -> Option<String> {
+    match typ {
+        LuaType::StringConst(s) | LuaType::DocStringConst(s) => Some(s.to_string()),
+        LuaType::IntegerConst(i) | LuaType::DocIntegerConst(i) => Some(i.to_string()),
+        _ => None,
+    }
+}
+
 fn infer_cmp_expr(_: &DbIndex, left: LuaType, right: LuaType, op: BinaryOperator) -> InferResult {
     match (left, right) {
         (LuaType::IntegerConst(i), LuaType::IntegerConst(j)) => {
@@ -2,8 +2,9 @@ use std::sync::Arc;
 
 use emmylua_parser::{
     LuaAstNode, LuaDocBinaryType, LuaDocDescriptionOwner, LuaDocFuncType, LuaDocGenericType,
-    LuaDocMultiLineUnionType, LuaDocObjectFieldKey, LuaDocObjectType, LuaDocStrTplType, LuaDocType,
-    LuaDocUnaryType, LuaDocVariadicType, LuaLiteralToken, LuaSyntaxKind, LuaTypeBinaryOperator,
+    LuaDocMultiLineUnionType, LuaDocObjectFieldKey, LuaDocObjectType, LuaDocStrTplType,
+    LuaDocTemplateLiteralPart, LuaDocTemplateLiteralType, LuaDocType, LuaDocUnaryType,
+    LuaDocVariadicType, LuaLiteralToken, LuaSyntaxKind, LuaTypeBinaryOperator,
     LuaTypeUnaryOperator, NumberResult,
 };
 use rowan::TextRange;
@@ -109,6 +110,9 @@ pub fn infer_doc_type(ctx: DocTypeInferContext<'_>, node: &LuaDocType) -> LuaTyp
         LuaDocType::StrTpl(str_tpl) => {
             return infer_str_tpl(ctx, str_tpl, node);
         }
+        LuaDocType::TemplateLiteral(template_literal) => {
+            return infer_template_literal_type(ctx, template_literal);
+        }
         LuaDocType::Variadic(variadic_type) => {
             return infer_variadic_type(ctx, variadic_type).unwrap_or(LuaType::Unknown);
         }
@@ -311,6 +315,23 @@ fn infer_special_generic_type(
                 LuaAliasCallType::new(LuaAliasCallKind::Merge, params).into(),
             ));
         }
+        "Uppercase" | "Lowercase" | "Capitalize" => {
+            let mut params = Vec::new();
+            for param in generic_type.get_generic_types()?.get_types() {
+                params.push(infer_doc_type(ctx, &param));
+            }
+            if params.len() != 1 {
+                return Some(LuaType::Unknown);
+            }
+            let call_kind = match name {
+                "Uppercase" => LuaAliasCallKind::Uppercase,
+                "Lowercase" => LuaAliasCallKind::Lowercase,
+                _ => LuaAliasCallKind::Capitalize,
+            };
+            return Some(LuaType::Call(
+                LuaAliasCallType::new(call_kind, params).into(),
+            ));
+        }
         _ => {}
     }
 
@@ -568,6 +589,25 @@ fn infer_str_tpl(
     LuaType::Unknown
 }
 
+fn infer_template_literal_type(
+    ctx: DocTypeInferContext<'_>,
+    template_literal: &LuaDocTemplateLiteralType,
+) -> LuaType {
+    let mut operands = Vec::new();
+    for part in template_literal.get_parts() {
+        match part {
+            LuaDocTemplateLiteralPart::Text(text) => {
+                operands.push(LuaType::DocStringConst(SmolStr::new(text).into()));
+            }
+            LuaDocTemplateLiteralPart::Type(doc_type) => {
+                operands.push(infer_doc_type(ctx, &doc_type));
+            }
+        }
+    }
+
+    LuaType::Call(LuaAliasCallType::new(LuaAliasCallKind::Template, operands).into())
+}
+
 fn infer_variadic_type(
     ctx: DocTypeInferContext<'_>,
     variadic_type: &LuaDocVariadicType,
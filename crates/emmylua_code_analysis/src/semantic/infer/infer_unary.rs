@@ -3,6 +3,7 @@ use emmylua_parser::{LuaUnaryExpr, UnaryOperator};
 use crate::{
     LuaInferCache,
     db_index::{DbIndex, LuaOperatorMetaMethod, LuaType},
+    get_opaque_base_type,
 };
 
 use super::{InferFailReason, InferResult, get_custom_type_operator, infer_expr};
@@ -18,15 +19,43 @@ pub fn infer_unary_expr(
         .get_op();
     let inner_expr = unary_expr.get_expr().ok_or(InferFailReason::None)?;
     let inner_type = infer_expr(db, cache, inner_expr)?;
+    let inner_type = get_opaque_operand_type(db, inner_type, op);
     match op {
         UnaryOperator::OpNot => infer_unary_expr_not(inner_type),
-        UnaryOperator::OpLen => Ok(LuaType::Integer),
+        UnaryOperator::OpLen => infer_unary_expr_len(inner_type),
         UnaryOperator::OpUnm => infer_unary_expr_unm(db, inner_type),
         UnaryOperator::OpBNot => infer_unary_expr_bnot(db, inner_type),
         UnaryOperator::OpNop => Ok(inner_type),
     }
 }
 
+/// opaque 类型没有声明对应的运算符时, 按其基础类型参与运算
+fn get_opaque_operand_type(db: &DbIndex, typ: LuaType, op: UnaryOperator) -> LuaType {
+    let meta_method = match op {
+        UnaryOperator::OpUnm => LuaOperatorMetaMethod::Unm,
+        UnaryOperator::OpBNot => LuaOperatorMetaMethod::BNot,
+        UnaryOperator::OpLen => LuaOperatorMetaMethod::Len,
+        _ => return typ,
+    };
+
+    let Some(base_type) = get_opaque_base_type(db, &typ) else {
+        return typ;
+    };
+    if get_custom_type_operator(db, typ.clone(), meta_method).is_some() {
+        return typ;
+    }
+    base_type.clone()
+}
+
+fn infer_unary_expr_len(inner_type: LuaType) -> InferResult {
+    match inner_type {
+        LuaType::StringConst(s) | LuaType::DocStringConst(s) => {
+            Ok(LuaType::IntegerConst(s.len() as i64))
+        }
+        _ => Ok(LuaType::Integer),
+    }
+}
+
 fn infer_unary_custom_operator(
     db: &DbIndex,
     inner: &LuaType,
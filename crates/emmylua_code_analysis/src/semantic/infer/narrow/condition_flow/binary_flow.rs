@@ -370,11 +370,58 @@ pub(super) fn narrow_eq_condition(
             }
         }
         InferConditionFlow::FalseCondition => {
+            let antecedent_type = expand_enum_for_literal(db, antecedent_type, &right_expr_type);
+            // 已排除最后一个声明的字面量, 剩余分支不可达
+            if is_doc_literal(&antecedent_type)
+                && always_literal_equal(&antecedent_type, &right_expr_type)
+            {
+                return LuaType::Never;
+            }
+
             TypeOps::Remove.apply(db, &antecedent_type, &right_expr_type)
         }
     }
 }
 
+/// 与枚举成员比较时将枚举展开为成员值的联合, 以便逐个排除成员,
+/// 查询结果会在 `keep_enum_identity` 中还原为枚举类型
+fn expand_enum_for_literal(
+    db: &DbIndex,
+    antecedent_type: LuaType,
+    right_type: &LuaType,
+) -> LuaType {
+    let LuaType::Ref(type_decl_id) = &antecedent_type else {
+        return antecedent_type;
+    };
+    let Some(field_type) = db
+        .get_type_index()
+        .get_type_decl(type_decl_id)
+        .and_then(|type_decl| type_decl.get_enum_field_type(db))
+    else {
+        return antecedent_type;
+    };
+
+    let members = match &field_type {
+        LuaType::Union(union) => union.into_vec(),
+        _ => vec![field_type.clone()],
+    };
+    let matches_member = members
+        .iter()
+        .any(|member| is_doc_literal(member) && always_literal_equal(member, right_type));
+    if matches_member {
+        field_type
+    } else {
+        antecedent_type
+    }
+}
+
+fn is_doc_literal(typ: &LuaType) -> bool {
+    matches!(
+        typ,
+        LuaType::DocStringConst(_) | LuaType::DocIntegerConst(_) | LuaType::DocBooleanConst(_)
+    )
+}
+
 #[allow(clippy::too_many_arguments)]
 fn get_var_eq_condition_action(
     db: &DbIndex,
@@ -6,7 +6,7 @@ mod var_ref_id;
 
 use crate::{
     CacheEntry, DbIndex, FlowAntecedent, FlowId, FlowNode, FlowTree, InferFailReason,
-    LuaInferCache, infer_param,
+    LuaInferCache, LuaType, infer_param,
     semantic::infer::{
         InferResult,
         infer_name::{find_decl_member_type, infer_global_type},
@@ -35,7 +35,50 @@ pub fn infer_expr_narrow_type(
     };
 
     let root = LuaChunk::cast(expr.get_root()).ok_or(InferFailReason::None)?;
-    get_type_at_flow::get_type_at_flow(db, flow_tree, cache, &root, &var_ref_id, flow_id)
+    let narrowed =
+        get_type_at_flow::get_type_at_flow(db, flow_tree, cache, &root, &var_ref_id, flow_id)?;
+    Ok(keep_enum_identity(db, cache, &var_ref_id, narrowed))
+}
+
+/// 流分析内部把枚举展开为成员值以便逐个排除, 对外仍然返回变量声明的枚举类型
+fn keep_enum_identity(
+    db: &DbIndex,
+    cache: &mut LuaInferCache,
+    var_ref_id: &VarRefId,
+    narrowed: LuaType,
+) -> LuaType {
+    let narrowed_members = match &narrowed {
+        LuaType::Union(union) => union.into_vec(),
+        LuaType::DocStringConst(_) | LuaType::DocIntegerConst(_) | LuaType::DocBooleanConst(_) => {
+            vec![narrowed.clone()]
+        }
+        _ => return narrowed,
+    };
+    let Ok(declared) = get_var_ref_type(db, cache, var_ref_id) else {
+        return narrowed;
+    };
+    let LuaType::Ref(type_decl_id) = &declared else {
+        return narrowed;
+    };
+    let Some(enum_field_type) = db
+        .get_type_index()
+        .get_type_decl(type_decl_id)
+        .and_then(|type_decl| type_decl.get_enum_field_type(db))
+    else {
+        return narrowed;
+    };
+    let enum_members = match enum_field_type {
+        LuaType::Union(union) => union.into_vec(),
+        typ => vec![typ],
+    };
+    if narrowed_members
+        .iter()
+        .all(|member| enum_members.contains(member))
+    {
+        declared
+    } else {
+        narrowed
+    }
 }
 
 pub(in crate::semantic) fn get_var_ref_type(
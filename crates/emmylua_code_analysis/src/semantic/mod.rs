@@ -4,6 +4,7 @@ mod generic;
 mod guard;
 mod infer;
 mod member;
+mod moniker;
 mod overload_resolve;
 mod reference;
 mod semantic_info;
@@ -32,6 +33,7 @@ use member::{
     find_member_origin_owner, find_members_in_scope, find_members_with_key_in_scope,
     get_member_map_in_scope,
 };
+pub use moniker::{MONIKER_SCHEME, get_moniker_identifier};
 use reference::is_reference_to;
 use rowan::{NodeOrToken, TextRange};
 pub use semantic_info::SemanticInfo;
@@ -62,6 +64,7 @@ pub(crate) use infer::{infer_expr, try_infer_expr_no_flow};
 use overload_resolve::resolve_signature;
 pub(crate) use overload_resolve::{
     callable_accepts_args, get_func_param_type, is_func_last_param_variadic,
+    resolve_signature_by_args,
 };
 pub use overload_resolve::{
     collect_callable_overload_groups, filter_callable_overloads, find_callable_overload,
@@ -212,6 +215,25 @@ impl<'a> SemanticModel<'a> {
         callable_accepts_args(self.db, func, expr_types, is_colon_call, arg_count)
     }
 
+    /// 根据已知的参数类型从重载中选出最匹配的函数
+    pub fn resolve_signature_by_args(
+        &self,
+        overloads: &[Arc<LuaFunctionType>],
+        expr_types: &[LuaType],
+        is_colon_call: bool,
+        arg_count: Option<usize>,
+    ) -> Option<Arc<LuaFunctionType>> {
+        resolve_signature_by_args(
+            self.db,
+            overloads,
+            expr_types,
+            is_colon_call,
+            arg_count,
+            &[],
+        )
+        .ok()
+    }
+
     /// 推断表达式列表类型, 位于最后的表达式会触发多值推断
     pub fn infer_expr_list_types(
         &self,
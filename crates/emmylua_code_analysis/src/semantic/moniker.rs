@@ -0,0 +1,67 @@
+use crate::{DbIndex, FileId, LuaMemberKey, LuaMemberOwner, LuaSemanticDeclId, LuaType};
+
+/// The moniker scheme of lua symbols, the full moniker is `lua:<identifier>`.
+pub const MONIKER_SCHEME: &str = "lua";
+
+/// Returns a stable identifier of a symbol that can be referenced from other files or
+/// projects, such as `<module>:<Class>.<member>`, `<module>:<member>` for a member of the
+/// table a module returns, or `<module>:<global>`. Local symbols have no identifier.
+pub fn get_moniker_identifier(db: &DbIndex, semantic_decl: &LuaSemanticDeclId) -> Option<String> {
+    match semantic_decl {
+        LuaSemanticDeclId::TypeDecl(type_decl_id) => {
+            let type_decl = db.get_type_index().get_type_decl(type_decl_id)?;
+            let file_id = type_decl.get_locations().first()?.file_id;
+            Some(format!(
+                "{}:{}",
+                get_module_name(db, file_id)?,
+                type_decl.get_full_name()
+            ))
+        }
+        LuaSemanticDeclId::Member(member_id) => {
+            let member = db.get_member_index().get_member(member_id)?;
+            let member_name = match member.get_key() {
+                LuaMemberKey::Name(name) => name.to_string(),
+                LuaMemberKey::Integer(i) => format!("[{}]", i),
+                _ => return None,
+            };
+            match db.get_member_index().get_current_owner(member_id)? {
+                LuaMemberOwner::Type(type_decl_id) => {
+                    let owner = get_moniker_identifier(db, &type_decl_id.clone().into())?;
+                    Some(format!("{}.{}", owner, member_name))
+                }
+                LuaMemberOwner::Element(range) => {
+                    let module = db.get_module_index().get_module(range.file_id)?;
+                    let is_export = match &module.export_type {
+                        Some(LuaType::TableConst(table)) => table == range,
+                        Some(LuaType::Instance(instance)) => instance.get_range() == range,
+                        _ => false,
+                    };
+                    is_export.then(|| format!("{}:{}", module.full_module_name, member_name))
+                }
+                LuaMemberOwner::GlobalPath(global_id) => Some(format!(
+                    "{}:{}.{}",
+                    get_module_name(db, member_id.file_id)?,
+                    global_id.get_name(),
+                    member_name
+                )),
+                LuaMemberOwner::LocalUnresolve => None,
+            }
+        }
+        LuaSemanticDeclId::LuaDecl(decl_id) => {
+            let module = db.get_module_index().get_module(decl_id.file_id)?;
+            if module.semantic_id.as_ref() == Some(semantic_decl) {
+                return Some(module.full_module_name.clone());
+            }
+            let decl = db.get_decl_index().get_decl(decl_id)?;
+            decl.is_global()
+                .then(|| format!("{}:{}", module.full_module_name, decl.get_name()))
+        }
+        LuaSemanticDeclId::Signature(_) => None,
+    }
+}
+
+fn get_module_name(db: &DbIndex, file_id: FileId) -> Option<String> {
+    db.get_module_index()
+        .get_module(file_id)
+        .map(|module| module.full_module_name.clone())
+}
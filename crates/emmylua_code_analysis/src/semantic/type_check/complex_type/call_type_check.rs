@@ -2,7 +2,8 @@ use std::sync::Arc;
 
 use crate::{
     LuaAliasCallKind, LuaAliasCallType, LuaMemberKey, LuaType, LuaUnionType, TypeCheckFailReason,
-    TypeCheckResult, get_keyof_members,
+    TypeCheckResult, TypeSubstitutor, get_keyof_members, instantiate_type_generic,
+    is_template_literal_match,
     semantic::type_check::{
         check_general_type_compact, type_check_context::TypeCheckContext,
         type_check_guard::TypeCheckGuard,
@@ -64,6 +65,47 @@ pub fn check_call_type_compact(
         }
     }
 
+    if matches!(
+        source_call.get_call_kind(),
+        LuaAliasCallKind::Template
+            | LuaAliasCallKind::Uppercase
+            | LuaAliasCallKind::Lowercase
+            | LuaAliasCallKind::Capitalize
+    ) && !source_call.contain_tpl()
+    {
+        // 能展开为字面量联合的直接按联合检查
+        let source = LuaType::Call(source_call.clone().into());
+        let resolved = instantiate_type_generic(context.db, &source, &TypeSubstitutor::new());
+        if resolved != source {
+            return check_general_type_compact(
+                context,
+                &resolved,
+                compact_type,
+                check_guard.next_level()?,
+            );
+        }
+    }
+
+    if let LuaAliasCallKind::Template = source_call.get_call_kind() {
+        return match compact_type {
+            LuaType::StringConst(s) | LuaType::DocStringConst(s) => {
+                if is_template_literal_match(context.db, source_call, s) {
+                    Ok(())
+                } else {
+                    Err(TypeCheckFailReason::TypeNotMatch)
+                }
+            }
+            LuaType::Call(compact_call) if compact_call.as_ref() == source_call => Ok(()),
+            LuaType::Union(union) => {
+                for typ in union.into_vec() {
+                    check_call_type_compact(context, source_call, &typ, check_guard.next_level()?)?;
+                }
+                Ok(())
+            }
+            _ => Err(TypeCheckFailReason::TypeNotMatch),
+        };
+    }
+
     // TODO: 实现其他 call 类型的检查
     Ok(())
 }
@@ -1,11 +1,13 @@
 use crate::{
+    RenderLevel,
     db_index::{LuaFunctionType, LuaOperatorMetaMethod, LuaSignatureId, LuaType, LuaTypeDeclId},
+    humanize_type,
     semantic::type_check::type_check_context::TypeCheckContext,
 };
 
 use super::{
-    TypeCheckResult, check_general_type_compact, type_check_fail_reason::TypeCheckFailReason,
-    type_check_guard::TypeCheckGuard,
+    TypeCheckResult, check_general_type_compact, generic_type::check_strict_type_compact,
+    type_check_fail_reason::TypeCheckFailReason, type_check_guard::TypeCheckGuard,
 };
 
 pub fn check_doc_func_type_compact(
@@ -90,19 +92,43 @@ fn check_doc_func_type_compact_for_params(
 
         if let (Some(source_type), Some(compact_type)) = (source_param_type, compact_param_type) {
             // 函数赋值时, 被赋值函数必须接受目标类型可能传入的所有参数.
-            match check_general_type_compact(
-                context,
-                compact_type,
-                source_type,
-                check_guard.next_level()?,
-            ) {
+            // 开启 strict.functionVariance 时参数位置按逆变严格检查
+            let result = if is_function_variance(context) {
+                check_strict_type_compact(
+                    context,
+                    compact_type,
+                    source_type,
+                    check_guard.next_level()?,
+                )
+            } else {
+                check_general_type_compact(
+                    context,
+                    compact_type,
+                    source_type,
+                    check_guard.next_level()?,
+                )
+            };
+            match result {
                 Ok(()) => {}
                 Err(e) if e.is_type_not_match() => {
                     if i == 0 && source_type.is_self_infer() && compact_param.0 == "self" {
                         continue;
                     }
-                    // add error message
-                    return Err(e);
+                    if !is_function_variance(context) {
+                        return Err(e);
+                    }
+                    if !context.detail {
+                        return Err(TypeCheckFailReason::TypeNotMatch);
+                    }
+                    return Err(TypeCheckFailReason::TypeNotMatchWithReason(
+                        t!(
+                            "parameter `%{name}` is contravariant, `%{expect}` is not assignable to `%{got}`",
+                            name = compact_param.0,
+                            expect = humanize_type(context.db, source_type, RenderLevel::Simple),
+                            got = humanize_type(context.db, compact_type, RenderLevel::Simple)
+                        )
+                        .to_string(),
+                    ));
                 }
                 Err(e) => {
                     return Err(e);
@@ -111,9 +137,49 @@ fn check_doc_func_type_compact_for_params(
         }
     }
 
-    // todo check return type
+    check_doc_func_type_compact_for_return(context, source_func, compact_func, check_guard)
+}
 
-    Ok(())
+fn is_function_variance(context: &TypeCheckContext) -> bool {
+    context.db.get_emmyrc().strict.function_variance
+}
+
+/// 返回值位置是协变的, 被赋值函数的返回值必须能赋值给目标类型的返回值,
+/// 只在开启 strict.functionVariance 时检查
+fn check_doc_func_type_compact_for_return(
+    context: &mut TypeCheckContext,
+    source_func: &LuaFunctionType,
+    compact_func: &LuaFunctionType,
+    check_guard: TypeCheckGuard,
+) -> TypeCheckResult {
+    if !is_function_variance(context) {
+        return Ok(());
+    }
+
+    let source_ret = source_func.get_ret();
+    let compact_ret = compact_func.get_ret();
+    let is_unchecked =
+        |typ: &LuaType| typ.is_unknown() || typ.is_any() || typ.is_nil() || typ.contain_tpl();
+    if is_unchecked(source_ret) || is_unchecked(compact_ret) {
+        return Ok(());
+    }
+
+    match check_strict_type_compact(context, source_ret, compact_ret, check_guard.next_level()?) {
+        Err(e) if e.is_type_not_match() => {
+            if !context.detail {
+                return Err(TypeCheckFailReason::TypeNotMatch);
+            }
+            Err(TypeCheckFailReason::TypeNotMatchWithReason(
+                t!(
+                    "return type is covariant, `%{got}` is not assignable to `%{expect}`",
+                    got = humanize_type(context.db, compact_ret, RenderLevel::Simple),
+                    expect = humanize_type(context.db, source_ret, RenderLevel::Simple)
+                )
+                .to_string(),
+            ))
+        }
+        result => result,
+    }
 }
 
 fn check_doc_func_type_compact_for_varargs(
@@ -1,13 +1,14 @@
 use std::{collections::HashMap, sync::Arc};
 
 use crate::{
-    LuaGenericType, LuaMemberOwner, LuaType, LuaTypeCache, LuaTypeDeclId, RenderLevel,
-    TypeSubstitutor, complete_type_generic_args_in_type, humanize_type, instantiate_type_generic,
+    GenericVariance, LuaGenericType, LuaMemberOwner, LuaType, LuaTypeCache, LuaTypeDeclId,
+    RenderLevel, TypeSubstitutor, complete_type_generic_args_in_type, humanize_type,
+    instantiate_type_generic,
     semantic::{member::find_members, type_check::type_check_context::TypeCheckContext},
 };
 
 use super::{
-    TypeCheckResult, check_general_type_compact, instantiate_generic_alias_origin,
+    TypeCheckResult, check_general_type_compact, instantiate_generic_alias_origin, is_sub_type_of,
     type_check_fail_reason::TypeCheckFailReason, type_check_guard::TypeCheckGuard,
 };
 
@@ -136,9 +137,94 @@ fn check_generic_type_compact_generic(
         return Err(TypeCheckFailReason::TypeNotMatch);
     }
 
+    let generic_params = context
+        .db
+        .get_type_index()
+        .get_generic_params(&source_base_id)
+        .cloned()
+        .unwrap_or_default();
     let next_guard = check_guard.next_level()?;
-    for (source_param, compact_param) in source_params.iter().zip(compact_params.iter()) {
-        check_general_type_compact(context, source_param, compact_param, next_guard)?;
+    for (i, (source_param, compact_param)) in
+        source_params.iter().zip(compact_params.iter()).enumerate()
+    {
+        let Some(generic_param) = generic_params.get(i) else {
+            check_general_type_compact(context, source_param, compact_param, next_guard)?;
+            continue;
+        };
+
+        match generic_param.variance {
+            GenericVariance::Default => {
+                check_general_type_compact(context, source_param, compact_param, next_guard)?;
+            }
+            GenericVariance::Covariant => {
+                if check_strict_type_compact(context, source_param, compact_param, next_guard)
+                    .is_err()
+                {
+                    if !context.detail {
+                        return Err(TypeCheckFailReason::TypeNotMatch);
+                    }
+                    return Err(TypeCheckFailReason::TypeNotMatchWithReason(
+                        t!(
+                            "`%{got}` is not assignable to `%{expect}` for covariant (`out`) generic parameter `%{name}`",
+                            got = humanize_type(context.db, compact_param, RenderLevel::Simple),
+                            expect = humanize_type(context.db, source_param, RenderLevel::Simple),
+                            name = generic_param.name
+                        )
+                        .to_string(),
+                    ));
+                }
+            }
+            GenericVariance::Contravariant => {
+                if check_strict_type_compact(context, compact_param, source_param, next_guard)
+                    .is_err()
+                {
+                    if !context.detail {
+                        return Err(TypeCheckFailReason::TypeNotMatch);
+                    }
+                    return Err(TypeCheckFailReason::TypeNotMatchWithReason(
+                        t!(
+                            "`%{expect}` is not assignable to `%{got}` for contravariant (`in`) generic parameter `%{name}`",
+                            got = humanize_type(context.db, compact_param, RenderLevel::Simple),
+                            expect = humanize_type(context.db, source_param, RenderLevel::Simple),
+                            name = generic_param.name
+                        )
+                        .to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 声明了变体的位置需要严格的子类型关系, 不再接受父类赋值给子类的宽松转换
+pub(super) fn check_strict_type_compact(
+    context: &mut TypeCheckContext,
+    source: &LuaType,
+    compact_type: &LuaType,
+    check_guard: TypeCheckGuard,
+) -> TypeCheckResult {
+    check_general_type_compact(context, source, compact_type, check_guard)?;
+
+    let (LuaType::Ref(source_id) | LuaType::Def(source_id)) = source else {
+        return Ok(());
+    };
+    let (LuaType::Ref(compact_id) | LuaType::Def(compact_id)) = compact_type else {
+        return Ok(());
+    };
+    if source_id == compact_id || is_sub_type_of(context.db, compact_id, source_id) {
+        return Ok(());
+    }
+
+    let type_index = context.db.get_type_index();
+    let is_class = |id: &LuaTypeDeclId| {
+        type_index
+            .get_type_decl(id)
+            .is_some_and(|decl| decl.is_class())
+    };
+    if is_class(source_id) && is_class(compact_id) {
+        return Err(TypeCheckFailReason::TypeNotMatch);
     }
 
     Ok(())
@@ -270,7 +270,12 @@ fn escape_type(db: &DbIndex, typ: &LuaType) -> Option<LuaType> {
         LuaType::Call(alias_call)
             if matches!(
                 alias_call.get_call_kind(),
-                LuaAliasCallKind::Index | LuaAliasCallKind::RawGet
+                LuaAliasCallKind::Index
+                    | LuaAliasCallKind::RawGet
+                    | LuaAliasCallKind::Template
+                    | LuaAliasCallKind::Uppercase
+                    | LuaAliasCallKind::Lowercase
+                    | LuaAliasCallKind::Capitalize
             ) && !typ.contain_tpl() =>
         {
             let resolved = instantiate_type_generic(db, typ, &TypeSubstitutor::new());
@@ -79,6 +79,8 @@ pub fn check_ref_type_compact(
 
     if type_decl.is_enum() {
         check_ref_enum(context, source_id, compact_type, check_guard, type_decl)
+    } else if type_decl.is_opaque() {
+        check_ref_opaque(context, source_id, compact_type, check_guard)
     } else {
         check_ref_class(context, source_id, compact_type, check_guard)
     }
@@ -159,6 +161,48 @@ fn check_ref_enum(
     )
 }
 
+/// opaque 类型只接受自身及其子类型, 基础类型需要通过 `---@cast` 或构造函数转换
+fn check_ref_opaque(
+    context: &mut TypeCheckContext,
+    source_id: &LuaTypeDeclId,
+    compact_type: &LuaType,
+    check_guard: TypeCheckGuard,
+) -> TypeCheckResult {
+    let compact_id = match compact_type {
+        LuaType::Def(id) | LuaType::Ref(id) => Some(id.clone()),
+        LuaType::Generic(generic) => Some(generic.get_base_type_id()),
+        LuaType::Union(union_type) => {
+            for typ in union_type.into_vec() {
+                check_general_type_compact(
+                    context,
+                    &LuaType::Ref(source_id.clone()),
+                    &typ,
+                    check_guard.next_level()?,
+                )?;
+            }
+            return Ok(());
+        }
+        _ => None,
+    };
+
+    if let Some(compact_id) = compact_id
+        && (&compact_id == source_id || is_sub_type_of(context.db, &compact_id, source_id))
+    {
+        return Ok(());
+    }
+
+    if context.detail {
+        return Err(TypeCheckFailReason::TypeNotMatchWithReason(
+            t!(
+                "opaque type `%{name}` only accepts values converted by `---@cast` or its constructor.",
+                name = source_id.get_name()
+            )
+            .to_string(),
+        ));
+    }
+    Err(TypeCheckFailReason::TypeNotMatch)
+}
+
 fn check_ref_class(
     context: &mut TypeCheckContext,
     source_id: &LuaTypeDeclId,
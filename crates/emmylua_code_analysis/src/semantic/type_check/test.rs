@@ -353,4 +353,181 @@ mod test {
         assert_eq!(ws.expr_ty("AFTER_CAST"), LuaType::Unknown);
         assert_eq!(ws.expr_ty("table.__sentinel()"), ws.ty("integer"));
     }
+
+    #[test]
+    fn test_opaque_type() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def(
+            r#"
+            ---@class (opaque) EntityId: integer
+
+            ---@class (opaque) ItemId: integer
+            "#,
+        );
+
+        let entity_ty = ws.ty("EntityId");
+        let item_ty = ws.ty("ItemId");
+        let integer_ty = ws.ty("integer");
+        let literal_ty = ws.expr_ty("1");
+
+        assert!(ws.check_type(&entity_ty, &entity_ty));
+        assert!(ws.check_type(&integer_ty, &entity_ty));
+        assert!(!ws.check_type(&entity_ty, &integer_ty));
+        assert!(!ws.check_type(&entity_ty, &literal_ty));
+        assert!(!ws.check_type(&entity_ty, &item_ty));
+
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            ---@param id EntityId
+            local function despawn(id) end
+
+            despawn(1)
+            "#
+        ));
+
+        assert!(ws.has_no_diagnostic(
+            DiagnosticCode::AssignTypeMismatch,
+            r#"
+            local raw = 1
+            ---@cast raw EntityId
+
+            ---@type EntityId
+            local id = raw
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_opaque_type_arithmetic() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def(
+            r#"
+            ---@class (opaque) EntityId: integer
+
+            ---@type EntityId
+            entity = nil
+            "#,
+        );
+
+        assert_eq!(ws.expr_ty("entity + 1"), ws.ty("integer"));
+        assert_eq!(ws.expr_ty("entity * 2"), ws.ty("integer"));
+        assert_eq!(ws.expr_ty("-entity"), ws.ty("integer"));
+        assert_eq!(ws.expr_ty("entity // 2"), ws.ty("integer"));
+    }
+
+    #[test]
+    fn test_generic_variance() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def(
+            r#"
+            ---@class Animal
+            ---@class Dog: Animal
+
+            ---@class Producer<out T>
+            ---@class Consumer<in T>
+            ---@class Box<T>
+            "#,
+        );
+
+        let producer_animal = ws.ty("Producer<Animal>");
+        let producer_dog = ws.ty("Producer<Dog>");
+        assert!(ws.check_type(&producer_animal, &producer_dog));
+        assert!(!ws.check_type(&producer_dog, &producer_animal));
+
+        let consumer_animal = ws.ty("Consumer<Animal>");
+        let consumer_dog = ws.ty("Consumer<Dog>");
+        assert!(ws.check_type(&consumer_dog, &consumer_animal));
+        assert!(!ws.check_type(&consumer_animal, &consumer_dog));
+
+        let box_animal = ws.ty("Box<Animal>");
+        let box_dog = ws.ty("Box<Dog>");
+        assert!(ws.check_type(&box_animal, &box_dog));
+        assert!(ws.check_type(&box_dog, &box_animal));
+
+        assert!(!ws.has_no_diagnostic(
+            DiagnosticCode::ParamTypeMismatch,
+            r#"
+            ---@param producer Producer<Dog>
+            local function feed(producer) end
+
+            ---@type Producer<Animal>
+            local animals
+            feed(animals)
+            "#
+        ));
+    }
+
+    #[test]
+    fn test_function_variance() {
+        let mut ws = VirtualWorkspace::new();
+        let mut emmyrc = ws.get_emmyrc();
+        emmyrc.strict.function_variance = true;
+        ws.update_emmyrc(emmyrc);
+        ws.def(
+            r#"
+            ---@class Animal
+            ---@class Dog: Animal
+            "#,
+        );
+
+        let takes_animal = ws.ty("fun(a: Animal)");
+        let takes_dog = ws.ty("fun(a: Dog)");
+        assert!(ws.check_type(&takes_dog, &takes_animal));
+        assert!(!ws.check_type(&takes_animal, &takes_dog));
+
+        let returns_animal = ws.ty("fun(): Animal");
+        let returns_dog = ws.ty("fun(): Dog");
+        assert!(ws.check_type(&returns_animal, &returns_dog));
+        assert!(!ws.check_type(&returns_dog, &returns_animal));
+    }
+
+    #[test]
+    fn test_function_variance_is_off_by_default() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def(
+            r#"
+            ---@class Animal
+            ---@class Dog: Animal
+            "#,
+        );
+
+        let takes_animal = ws.ty("fun(a: Animal)");
+        let takes_dog = ws.ty("fun(a: Dog)");
+        assert!(ws.check_type(&takes_animal, &takes_dog));
+        let returns_animal = ws.ty("fun(): Animal");
+        let returns_dog = ws.ty("fun(): Dog");
+        assert!(ws.check_type(&returns_dog, &returns_animal));
+
+        let callback = r#"
+            ---@param cb fun(a: Animal): Dog
+            local function each(cb) end
+
+            ---@param dog Dog
+            ---@return Animal
+            local function bark(dog) return dog end
+
+            each(bark)
+            "#;
+        assert!(ws.has_no_diagnostic(DiagnosticCode::ParamTypeMismatch, callback));
+
+        let overload = r#"
+            ---@type fun(a: Animal): Dog
+            local handler
+
+            ---@param a Dog
+            ---@return Animal
+            ---@overload fun(a: Dog, b: integer): Animal
+            local function handle(a) return a end
+
+            handler = handle
+            "#;
+        assert!(ws.has_no_diagnostic(DiagnosticCode::AssignTypeMismatch, overload));
+
+        let mut emmyrc = ws.get_emmyrc();
+        emmyrc.strict.function_variance = true;
+        ws.update_emmyrc(emmyrc);
+        assert!(!ws.has_no_diagnostic(DiagnosticCode::ParamTypeMismatch, callback));
+        assert!(!ws.has_no_diagnostic(DiagnosticCode::AssignTypeMismatch, overload));
+    }
 }
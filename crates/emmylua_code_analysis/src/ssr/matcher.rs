@@ -0,0 +1,152 @@
+use emmylua_parser::{
+    LuaAstNode, LuaAstToken, LuaExpr, LuaKind, LuaNameExpr, LuaStringToken, LuaSyntaxElement,
+    LuaSyntaxKind, LuaSyntaxNode, LuaSyntaxToken, LuaTokenKind,
+};
+use rowan::NodeOrToken;
+
+use super::PLACEHOLDER_PREFIX;
+
+#[derive(Debug, Default)]
+pub struct SsrBindings {
+    bindings: Vec<(String, LuaSyntaxElement)>,
+}
+
+impl SsrBindings {
+    pub fn get(&self, name: &str) -> Option<&LuaSyntaxElement> {
+        self.bindings
+            .iter()
+            .find(|(bound_name, _)| bound_name == name)
+            .map(|(_, element)| element)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &LuaSyntaxElement)> {
+        self.bindings
+            .iter()
+            .map(|(name, element)| (name.as_str(), element))
+    }
+
+    fn bind(&mut self, name: String, element: LuaSyntaxElement) -> bool {
+        if let Some(bound) = self.get(&name) {
+            return significant_tokens(bound).eq(significant_tokens(&element));
+        }
+
+        self.bindings.push((name, element));
+        true
+    }
+}
+
+pub fn is_placeholder_node(node: &LuaSyntaxNode) -> bool {
+    get_node_placeholder(node).is_some()
+}
+
+fn get_node_placeholder(node: &LuaSyntaxNode) -> Option<String> {
+    let name = LuaNameExpr::cast(node.clone())?.get_name_text()?;
+    name.strip_prefix(PLACEHOLDER_PREFIX).map(str::to_string)
+}
+
+fn get_token_placeholder(token: &LuaSyntaxToken) -> Option<String> {
+    if token.kind() != LuaTokenKind::TkName.into() {
+        return None;
+    }
+    token
+        .text()
+        .strip_prefix(PLACEHOLDER_PREFIX)
+        .map(str::to_string)
+}
+
+pub fn match_node(
+    pattern: &LuaSyntaxNode,
+    target: &LuaSyntaxNode,
+    bindings: &mut SsrBindings,
+) -> bool {
+    if let Some(name) = get_node_placeholder(pattern) {
+        if !LuaExpr::can_cast(target.kind().into()) {
+            return false;
+        }
+        return bindings.bind(name, target.clone().into());
+    }
+
+    if pattern.kind() != target.kind() {
+        return false;
+    }
+
+    let mut pattern_children = significant_children(pattern);
+    let mut target_children = significant_children(target);
+    loop {
+        let matched = match (pattern_children.next(), target_children.next()) {
+            (None, None) => return true,
+            (Some(NodeOrToken::Node(pattern)), Some(NodeOrToken::Node(target))) => {
+                match_node(&pattern, &target, bindings)
+            }
+            (Some(NodeOrToken::Token(pattern)), Some(NodeOrToken::Token(target))) => {
+                match_token(&pattern, &target, bindings)
+            }
+            _ => false,
+        };
+        if !matched {
+            return false;
+        }
+    }
+}
+
+fn match_token(
+    pattern: &LuaSyntaxToken,
+    target: &LuaSyntaxToken,
+    bindings: &mut SsrBindings,
+) -> bool {
+    if let Some(name) = get_token_placeholder(pattern) {
+        if target.kind() != LuaTokenKind::TkName.into() {
+            return false;
+        }
+        return bindings.bind(name, target.clone().into());
+    }
+
+    if pattern.kind() != target.kind() {
+        return false;
+    }
+
+    // `"a"`, `'a'` and `[[a]]` are the same string
+    if let (Some(pattern), Some(target)) = (
+        LuaStringToken::cast(pattern.clone()),
+        LuaStringToken::cast(target.clone()),
+    ) {
+        return pattern.get_value() == target.get_value();
+    }
+
+    pattern.text() == target.text()
+}
+
+fn is_trivia(element: &LuaSyntaxElement) -> bool {
+    match element {
+        NodeOrToken::Node(node) => node.kind() == LuaKind::Syntax(LuaSyntaxKind::Comment),
+        NodeOrToken::Token(token) => matches!(
+            token.kind().into(),
+            LuaTokenKind::TkWhitespace
+                | LuaTokenKind::TkEndOfLine
+                | LuaTokenKind::TkShortComment
+                | LuaTokenKind::TkLongComment
+        ),
+    }
+}
+
+fn significant_children(node: &LuaSyntaxNode) -> impl Iterator<Item = LuaSyntaxElement> {
+    node.children_with_tokens()
+        .filter(|element| !is_trivia(element))
+}
+
+fn significant_tokens(element: &LuaSyntaxElement) -> impl Iterator<Item = String> {
+    let tokens: Vec<LuaSyntaxToken> = match element {
+        NodeOrToken::Node(node) => node
+            .descendants_with_tokens()
+            .filter(|element| {
+                !is_trivia(element)
+                    && !element
+                        .ancestors()
+                        .any(|node| node.kind() == LuaKind::Syntax(LuaSyntaxKind::Comment))
+            })
+            .filter_map(|element| element.into_token())
+            .collect(),
+        NodeOrToken::Token(token) => vec![token.clone()],
+    };
+    tokens.into_iter().map(|token| token.text().to_string())
+}
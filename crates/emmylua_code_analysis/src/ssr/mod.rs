@@ -0,0 +1,406 @@
+mod matcher;
+mod test;
+
+use std::collections::HashMap;
+
+use emmylua_parser::{
+    LexerConfig, LuaAstNode, LuaDocTagType, LuaExpr, LuaLexer, LuaParser, LuaSyntaxKind,
+    LuaSyntaxNode, LuaSyntaxTree, LuaTokenKind, ParserConfig, Reader,
+};
+use rowan::{TextRange, TextSize};
+
+use crate::{DocTypeInferContext, LuaType, SemanticModel, infer_doc_type};
+use matcher::{SsrBindings, is_placeholder_node, match_node};
+
+const PLACEHOLDER_PREFIX: &str = "__ssr_";
+
+/// A structural search and replace rule.
+///
+/// The textual form is `search ==>> replacement where $a: Type, $b: Type`, where the
+/// replacement and the `where` clause are optional. `$name` placeholders in the search
+/// pattern match any expression (or any name in a name position, such as `obj.$field`),
+/// and a placeholder used more than once must match the same code every time.
+#[derive(Debug, Clone)]
+pub struct SsrRule {
+    pattern: SsrPattern,
+    template: Option<String>,
+    constraints: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+struct SsrPattern {
+    tree: LuaSyntaxTree,
+    kind: SsrPatternKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SsrPatternKind {
+    Expr,
+    Stat,
+}
+
+#[derive(Debug, Clone)]
+pub struct SsrMatch {
+    pub range: TextRange,
+    /// The source text bound to each placeholder, keyed by placeholder name without `$`.
+    pub placeholders: HashMap<String, String>,
+    /// The rendered replacement, `None` for search-only rules.
+    pub replacement: Option<String>,
+}
+
+impl SsrRule {
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let (rule, constraints) = match rule.find(" where $") {
+            Some(pos) => (
+                &rule[..pos],
+                parse_constraints(&rule[pos + " where ".len()..])?,
+            ),
+            None => (rule, Vec::new()),
+        };
+
+        let (search, template) = match rule.split_once("==>>") {
+            Some((search, template)) => (search.trim(), Some(template.trim().to_string())),
+            None => (rule.trim(), None),
+        };
+        if search.is_empty() {
+            return Err("empty search pattern".to_string());
+        }
+
+        let pattern = SsrPattern::parse(search)?;
+        let placeholders = collect_placeholders(search);
+        if let Some(template) = &template {
+            for name in collect_placeholders(template) {
+                if !placeholders.contains(&name) {
+                    return Err(format!(
+                        "placeholder `${}` is not defined in the search pattern",
+                        name
+                    ));
+                }
+            }
+        }
+        for (name, _) in &constraints {
+            if !placeholders.contains(name) {
+                return Err(format!(
+                    "constrained placeholder `${}` is not defined in the search pattern",
+                    name
+                ));
+            }
+        }
+
+        Ok(Self {
+            pattern,
+            template,
+            constraints,
+        })
+    }
+
+    pub fn has_replacement(&self) -> bool {
+        self.template.is_some()
+    }
+
+    /// Finds all non-overlapping matches in the file of `semantic_model`. When an outer
+    /// match contains further matches inside its placeholders, they are folded into the
+    /// outer replacement.
+    pub fn find_matches(&self, semantic_model: &SemanticModel) -> Vec<SsrMatch> {
+        let Some(pattern_node) = self.pattern.get_pattern_node() else {
+            return Vec::new();
+        };
+        let mut constraints = Vec::new();
+        for (name, type_text) in &self.constraints {
+            let Some(typ) = parse_constraint_type(semantic_model, type_text) else {
+                return Vec::new();
+            };
+            constraints.push((name.as_str(), typ));
+        }
+
+        let mut ctx = SsrContext {
+            semantic_model,
+            pattern_node,
+            template: self.template.as_deref(),
+            constraints,
+        };
+        let root = semantic_model.get_root().syntax().clone();
+        ctx.find_matches_in(&root)
+    }
+
+    /// Returns the text edits for every match in the file. Search-only rules yield no edits.
+    pub fn replace(&self, semantic_model: &SemanticModel) -> Vec<(TextRange, String)> {
+        self.find_matches(semantic_model)
+            .into_iter()
+            .filter_map(|m| Some((m.range, m.replacement?)))
+            .collect()
+    }
+}
+
+struct SsrContext<'a, 'm> {
+    semantic_model: &'a SemanticModel<'m>,
+    pattern_node: LuaSyntaxNode,
+    template: Option<&'a str>,
+    constraints: Vec<(&'a str, LuaType)>,
+}
+
+impl SsrContext<'_, '_> {
+    fn find_matches_in(&mut self, root: &LuaSyntaxNode) -> Vec<SsrMatch> {
+        let mut result = Vec::new();
+        let mut preorder = root.preorder();
+        while let Some(event) = preorder.next() {
+            let rowan::WalkEvent::Enter(node) = event else {
+                continue;
+            };
+            if let Some(ssr_match) = self.try_match(&node) {
+                result.push(ssr_match);
+                preorder.skip_subtree();
+            }
+        }
+
+        result
+    }
+
+    fn try_match(&mut self, node: &LuaSyntaxNode) -> Option<SsrMatch> {
+        let mut bindings = SsrBindings::default();
+        if !match_node(&self.pattern_node, node, &mut bindings) {
+            return None;
+        }
+
+        for (name, typ) in &self.constraints {
+            let expr = bindings
+                .get(name)?
+                .as_node()
+                .cloned()
+                .and_then(LuaExpr::cast)?;
+            let expr_type = self.semantic_model.infer_expr(expr).ok()?;
+            if self.semantic_model.type_check(typ, &expr_type).is_err() {
+                return None;
+            }
+        }
+
+        let mut placeholders = HashMap::new();
+        for (name, element) in bindings.iter() {
+            let text = match element {
+                rowan::NodeOrToken::Node(node) => self.render_nested(node),
+                rowan::NodeOrToken::Token(token) => token.text().to_string(),
+            };
+            placeholders.insert(name.to_string(), text);
+        }
+
+        let replacement = self
+            .template
+            .map(|template| render_template(template, &placeholders));
+        Some(SsrMatch {
+            range: node.text_range(),
+            placeholders,
+            replacement,
+        })
+    }
+
+    /// Renders the text of a bound node, applying the rule to any matches inside it.
+    fn render_nested(&mut self, node: &LuaSyntaxNode) -> String {
+        let text = node.text().to_string();
+        if self.template.is_none() {
+            return text;
+        }
+
+        let start = node.text_range().start();
+        let edits = self
+            .find_matches_in(node)
+            .into_iter()
+            .filter_map(|m| Some((m.range - start, m.replacement?)))
+            .collect();
+        apply_text_edits(&text, edits)
+    }
+}
+
+impl SsrPattern {
+    fn parse(search: &str) -> Result<Self, String> {
+        let source = encode_placeholders(search);
+        let expr_source = format!("return {}", source);
+        let tree = LuaParser::parse(&expr_source, ParserConfig::default());
+        if tree.get_errors().is_empty() {
+            let pattern = Self {
+                tree,
+                kind: SsrPatternKind::Expr,
+            };
+            if let Some(node) = pattern.get_pattern_node() {
+                if is_placeholder_node(&node) {
+                    return Err("search pattern must not be a lone placeholder".to_string());
+                }
+                return Ok(pattern);
+            }
+        }
+
+        let tree = LuaParser::parse(&source, ParserConfig::default());
+        if let Some(error) = tree.get_errors().first() {
+            return Err(format!("invalid search pattern: {}", error.message));
+        }
+        let pattern = Self {
+            tree,
+            kind: SsrPatternKind::Stat,
+        };
+        if pattern.get_pattern_node().is_none() {
+            return Err("search pattern must be a single expression or statement".to_string());
+        }
+
+        Ok(pattern)
+    }
+
+    fn get_pattern_node(&self) -> Option<LuaSyntaxNode> {
+        let block = self.tree.get_chunk_node().get_block()?;
+        let mut stats = block.get_stats();
+        let stat = stats.next()?;
+        if stats.next().is_some() {
+            return None;
+        }
+
+        match self.kind {
+            SsrPatternKind::Stat => Some(stat.syntax().clone()),
+            SsrPatternKind::Expr => {
+                if stat.syntax().kind() != LuaSyntaxKind::ReturnStat.into() {
+                    return None;
+                }
+                let mut exprs = stat.syntax().children().filter_map(LuaExpr::cast);
+                let expr = exprs.next()?;
+                if exprs.next().is_some() {
+                    return None;
+                }
+                Some(expr.syntax().clone())
+            }
+        }
+    }
+}
+
+fn parse_constraints(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut constraints = Vec::new();
+    for item in text.split(',') {
+        let Some((name, type_text)) = item.split_once(':') else {
+            return Err(format!("invalid constraint `{}`", item.trim()));
+        };
+        let Some(name) = name.trim().strip_prefix('$') else {
+            return Err(format!("invalid constraint `{}`", item.trim()));
+        };
+        let type_text = type_text.trim();
+        if name.is_empty() || type_text.is_empty() {
+            return Err(format!("invalid constraint `{}`", item.trim()));
+        }
+        constraints.push((name.to_string(), type_text.to_string()));
+    }
+
+    Ok(constraints)
+}
+
+fn parse_constraint_type(semantic_model: &SemanticModel, type_text: &str) -> Option<LuaType> {
+    let source = format!("---@type {}\nlocal _", type_text);
+    let tree = LuaParser::parse(&source, ParserConfig::default());
+    let doc_type = tree
+        .get_chunk_node()
+        .descendants::<LuaDocTagType>()
+        .next()?
+        .get_type_list()
+        .next()?;
+    let ctx = DocTypeInferContext::new(semantic_model.get_db(), semantic_model.get_file_id());
+    Some(infer_doc_type(ctx, &doc_type))
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Calls `f` for every `$name` placeholder in `text` with its byte range and name. A `$` inside
+/// a string literal or a comment is kept as is, so rules can match string contents.
+fn for_each_placeholder(text: &str, mut f: impl FnMut(std::ops::Range<usize>, &str)) {
+    let literal_ranges = get_literal_ranges(text);
+    let in_literal = |offset: usize| {
+        literal_ranges
+            .iter()
+            .any(|range| range.start <= offset && offset < range.end)
+    };
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c != '$' || !chars.peek().is_some_and(|(_, c)| is_ident_start(*c)) || in_literal(start) {
+            continue;
+        }
+        let mut end = start + 1;
+        while let Some((pos, c)) = chars.peek() {
+            if !is_ident_char(*c) {
+                break;
+            }
+            end = pos + c.len_utf8();
+            chars.next();
+        }
+        f(start..end, &text[start + 1..end]);
+    }
+}
+
+/// Byte ranges of the string literals and comments of `text`.
+fn get_literal_ranges(text: &str) -> Vec<std::ops::Range<usize>> {
+    let mut lexer = LuaLexer::new(Reader::new(text), LexerConfig::default(), None);
+    lexer
+        .tokenize()
+        .into_iter()
+        .filter(|token| {
+            matches!(
+                token.kind,
+                LuaTokenKind::TkString
+                    | LuaTokenKind::TkLongString
+                    | LuaTokenKind::TkShortComment
+                    | LuaTokenKind::TkLongComment
+                    | LuaTokenKind::TKNonStdComment
+            )
+        })
+        .map(|token| token.range.start_offset..token.range.end_offset())
+        .collect()
+}
+
+fn collect_placeholders(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for_each_placeholder(text, |_, name| names.push(name.to_string()));
+    names
+}
+
+fn encode_placeholders(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for_each_placeholder(text, |range, name| {
+        result.push_str(&text[last..range.start]);
+        result.push_str(PLACEHOLDER_PREFIX);
+        result.push_str(name);
+        last = range.end;
+    });
+    result.push_str(&text[last..]);
+    result
+}
+
+fn render_template(template: &str, placeholders: &HashMap<String, String>) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut last = 0;
+    for_each_placeholder(template, |range, name| {
+        result.push_str(&template[last..range.start]);
+        match placeholders.get(name) {
+            Some(text) => result.push_str(text),
+            None => result.push_str(&template[range.clone()]),
+        }
+        last = range.end;
+    });
+    result.push_str(&template[last..]);
+    result
+}
+
+/// Applies non-overlapping edits to `text`. Edits may be given in any order.
+pub fn apply_text_edits(text: &str, mut edits: Vec<(TextRange, String)>) -> String {
+    edits.sort_by_key(|(range, _)| range.start());
+    let mut result = String::with_capacity(text.len());
+    let mut last = TextSize::from(0);
+    for (range, new_text) in edits {
+        if range.start() < last {
+            continue;
+        }
+        result.push_str(&text[TextRange::new(last, range.start())]);
+        result.push_str(&new_text);
+        last = range.end();
+    }
+    result.push_str(&text[usize::from(last)..]);
+    result
+}
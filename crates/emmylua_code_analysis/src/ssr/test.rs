@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod test {
+    use crate::{SsrRule, VirtualWorkspace, apply_text_edits};
+
+    fn replace(ws: &mut VirtualWorkspace, rule: &str, code: &str) -> String {
+        let rule = SsrRule::parse(rule).unwrap();
+        let file_id = ws.def(code);
+        let semantic_model = ws.analysis.compilation.get_semantic_model(file_id).unwrap();
+        apply_text_edits(code, rule.replace(&semantic_model))
+    }
+
+    #[test]
+    fn test_replace_method_call() {
+        let mut ws = VirtualWorkspace::new();
+        let result = replace(
+            &mut ws,
+            r#"$obj:GetComponent("Transform") ==>> $obj.transform"#,
+            r#"
+            local t = player:GetComponent('Transform')
+            local r = player:GetComponent("Renderer")
+            enemy:GetComponent( "Transform" ):Translate(1, 2)
+            "#,
+        );
+        assert_eq!(
+            result,
+            r#"
+            local t = player.transform
+            local r = player:GetComponent("Renderer")
+            enemy.transform:Translate(1, 2)
+            "#
+        );
+    }
+
+    #[test]
+    fn test_nested_matches() {
+        let mut ws = VirtualWorkspace::new();
+        let result = replace(
+            &mut ws,
+            "math.max($a, $b) ==>> max2($a, $b)",
+            "local x = math.max(math.max(a, b), c)",
+        );
+        assert_eq!(result, "local x = max2(max2(a, b), c)");
+    }
+
+    #[test]
+    fn test_repeated_placeholder() {
+        let mut ws = VirtualWorkspace::new();
+        let result = replace(
+            &mut ws,
+            "$a = $a + 1 ==>> $a += 1",
+            r#"
+            i = i + 1
+            j = k + 1
+            t.n = t.n + 1
+            "#,
+        );
+        assert_eq!(
+            result,
+            r#"
+            i += 1
+            j = k + 1
+            t.n += 1
+            "#
+        );
+    }
+
+    #[test]
+    fn test_name_placeholder() {
+        let mut ws = VirtualWorkspace::new();
+        let result = replace(
+            &mut ws,
+            "self.$field ==>> self._$field",
+            "return self.name, other.name",
+        );
+        assert_eq!(result, "return self._name, other.name");
+    }
+
+    #[test]
+    fn test_type_constraint() {
+        let mut ws = VirtualWorkspace::new();
+        ws.def(
+            r#"
+            ---@class GameObject
+            ---@field transform table
+            local GameObject = {}
+
+            ---@param name string
+            function GameObject:GetComponent(name) end
+            "#,
+        );
+        let result = replace(
+            &mut ws,
+            r#"$obj:GetComponent("Transform") ==>> $obj.transform where $obj: GameObject"#,
+            r#"
+            ---@type GameObject
+            local go
+            local other = {}
+            local a = go:GetComponent("Transform")
+            local b = other:GetComponent("Transform")
+            "#,
+        );
+        assert_eq!(
+            result,
+            r#"
+            ---@type GameObject
+            local go
+            local other = {}
+            local a = go.transform
+            local b = other:GetComponent("Transform")
+            "#
+        );
+    }
+
+    #[test]
+    fn test_search_only() {
+        let mut ws = VirtualWorkspace::new();
+        let rule = SsrRule::parse("print($x)").unwrap();
+        let file_id = ws.def("print(1)\nprint(a, b)\nlocal f = print(g)");
+        let semantic_model = ws.analysis.compilation.get_semantic_model(file_id).unwrap();
+        let matches = rule.find_matches(&semantic_model);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(
+            matches[1].placeholders.get("x").map(String::as_str),
+            Some("g")
+        );
+        assert!(matches.iter().all(|m| m.replacement.is_none()));
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert!(SsrRule::parse("").is_err());
+        assert!(SsrRule::parse("$a").is_err());
+        assert!(SsrRule::parse("f($a) ==>> g($b)").is_err());
+        assert!(SsrRule::parse("f($a) ==>> g($a) where $b: string").is_err());
+        assert!(SsrRule::parse("f($a ==>> g($a)").is_err());
+    }
+
+    #[test]
+    fn test_placeholder_text_in_strings() {
+        let mut ws = VirtualWorkspace::new();
+        let result = replace(
+            &mut ws,
+            r#"fmt("$price", $v) ==>> fmt_price($v, "$v")"#,
+            r#"
+            local a = fmt("$price", cost)
+            local b = fmt("price", cost)
+            "#,
+        );
+        assert_eq!(
+            result,
+            r#"
+            local a = fmt_price(cost, "$v")
+            local b = fmt("price", cost)
+            "#
+        );
+    }
+}
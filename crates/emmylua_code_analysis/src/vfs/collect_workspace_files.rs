@@ -1,8 +1,11 @@
 use std::{
     collections::HashSet,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
+use walkdir::WalkDir;
+
 use crate::{Emmyrc, EmmyrcWorkspacePathItem, LuaFileInfo, load_workspace_files};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -142,6 +145,61 @@ pub fn collect_workspace_files(
     files
 }
 
+pub const CONFIG_FILE_NAMES: [&str; 3] = [".luarc.json", ".emmyrc.json", ".emmyrc.lua"];
+
+/// Finds the directories of the main workspaces that contain their own config file, other
+/// than `config_root` whose config applies to the whole workspace. Hidden and ignored
+/// directories are not searched.
+pub fn find_config_scope_dirs(
+    workspaces: &[WorkspaceFolder],
+    emmyrc: &Emmyrc,
+    config_root: Option<&Path>,
+) -> Vec<PathBuf> {
+    let (_, _, exclude_dirs) = calculate_include_and_exclude(emmyrc);
+    let mut dirs = Vec::new();
+    for workspace in workspaces.iter().filter(|workspace| !workspace.is_library) {
+        let walker = WalkDir::new(&workspace.root)
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.file_type().is_dir()
+                    && (entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.'))
+                    && !exclude_dirs.iter().any(|dir| entry.path().starts_with(dir))
+            });
+        for entry in walker.filter_map(|entry| entry.ok()) {
+            let dir = entry.path();
+            if config_root.is_some_and(|root| root == dir) || dirs.iter().any(|d| d == dir) {
+                continue;
+            }
+            if CONFIG_FILE_NAMES
+                .iter()
+                .any(|name| dir.join(name).is_file())
+            {
+                dirs.push(dir.to_path_buf());
+            }
+        }
+    }
+
+    dirs
+}
+
+/// Adds the libraries of the scoped configs that are not libraries of the workspace yet.
+pub fn extend_scoped_library_folders(
+    workspace_folders: &mut Vec<WorkspaceFolder>,
+    scoped_emmyrcs: &[(PathBuf, Arc<Emmyrc>)],
+) {
+    for (_, scoped_emmyrc) in scoped_emmyrcs {
+        for library in build_workspace_folders(&[], scoped_emmyrc) {
+            if library.is_library
+                && !workspace_folders
+                    .iter()
+                    .any(|folder| folder.root == library.root && folder.import == library.import)
+            {
+                workspace_folders.push(library);
+            }
+        }
+    }
+}
+
 pub fn calculate_include_and_exclude(emmyrc: &Emmyrc) -> (Vec<String>, Vec<String>, Vec<PathBuf>) {
     let mut include = vec!["**/*.lua".to_string()];
     let mut exclude = Vec::new();
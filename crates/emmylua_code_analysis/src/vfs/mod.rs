@@ -13,7 +13,7 @@ pub use file_uri_handler::{file_path_to_uri, uri_to_file_path};
 use hashbrown::HashMap;
 pub use loader::{LuaFileInfo, load_workspace_files, read_file_with_encoding};
 use lsp_types::Uri;
-use rowan::NodeCache;
+use rowan::{NodeCache, TextRange, TextSize};
 use std::path::PathBuf;
 use std::sync::Arc;
 pub use virtual_url::VirtualUrlGenerator;
@@ -29,6 +29,7 @@ pub struct Vfs {
     line_index_map: HashMap<FileId, LineIndex>,
     tree_map: HashMap<FileId, LuaSyntaxTree>,
     emmyrc: Option<Arc<Emmyrc>>,
+    scoped_emmyrcs: Vec<(PathBuf, Arc<Emmyrc>)>,
     node_cache: NodeCache,
 }
 
@@ -48,6 +49,7 @@ impl Vfs {
             line_index_map: HashMap::new(),
             tree_map: HashMap::new(),
             emmyrc: None,
+            scoped_emmyrcs: Vec::new(),
             node_cache: NodeCache::default(),
         }
     }
@@ -104,12 +106,20 @@ impl Vfs {
 
         if let Some(data) = &data {
             let line_index = LineIndex::parse(data);
-            let parse_config = self
-                .emmyrc
-                .as_ref()
-                .expect("emmyrc set")
-                .get_parse_config(&mut self.node_cache);
-            let tree = LuaParser::parse(data, parse_config);
+            let old_content = self
+                .file_data
+                .get(fid.id as usize)
+                .and_then(|content| content.as_ref())
+                .map(|content| content.content.as_str());
+            let edit_range = old_content.and_then(|old_content| get_edit_range(old_content, data));
+            let emmyrc = self.get_file_emmyrc(&fid).expect("emmyrc set");
+            let parse_config = emmyrc.get_parse_config(&mut self.node_cache);
+            let tree = match (self.tree_map.get(&fid), edit_range) {
+                (Some(old_tree), Some(edit_range)) => {
+                    LuaParser::reparse(old_tree, edit_range, data, parse_config)
+                }
+                _ => LuaParser::parse(data, parse_config),
+            };
             self.tree_map.insert(fid, tree);
             self.line_index_map.insert(fid, line_index);
         } else {
@@ -165,6 +175,31 @@ impl Vfs {
         self.emmyrc = Some(emmyrc);
     }
 
+    /// Sets the configs that apply to the files below their directory instead of the
+    /// workspace config. The innermost directory wins.
+    pub fn update_scoped_configs(&mut self, mut scoped_emmyrcs: Vec<(PathBuf, Arc<Emmyrc>)>) {
+        scoped_emmyrcs.sort_by_key(|(root, _)| std::cmp::Reverse(root.components().count()));
+        self.scoped_emmyrcs = scoped_emmyrcs;
+    }
+
+    pub fn get_scoped_configs(&self) -> &[(PathBuf, Arc<Emmyrc>)] {
+        &self.scoped_emmyrcs
+    }
+
+    /// Returns the config that applies to the file.
+    pub fn get_file_emmyrc(&self, id: &FileId) -> Option<Arc<Emmyrc>> {
+        if let Some(path) = self.file_path_map.get(&id.id)
+            && let Some((_, emmyrc)) = self
+                .scoped_emmyrcs
+                .iter()
+                .find(|(root, _)| path.starts_with(root))
+        {
+            return Some(emmyrc.clone());
+        }
+
+        self.emmyrc.clone()
+    }
+
     pub fn get_file_content(&self, id: &FileId) -> Option<&String> {
         let opt = &self.file_data[id.id as usize];
         if let Some(s) = opt {
@@ -246,6 +281,7 @@ impl Vfs {
         self.line_index_map.clear();
         self.tree_map.clear();
         self.emmyrc = None;
+        self.scoped_emmyrcs.clear();
         self.node_cache = NodeCache::default();
     }
 }
@@ -255,3 +291,74 @@ struct FileContent {
     content: String,
     is_remote: bool,
 }
+
+/// Returns the range of `old_text` that was replaced to get `new_text`, or `None` when the
+/// texts are equal.
+fn get_edit_range(old_text: &str, new_text: &str) -> Option<TextRange> {
+    if old_text == new_text {
+        return None;
+    }
+
+    let mut prefix = old_text
+        .bytes()
+        .zip(new_text.bytes())
+        .take_while(|(a, b)| a == b)
+        .count();
+    while !old_text.is_char_boundary(prefix) {
+        prefix -= 1;
+    }
+    let max_suffix = old_text.len().min(new_text.len()) - prefix;
+    let mut suffix = old_text
+        .bytes()
+        .rev()
+        .zip(new_text.bytes().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    while !old_text.is_char_boundary(old_text.len() - suffix) {
+        suffix -= 1;
+    }
+
+    Some(TextRange::new(
+        TextSize::from(prefix as u32),
+        TextSize::from((old_text.len() - suffix) as u32),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VirtualUrlGenerator;
+
+    #[test]
+    fn test_edit_range() {
+        assert_eq!(get_edit_range("local a = 1", "local a = 1"), None);
+        assert_eq!(
+            get_edit_range("local a = 1", "local a = 12"),
+            Some(TextRange::new(11.into(), 11.into()))
+        );
+        assert_eq!(
+            get_edit_range("aaaa", "aa"),
+            Some(TextRange::new(2.into(), 4.into()))
+        );
+        assert_eq!(
+            get_edit_range("print('中')", "print('文')"),
+            Some(TextRange::new(7.into(), 10.into()))
+        );
+    }
+
+    #[test]
+    fn test_reparse_on_change() {
+        let mut vfs = Vfs::new();
+        vfs.update_config(Emmyrc::default().into());
+        let uri = VirtualUrlGenerator::new().new_uri("test.lua");
+        let code = "local function f(x)\n    return x\nend\nprint(f(1))\n";
+        let id = vfs.set_file_content(&uri, Some(code.to_string()));
+        let new_code = code.replace("return x", "return x + 1");
+        vfs.set_file_content(&uri, Some(new_code.clone()));
+
+        let tree = vfs.get_syntax_tree(&id).unwrap();
+        assert_eq!(tree.get_red_root().text().to_string(), new_code);
+        assert!(tree.get_errors().is_empty());
+    }
+}
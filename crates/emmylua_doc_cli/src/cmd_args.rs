@@ -43,7 +43,7 @@ pub struct CmdArgs {
     #[arg(long, value_enum, ignore_case = true)]
     pub format: Option<Format>,
 
-    /// Specify output destination (can be stdout when output_format is json)
+    /// Specify output destination (can be stdout when output_format is json or lsif)
     #[arg(long, short, default_value = "./output")]
     pub output: OutputDestination,
 
@@ -69,6 +69,8 @@ pub struct CmdArgs {
 pub enum Format {
     Json,
     Markdown,
+    /// An LSIF dump for code intelligence platforms
+    Lsif,
 }
 
 #[allow(unused)]
@@ -7,6 +7,7 @@ mod cmd_args;
 mod common;
 mod init;
 mod json_generator;
+mod lsif_generator;
 mod markdown_generator;
 
 #[allow(unused)]
@@ -62,5 +63,6 @@ pub fn run_doc_cli(mut cmd_args: CmdArgs) -> Result<(), Box<dyn std::error::Erro
             cmd_args.mixin,
         ),
         Format::Json => json_generator::generate_json(&analysis, cmd_args.output),
+        Format::Lsif => lsif_generator::generate_lsif(&analysis, &main_path, cmd_args.output),
     }
 }
@@ -0,0 +1,624 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, hash_map::Entry},
+    path::Path,
+};
+
+use crate::common::render_typ;
+use crate::lsif_generator::lsif_writer::LsifWriter;
+use emmylua_code_analysis::{
+    DbIndex, FileId, InFiled, LuaCompilation, LuaDecl, LuaMember, LuaMemberKey, LuaMemberOwner,
+    LuaSemanticDeclId, LuaType, LuaTypeDecl, LuaTypeDeclId, MONIKER_SCHEME, RenderLevel,
+    SemanticDeclLevel, SemanticModel, file_path_to_uri, get_moniker_identifier,
+};
+use emmylua_parser::{
+    LuaAstNode, LuaAstToken, LuaDocFieldKey, LuaDocTagField, LuaIndexExpr, LuaIndexKey,
+    LuaSyntaxId, LuaSyntaxNode, LuaTableField,
+};
+use rowan::TextRange;
+use serde_json::{Value, json};
+
+/// A symbol of the dump with everything that is known about it.
+#[derive(Debug, Default)]
+struct Symbol {
+    definitions: Vec<InFiled<TextRange>>,
+    references: Vec<InFiled<TextRange>>,
+    hover: Option<String>,
+    moniker: Option<String>,
+    /// Definitions of the classes that extend this type.
+    implementations: Vec<InFiled<TextRange>>,
+}
+
+/// Exports the definitions, references, hover docs, monikers and class hierarchy of the main
+/// workspace files as LSIF vertices and edges.
+pub fn export(compilation: &LuaCompilation, project_root: &Path) -> Vec<Value> {
+    let db = compilation.get_db();
+    let module_index = db.get_module_index();
+    let mut file_ids = db
+        .get_vfs()
+        .get_all_local_file_ids()
+        .into_iter()
+        .filter(|file_id| module_index.is_main(file_id))
+        .collect::<Vec<_>>();
+    file_ids.sort();
+
+    let mut symbols = Vec::new();
+    collect_decl_symbols(db, &file_ids, &mut symbols);
+    collect_type_symbols(db, &mut symbols);
+    collect_member_symbols(compilation, &file_ids, &mut symbols);
+
+    write_lsif(db, project_root, &file_ids, &symbols)
+}
+
+fn collect_decl_symbols(db: &DbIndex, file_ids: &[FileId], symbols: &mut Vec<Symbol>) {
+    let reference_index = db.get_reference_index();
+    let mut globals: BTreeMap<String, Vec<&LuaDecl>> = BTreeMap::new();
+    for file_id in file_ids {
+        let Some(decl_tree) = db.get_decl_index().get_decl_tree(file_id) else {
+            continue;
+        };
+        let mut decls = decl_tree.get_decls().values().collect::<Vec<_>>();
+        decls.sort_by_key(|decl| decl.get_position());
+        for decl in decls {
+            if decl.is_implicit_self() {
+                continue;
+            }
+            if decl.is_global() {
+                globals
+                    .entry(decl.get_name().to_string())
+                    .or_default()
+                    .push(decl);
+                continue;
+            }
+
+            let references = reference_index
+                .get_decl_references(file_id, &decl.get_id())
+                .map(|decl_ref| {
+                    decl_ref
+                        .cells
+                        .iter()
+                        .filter(|cell| cell.range != decl.get_range())
+                        .map(|cell| InFiled::new(*file_id, cell.range))
+                        .collect()
+                })
+                .unwrap_or_default();
+            symbols.push(Symbol {
+                definitions: vec![InFiled::new(*file_id, decl.get_range())],
+                references,
+                hover: get_decl_hover(db, decl),
+                moniker: get_moniker_identifier(db, &decl.get_id().into()),
+                ..Default::default()
+            });
+        }
+    }
+
+    for (name, decls) in globals {
+        let definitions = decls
+            .iter()
+            .map(|decl| InFiled::new(decl.get_file_id(), decl.get_range()))
+            .collect::<Vec<_>>();
+        let references = reference_index
+            .get_global_references(&name)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|syntax_id| file_ids.contains(&syntax_id.file_id))
+            .map(|syntax_id| InFiled::new(syntax_id.file_id, syntax_id.value.get_range()))
+            .filter(|range| !definitions.contains(range))
+            .collect();
+        symbols.push(Symbol {
+            definitions,
+            references,
+            hover: get_decl_hover(db, decls[0]),
+            moniker: get_moniker_identifier(db, &decls[0].get_id().into()),
+            ..Default::default()
+        });
+    }
+}
+
+fn collect_type_symbols(db: &DbIndex, symbols: &mut Vec<Symbol>) {
+    let type_index = db.get_type_index();
+    let module_index = db.get_module_index();
+    let mut type_decls = type_index
+        .get_all_types()
+        .into_iter()
+        .filter(|type_decl| {
+            type_decl
+                .get_locations()
+                .iter()
+                .any(|location| module_index.is_main(&location.file_id))
+        })
+        .collect::<Vec<_>>();
+    type_decls.sort_by(|a, b| a.get_full_name().cmp(b.get_full_name()));
+
+    let mut type_symbols: HashMap<LuaTypeDeclId, usize> = HashMap::new();
+    for type_decl in &type_decls {
+        let definitions = type_decl
+            .get_locations()
+            .iter()
+            .filter(|location| module_index.is_main(&location.file_id))
+            .map(|location| InFiled::new(location.file_id, location.range))
+            .collect::<Vec<_>>();
+        let references = db
+            .get_reference_index()
+            .get_type_references(&type_decl.get_id())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|range| module_index.is_main(&range.file_id) && !definitions.contains(range))
+            .collect();
+        type_symbols.insert(type_decl.get_id(), symbols.len());
+        symbols.push(Symbol {
+            definitions,
+            references,
+            hover: get_type_hover(db, type_decl),
+            moniker: get_moniker_identifier(db, &type_decl.get_id().into()),
+            ..Default::default()
+        });
+    }
+
+    for type_decl in type_decls.iter().filter(|type_decl| type_decl.is_class()) {
+        let definitions = symbols[type_symbols[&type_decl.get_id()]]
+            .definitions
+            .clone();
+        for super_type in type_index
+            .get_super_types(&type_decl.get_id())
+            .unwrap_or_default()
+        {
+            let (LuaType::Ref(super_id) | LuaType::Def(super_id)) = super_type else {
+                continue;
+            };
+            if let Some(index) = type_symbols.get(&super_id) {
+                symbols[*index]
+                    .implementations
+                    .extend(definitions.iter().cloned());
+            }
+        }
+    }
+}
+
+/// What an index reference resolves to, member references match members of the same owner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ReferenceTarget {
+    Decl(LuaSemanticDeclId),
+    Owner(LuaMemberKey, LuaMemberOwner),
+}
+
+fn collect_member_symbols(
+    compilation: &LuaCompilation,
+    file_ids: &[FileId],
+    symbols: &mut Vec<Symbol>,
+) {
+    let db = compilation.get_db();
+    let mut members = Vec::new();
+    for file_id in file_ids {
+        for member in db.get_member_index().get_file_members(file_id) {
+            if !matches!(
+                member.get_key(),
+                LuaMemberKey::Name(_) | LuaMemberKey::Integer(_)
+            ) {
+                continue;
+            }
+            if let Some(definition) = get_member_name_range(db, *file_id, &member.get_syntax_id()) {
+                members.push((member, definition));
+            }
+        }
+    }
+
+    let keys = members
+        .iter()
+        .map(|(member, _)| member.get_key().clone())
+        .collect::<HashSet<_>>();
+    let references = collect_index_references(compilation, file_ids, keys);
+
+    let member_index = db.get_member_index();
+    for (member, definition) in members {
+        let member_decl = LuaSemanticDeclId::Member(member.get_id());
+        let mut targets = vec![ReferenceTarget::Decl(member_decl.clone())];
+        if let Some(owner) = member_index.get_current_owner(&member.get_id()) {
+            targets.push(ReferenceTarget::Owner(
+                member.get_key().clone(),
+                owner.clone(),
+            ));
+        }
+
+        let mut member_references = Vec::new();
+        for range in targets
+            .iter()
+            .filter_map(|target| references.get(target))
+            .flatten()
+        {
+            if *range != definition && !member_references.contains(range) {
+                member_references.push(range.clone());
+            }
+        }
+        member_references.sort_by_key(|range| (range.file_id, range.value.start()));
+
+        symbols.push(Symbol {
+            definitions: vec![definition],
+            references: member_references,
+            hover: get_member_hover(db, member),
+            moniker: get_moniker_identifier(db, &member_decl),
+            ..Default::default()
+        });
+    }
+}
+
+/// Resolves every index reference of `keys` once and groups the reference ranges by what they
+/// refer to.
+fn collect_index_references(
+    compilation: &LuaCompilation,
+    file_ids: &[FileId],
+    keys: HashSet<LuaMemberKey>,
+) -> HashMap<ReferenceTarget, Vec<InFiled<TextRange>>> {
+    let db = compilation.get_db();
+    let member_index = db.get_member_index();
+    let mut semantic_models: HashMap<FileId, SemanticModel> = HashMap::new();
+    let mut references: HashMap<ReferenceTarget, Vec<InFiled<TextRange>>> = HashMap::new();
+    for key in keys {
+        for syntax_id in db
+            .get_reference_index()
+            .get_index_references(&key)
+            .unwrap_or_default()
+        {
+            if !file_ids.contains(&syntax_id.file_id) {
+                continue;
+            }
+            let Some(range) = get_member_name_range(db, syntax_id.file_id, &syntax_id.value) else {
+                continue;
+            };
+            let semantic_model = match semantic_models.entry(syntax_id.file_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let Some(semantic_model) = compilation.get_semantic_model(syntax_id.file_id)
+                    else {
+                        continue;
+                    };
+                    entry.insert(semantic_model)
+                }
+            };
+            let Some(node) = syntax_id
+                .value
+                .to_node_from_root(semantic_model.get_root().syntax())
+            else {
+                continue;
+            };
+            let Some(decl) = semantic_model.find_decl(node.into(), SemanticDeclLevel::default())
+            else {
+                continue;
+            };
+
+            // same rules as `is_reference_to`: the member itself, or a member of the same owner
+            // or of the same origin owner
+            let mut targets = vec![ReferenceTarget::Decl(decl.clone())];
+            if let LuaSemanticDeclId::Member(member_id) = decl {
+                if let Some(owner) = member_index.get_current_owner(&member_id) {
+                    targets.push(ReferenceTarget::Owner(key.clone(), owner.clone()));
+                }
+                if let Some(LuaSemanticDeclId::Member(origin_id)) =
+                    semantic_model.get_member_origin_owner(member_id)
+                    && let Some(owner) = member_index.get_current_owner(&origin_id)
+                {
+                    targets.push(ReferenceTarget::Owner(key.clone(), owner.clone()));
+                }
+            }
+
+            targets.dedup();
+            for target in targets {
+                references.entry(target).or_default().push(range.clone());
+            }
+        }
+    }
+
+    references
+}
+
+/// Returns the range of the name in a member definition or index expression.
+fn get_member_name_range(
+    db: &DbIndex,
+    file_id: FileId,
+    syntax_id: &LuaSyntaxId,
+) -> Option<InFiled<TextRange>> {
+    let root = db.get_vfs().get_syntax_tree(&file_id)?.get_red_root();
+    let node = syntax_id.to_node_from_root(&root)?;
+    let range = get_name_range(&node).unwrap_or(syntax_id.get_range());
+    Some(InFiled::new(file_id, range))
+}
+
+fn get_name_range(node: &LuaSyntaxNode) -> Option<TextRange> {
+    if let Some(index_expr) = LuaIndexExpr::cast(node.clone()) {
+        return get_index_key_range(index_expr.get_index_key()?);
+    }
+    if let Some(table_field) = LuaTableField::cast(node.clone()) {
+        return get_index_key_range(table_field.get_field_key()?);
+    }
+    if let Some(doc_field) = LuaDocTagField::cast(node.clone()) {
+        return match doc_field.get_field_key()? {
+            LuaDocFieldKey::Name(name) => Some(name.get_range()),
+            LuaDocFieldKey::String(string) => Some(string.get_range()),
+            LuaDocFieldKey::Integer(integer) => Some(integer.get_range()),
+            LuaDocFieldKey::Type(_) => None,
+        };
+    }
+
+    None
+}
+
+fn get_index_key_range(key: LuaIndexKey) -> Option<TextRange> {
+    match key {
+        LuaIndexKey::Name(name) => Some(name.get_range()),
+        LuaIndexKey::String(string) => Some(string.get_range()),
+        LuaIndexKey::Integer(integer) => Some(integer.get_range()),
+        LuaIndexKey::Expr(_) | LuaIndexKey::Idx(_) => None,
+    }
+}
+
+fn get_decl_hover(db: &DbIndex, decl: &LuaDecl) -> Option<String> {
+    let typ = db
+        .get_type_index()
+        .get_type_cache(&decl.get_id().into())
+        .map(|cache| cache.as_type().clone())
+        .unwrap_or(LuaType::Unknown);
+    let kind = if decl.is_global() { "global" } else { "local" };
+    Some(render_hover(
+        db,
+        &format!(
+            "{} {}: {}",
+            kind,
+            decl.get_name(),
+            render_typ(db, &typ, RenderLevel::Simple)
+        ),
+        &decl.get_id().into(),
+    ))
+}
+
+fn get_type_hover(db: &DbIndex, type_decl: &LuaTypeDecl) -> Option<String> {
+    let signature = if type_decl.is_class() {
+        let super_types = db
+            .get_type_index()
+            .get_super_types(&type_decl.get_id())
+            .unwrap_or_default()
+            .iter()
+            .map(|typ| render_typ(db, typ, RenderLevel::Simple))
+            .collect::<Vec<_>>();
+        if super_types.is_empty() {
+            format!("class {}", type_decl.get_full_name())
+        } else {
+            format!(
+                "class {}: {}",
+                type_decl.get_full_name(),
+                super_types.join(", ")
+            )
+        }
+    } else if type_decl.is_enum() {
+        format!("enum {}", type_decl.get_full_name())
+    } else {
+        let origin = type_decl
+            .get_alias_ref()
+            .map(|typ| render_typ(db, typ, RenderLevel::Simple))
+            .unwrap_or_default();
+        format!("alias {} = {}", type_decl.get_full_name(), origin)
+    };
+
+    Some(render_hover(db, &signature, &type_decl.get_id().into()))
+}
+
+fn get_member_hover(db: &DbIndex, member: &LuaMember) -> Option<String> {
+    let typ = db
+        .get_type_index()
+        .get_type_cache(&member.get_id().into())
+        .map(|cache| cache.as_type().clone())
+        .unwrap_or(LuaType::Unknown);
+    let name = match member.get_key() {
+        LuaMemberKey::Name(name) => name.to_string(),
+        LuaMemberKey::Integer(i) => format!("[{}]", i),
+        _ => return None,
+    };
+    let name = match db.get_member_index().get_current_owner(&member.get_id()) {
+        Some(LuaMemberOwner::Type(type_decl_id)) => format!("{}.{}", type_decl_id.get_name(), name),
+        Some(LuaMemberOwner::GlobalPath(global_id)) => {
+            format!("{}.{}", global_id.get_name(), name)
+        }
+        _ => name,
+    };
+
+    Some(render_hover(
+        db,
+        &format!(
+            "(field) {}: {}",
+            name,
+            render_typ(db, &typ, RenderLevel::Simple)
+        ),
+        &LuaSemanticDeclId::Member(member.get_id()),
+    ))
+}
+
+fn render_hover(db: &DbIndex, signature: &str, semantic_decl: &LuaSemanticDeclId) -> String {
+    let description = db
+        .get_property_index()
+        .get_property(semantic_decl)
+        .and_then(|property| property.description());
+    match description {
+        Some(description) => format!("```lua\n{}\n```\n\n---\n\n{}", signature, description),
+        None => format!("```lua\n{}\n```", signature),
+    }
+}
+
+fn write_lsif(
+    db: &DbIndex,
+    project_root: &Path,
+    file_ids: &[FileId],
+    symbols: &[Symbol],
+) -> Vec<Value> {
+    let vfs = db.get_vfs();
+    let mut writer = LsifWriter::default();
+    writer.vertex(
+        "metaData",
+        json!({
+            "version": "0.6.0",
+            "projectRoot": file_path_to_uri(&project_root.to_path_buf())
+                .map(|uri| uri.as_str().to_string())
+                .unwrap_or_default(),
+            "positionEncoding": "utf-16",
+            "toolInfo": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+        }),
+    );
+    let project = writer.vertex("project", json!({ "kind": "lua" }));
+
+    let mut documents = HashMap::new();
+    for file_id in file_ids {
+        let Some(document) = vfs.get_document(file_id) else {
+            continue;
+        };
+        let document_id = writer.vertex(
+            "document",
+            json!({
+                "uri": document.get_uri().as_str(),
+                "languageId": "lua",
+            }),
+        );
+        documents.insert(*file_id, document_id);
+    }
+
+    // a range belongs to the first symbol that claims it
+    let mut ranges: HashMap<InFiled<TextRange>, u64> = HashMap::new();
+    let mut claimed_ranges = HashSet::new();
+    let mut document_ranges: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+    let mut add_range = |writer: &mut LsifWriter, range: &InFiled<TextRange>| -> Option<u64> {
+        if let Some(range_id) = ranges.get(range) {
+            return Some(*range_id);
+        }
+        let document_id = *documents.get(&range.file_id)?;
+        let lsp_range = vfs
+            .get_document(&range.file_id)?
+            .to_lsp_range(range.value)?;
+        let range_id = writer.vertex(
+            "range",
+            json!({
+                "start": lsp_range.start,
+                "end": lsp_range.end,
+            }),
+        );
+        ranges.insert(range.clone(), range_id);
+        document_ranges
+            .entry(document_id)
+            .or_default()
+            .push(range_id);
+        Some(range_id)
+    };
+
+    let mut result_sets = Vec::new();
+    for symbol in symbols {
+        let result_set = writer.vertex("resultSet", json!({}));
+        result_sets.push(result_set);
+        let mut definitions = Vec::new();
+        let mut references = Vec::new();
+        for (range, is_definition) in symbol
+            .definitions
+            .iter()
+            .map(|range| (range, true))
+            .chain(symbol.references.iter().map(|range| (range, false)))
+        {
+            let Some(range_id) = add_range(&mut writer, range) else {
+                continue;
+            };
+            if !claimed_ranges.insert(range_id) {
+                continue;
+            }
+            writer.edge("next", range_id, result_set);
+            let document_id = documents[&range.file_id];
+            if is_definition {
+                definitions.push((document_id, range_id));
+            } else {
+                references.push((document_id, range_id));
+            }
+        }
+
+        if !definitions.is_empty() {
+            let definition_result = writer.vertex("definitionResult", json!({}));
+            writer.edge("textDocument/definition", result_set, definition_result);
+            for (document_id, range_ids) in group_by_document(&definitions) {
+                writer.item_edge(definition_result, range_ids, document_id, None);
+            }
+        }
+
+        let reference_result = writer.vertex("referenceResult", json!({}));
+        writer.edge("textDocument/references", result_set, reference_result);
+        for (document_id, range_ids) in group_by_document(&definitions) {
+            writer.item_edge(
+                reference_result,
+                range_ids,
+                document_id,
+                Some("definitions"),
+            );
+        }
+        for (document_id, range_ids) in group_by_document(&references) {
+            writer.item_edge(reference_result, range_ids, document_id, Some("references"));
+        }
+
+        let implementations = symbol
+            .implementations
+            .iter()
+            .filter_map(|range| {
+                let range_id = add_range(&mut writer, range)?;
+                Some((documents[&range.file_id], range_id))
+            })
+            .collect::<Vec<_>>();
+        if !implementations.is_empty() {
+            let implementation_result = writer.vertex("implementationResult", json!({}));
+            writer.edge(
+                "textDocument/implementation",
+                result_set,
+                implementation_result,
+            );
+            for (document_id, range_ids) in group_by_document(&implementations) {
+                writer.item_edge(implementation_result, range_ids, document_id, None);
+            }
+        }
+
+        if let Some(hover) = &symbol.hover {
+            let hover_result = writer.vertex(
+                "hoverResult",
+                json!({
+                    "result": {
+                        "contents": {
+                            "kind": "markdown",
+                            "value": hover,
+                        },
+                    },
+                }),
+            );
+            writer.edge("textDocument/hover", result_set, hover_result);
+        }
+
+        if let Some(identifier) = &symbol.moniker {
+            let moniker = writer.vertex(
+                "moniker",
+                json!({
+                    "scheme": MONIKER_SCHEME,
+                    "identifier": identifier,
+                    "kind": "export",
+                    "unique": "scheme",
+                }),
+            );
+            writer.edge("moniker", result_set, moniker);
+        }
+    }
+
+    for (document_id, range_ids) in document_ranges {
+        writer.edges("contains", document_id, range_ids);
+    }
+    let mut document_ids = documents.into_values().collect::<Vec<_>>();
+    document_ids.sort();
+    writer.edges("contains", project, document_ids);
+
+    writer.finish()
+}
+
+fn group_by_document(ranges: &[(u64, u64)]) -> BTreeMap<u64, Vec<u64>> {
+    let mut groups: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+    for (document_id, range_id) in ranges {
+        groups.entry(*document_id).or_default().push(*range_id);
+    }
+    groups
+}
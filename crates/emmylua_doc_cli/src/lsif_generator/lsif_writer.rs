@@ -0,0 +1,88 @@
+use serde_json::{Map, Value, json};
+
+/// Collects the vertices and edges of an LSIF dump, see
+/// https://microsoft.github.io/language-server-protocol/specifications/lsif/0.6.0/specification/
+#[derive(Debug, Default)]
+pub struct LsifWriter {
+    next_id: u64,
+    elements: Vec<Value>,
+}
+
+impl LsifWriter {
+    pub fn vertex(&mut self, label: &str, data: Value) -> u64 {
+        let id = self.next_id();
+        let mut vertex = Map::new();
+        vertex.insert("id".to_string(), json!(id));
+        vertex.insert("type".to_string(), json!("vertex"));
+        vertex.insert("label".to_string(), json!(label));
+        if let Value::Object(data) = data {
+            vertex.extend(data);
+        }
+        self.elements.push(Value::Object(vertex));
+        id
+    }
+
+    /// Adds a 1:1 edge.
+    pub fn edge(&mut self, label: &str, out_v: u64, in_v: u64) {
+        let id = self.next_id();
+        self.elements.push(json!({
+            "id": id,
+            "type": "edge",
+            "label": label,
+            "outV": out_v,
+            "inV": in_v,
+        }));
+    }
+
+    /// Adds a 1:n edge.
+    pub fn edges(&mut self, label: &str, out_v: u64, in_vs: Vec<u64>) {
+        if in_vs.is_empty() {
+            return;
+        }
+
+        let id = self.next_id();
+        self.elements.push(json!({
+            "id": id,
+            "type": "edge",
+            "label": label,
+            "outV": out_v,
+            "inVs": in_vs,
+        }));
+    }
+
+    /// Adds an `item` edge from a result to ranges of `document`.
+    pub fn item_edge(
+        &mut self,
+        out_v: u64,
+        in_vs: Vec<u64>,
+        document: u64,
+        property: Option<&str>,
+    ) {
+        if in_vs.is_empty() {
+            return;
+        }
+
+        let id = self.next_id();
+        let mut edge = json!({
+            "id": id,
+            "type": "edge",
+            "label": "item",
+            "outV": out_v,
+            "inVs": in_vs,
+            "document": document,
+        });
+        if let Some(property) = property {
+            edge["property"] = json!(property);
+        }
+        self.elements.push(edge);
+    }
+
+    pub fn finish(self) -> Vec<Value> {
+        self.elements
+    }
+
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+}
@@ -0,0 +1,58 @@
+use crate::OutputDestination;
+use emmylua_code_analysis::EmmyLuaAnalysis;
+use std::{io::Write, path::Path};
+
+mod export;
+mod lsif_writer;
+#[cfg(test)]
+mod tests;
+
+pub fn generate_lsif(
+    analysis: &EmmyLuaAnalysis,
+    project_root: &Path,
+    output: OutputDestination,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = match output {
+        OutputDestination::File(output) if output.extension() == Some("lsif".as_ref()) => {
+            if let Some(parent) = output.parent()
+                && !parent.exists()
+            {
+                log::info!("Creating output directory: {:?}", parent);
+                std::fs::create_dir_all(parent)?;
+            }
+
+            OutputDestination::File(output)
+        }
+        OutputDestination::File(output) => {
+            if !output.exists() {
+                log::info!("Creating output directory: {:?}", output);
+                std::fs::create_dir_all(&output)?;
+            }
+
+            OutputDestination::File(output.join("dump.lsif"))
+        }
+        OutputDestination::Stdout => OutputDestination::Stdout,
+    };
+
+    let elements = export::export(&analysis.compilation, project_root);
+
+    match output {
+        OutputDestination::Stdout => {
+            let mut stdout = std::io::stdout().lock();
+            for element in &elements {
+                writeln!(stdout, "{}", serde_json::to_string(element)?)?;
+            }
+        }
+        OutputDestination::File(lsif_path) => {
+            log::info!("Writing LSIF dump to: {:?}", lsif_path);
+            let mut file = std::io::BufWriter::new(std::fs::File::create(&lsif_path)?);
+            for element in &elements {
+                writeln!(file, "{}", serde_json::to_string(element)?)?;
+            }
+            file.flush()?;
+            eprintln!("LSIF dump exported to {:?}", lsif_path);
+        }
+    }
+
+    Ok(())
+}
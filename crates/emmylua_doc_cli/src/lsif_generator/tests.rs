@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use emmylua_code_analysis::VirtualWorkspace;
+use serde_json::Value;
+
+use super::export::export;
+
+/// A range of the dump as `(file name, line, character)`.
+type Location = (String, u64, u64);
+
+struct Dump {
+    vertices: HashMap<u64, Value>,
+    edges: Vec<Value>,
+}
+
+impl Dump {
+    fn new(ws: &VirtualWorkspace) -> Self {
+        let elements = export(&ws.analysis.compilation, &ws.virtual_url_generator.base);
+        let mut vertices = HashMap::new();
+        let mut edges = Vec::new();
+        for element in elements {
+            if element["type"] == "vertex" {
+                vertices.insert(element["id"].as_u64().unwrap(), element);
+            } else {
+                edges.push(element);
+            }
+        }
+        Self { vertices, edges }
+    }
+
+    fn edge_targets(&self, label: &str, out_v: u64) -> Vec<u64> {
+        self.edges
+            .iter()
+            .filter(|edge| edge["label"] == label && edge["outV"] == out_v)
+            .flat_map(|edge| match edge.get("inVs") {
+                Some(in_vs) => in_vs
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|id| id.as_u64().unwrap())
+                    .collect(),
+                None => vec![edge["inV"].as_u64().unwrap()],
+            })
+            .collect()
+    }
+
+    fn file_name(&self, document: u64) -> String {
+        let uri = self.vertices[&document]["uri"].as_str().unwrap();
+        uri.rsplit('/').next().unwrap().to_string()
+    }
+
+    fn location(&self, document: u64, range: u64) -> Location {
+        let start = &self.vertices[&range]["start"];
+        (
+            self.file_name(document),
+            start["line"].as_u64().unwrap(),
+            start["character"].as_u64().unwrap(),
+        )
+    }
+
+    /// Returns the result set of the range starting at `line:character` of `file_name`.
+    fn result_set(&self, file_name: &str, line: u64, character: u64) -> u64 {
+        let (_, document) = self
+            .vertices
+            .iter()
+            .find(|(id, vertex)| vertex["label"] == "document" && self.file_name(**id) == file_name)
+            .unwrap();
+        let document = document["id"].as_u64().unwrap();
+        let range = self
+            .edge_targets("contains", document)
+            .into_iter()
+            .find(|range| {
+                self.location(document, *range) == (file_name.to_string(), line, character)
+            })
+            .unwrap_or_else(|| panic!("no range at {}:{}:{}", file_name, line, character));
+        self.edge_targets("next", range)[0]
+    }
+
+    fn items(&self, result: u64, property: Option<&str>) -> Vec<Location> {
+        let mut locations = self
+            .edges
+            .iter()
+            .filter(|edge| edge["label"] == "item" && edge["outV"] == result)
+            .filter(|edge| edge.get("property").and_then(Value::as_str) == property)
+            .flat_map(|edge| {
+                let document = edge["document"].as_u64().unwrap();
+                edge["inVs"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(move |range| self.location(document, range.as_u64().unwrap()))
+            })
+            .collect::<Vec<_>>();
+        locations.sort();
+        locations
+    }
+
+    fn definitions(&self, result_set: u64) -> Vec<Location> {
+        let result = self.edge_targets("textDocument/definition", result_set)[0];
+        self.items(result, None)
+    }
+
+    fn references(&self, result_set: u64) -> Vec<Location> {
+        let result = self.edge_targets("textDocument/references", result_set)[0];
+        self.items(result, Some("references"))
+    }
+
+    fn implementations(&self, result_set: u64) -> Vec<Location> {
+        let result = self.edge_targets("textDocument/implementation", result_set)[0];
+        self.items(result, None)
+    }
+
+    fn moniker(&self, result_set: u64) -> Option<String> {
+        let moniker = *self.edge_targets("moniker", result_set).first()?;
+        Some(self.vertices[&moniker]["identifier"].as_str()?.to_string())
+    }
+}
+
+fn location(file_name: &str, line: u64, character: u64) -> Location {
+    (file_name.to_string(), line, character)
+}
+
+#[test]
+fn test_local_definition_and_references() {
+    let mut ws = VirtualWorkspace::new();
+    ws.def_file(
+        "count.lua",
+        r#"
+        local count = 1
+        count = count + 1
+        print(count)
+        "#,
+    );
+
+    let dump = Dump::new(&ws);
+    let result_set = dump.result_set("count.lua", 1, 14);
+    assert_eq!(
+        dump.definitions(result_set),
+        vec![location("count.lua", 1, 14)]
+    );
+    assert_eq!(
+        dump.references(result_set),
+        vec![
+            location("count.lua", 2, 8),
+            location("count.lua", 2, 16),
+            location("count.lua", 3, 14),
+        ]
+    );
+    // a plain local is not visible from other packages
+    assert_eq!(dump.moniker(result_set), None);
+}
+
+#[test]
+fn test_member_references_across_files() {
+    let mut ws = VirtualWorkspace::new();
+    ws.def_files(vec![
+        (
+            "point.lua",
+            r#"
+            ---@class Point
+            ---@field x number
+            local Point = {}
+
+            function Point:len()
+                return self.x
+            end
+
+            return Point
+            "#,
+        ),
+        (
+            "main.lua",
+            r#"
+            local Point = require("point")
+            ---@type Point
+            local p
+            print(p.x, p:len())
+            "#,
+        ),
+    ]);
+
+    let dump = Dump::new(&ws);
+    let field = dump.result_set("point.lua", 2, 22);
+    assert_eq!(dump.definitions(field), vec![location("point.lua", 2, 22)]);
+    assert_eq!(
+        dump.references(field),
+        vec![location("main.lua", 4, 20), location("point.lua", 6, 28)]
+    );
+    assert_eq!(dump.moniker(field), Some("point:Point.x".to_string()));
+
+    let method = dump.result_set("point.lua", 5, 27);
+    assert_eq!(dump.references(method), vec![location("main.lua", 4, 25)]);
+    assert_eq!(dump.moniker(method), Some("point:Point.len".to_string()));
+
+    let class = dump.result_set("point.lua", 1, 22);
+    assert_eq!(dump.references(class), vec![location("main.lua", 2, 21)]);
+    assert_eq!(dump.moniker(class), Some("point:Point".to_string()));
+}
+
+#[test]
+fn test_class_implementations() {
+    let mut ws = VirtualWorkspace::new();
+    ws.def_files(vec![
+        (
+            "animal.lua",
+            r#"
+            ---@class Animal
+            local Animal = {}
+            "#,
+        ),
+        (
+            "dog.lua",
+            r#"
+            ---@class Dog: Animal
+            local Dog = {}
+
+            ---@class Puppy: Dog
+            local Puppy = {}
+            "#,
+        ),
+    ]);
+
+    let dump = Dump::new(&ws);
+    let animal = dump.result_set("animal.lua", 1, 22);
+    assert_eq!(
+        dump.implementations(animal),
+        vec![location("dog.lua", 1, 22)]
+    );
+    let dog = dump.result_set("dog.lua", 1, 22);
+    assert_eq!(dump.implementations(dog), vec![location("dog.lua", 4, 22)]);
+    let puppy = dump.result_set("dog.lua", 4, 22);
+    assert!(
+        dump.edge_targets("textDocument/implementation", puppy)
+            .is_empty()
+    );
+}
@@ -2,6 +2,7 @@ mod client;
 mod client_id;
 mod file_diagnostic;
 mod lsp_features;
+mod runtime_trace_cache;
 mod snapshot;
 mod status_bar;
 mod workspace_manager;
@@ -13,6 +14,7 @@ pub use file_diagnostic::FileDiagnostic;
 pub use lsp_features::LspFeatures;
 use lsp_server::{Connection, ErrorCode, Message, RequestId, Response};
 use lsp_types::ClientCapabilities;
+pub use runtime_trace_cache::RuntimeTraceCache;
 pub use snapshot::ServerContextSnapshot;
 pub use status_bar::ProgressTask;
 pub use status_bar::StatusBar;
@@ -91,6 +93,7 @@ use crate::context::snapshot::ServerContextInner;
 // - `workspace_initialized` (AtomicBool)
 // - `workspace_diagnostic_level` (AtomicU8)
 // - `workspace_version` (AtomicI64)
+// - `runtime_trace_cache` (std Mutex, only held while reading or replacing the cached trace)
 //
 // ## Notes:
 // - Use `drop(lock_guard)` explicitly to release locks early when needed
@@ -141,6 +144,7 @@ impl ServerContext {
                 workspace_manager,
                 status_bar,
                 lsp_features,
+                runtime_trace_cache: Arc::new(RuntimeTraceCache::new()),
             }),
         }
     }
@@ -0,0 +1,45 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use emmylua_code_analysis::RuntimeTrace;
+
+type TraceFileKey = Vec<(String, Option<SystemTime>)>;
+
+/// Parsed runtime trace files, reloaded only when the configured paths or their modification
+/// times change.
+#[derive(Default)]
+pub struct RuntimeTraceCache {
+    cached: Mutex<Option<(TraceFileKey, Arc<RuntimeTrace>)>>,
+}
+
+impl RuntimeTraceCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, trace_files: &[String]) -> Arc<RuntimeTrace> {
+        let key = trace_files
+            .iter()
+            .map(|path| {
+                let modified = std::fs::metadata(path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok();
+                (path.clone(), modified)
+            })
+            .collect::<TraceFileKey>();
+
+        // 只在读写缓存时短暂持有锁, 不会与其他锁嵌套
+        let mut cached = self.cached.lock().expect("trace cache mutex poisoned");
+        if let Some((cached_key, trace)) = cached.as_ref()
+            && *cached_key == key
+        {
+            return trace.clone();
+        }
+
+        let trace = Arc::new(RuntimeTrace::load_files(trace_files));
+        *cached = Some((key, trace.clone()));
+        trace
+    }
+}
@@ -3,7 +3,7 @@ use tokio::sync::RwLock;
 
 use emmylua_code_analysis::EmmyLuaAnalysis;
 
-use crate::context::lsp_features::LspFeatures;
+use crate::context::{lsp_features::LspFeatures, runtime_trace_cache::RuntimeTraceCache};
 
 use super::{
     client::ClientProxy, file_diagnostic::FileDiagnostic, status_bar::StatusBar,
@@ -43,6 +43,10 @@ impl ServerContextSnapshot {
     pub fn lsp_features(&self) -> &LspFeatures {
         &self.inner.lsp_features
     }
+
+    pub fn runtime_trace_cache(&self) -> &RuntimeTraceCache {
+        &self.inner.runtime_trace_cache
+    }
 }
 
 pub struct ServerContextInner {
@@ -52,4 +56,5 @@ pub struct ServerContextInner {
     pub workspace_manager: Arc<RwLock<WorkspaceManager>>,
     pub status_bar: Arc<StatusBar>,
     pub lsp_features: Arc<LspFeatures>,
+    pub runtime_trace_cache: Arc<RuntimeTraceCache>,
 }
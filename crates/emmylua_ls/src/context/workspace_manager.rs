@@ -4,7 +4,7 @@ mod tests;
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU8, AtomicU64, Ordering};
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
     time::Duration,
 };
@@ -14,8 +14,9 @@ use crate::context::ServerContextSnapshot;
 use crate::context::lsp_features::LspFeatures;
 use crate::handlers::{ClientConfig, init_analysis, register_files_watch};
 use emmylua_code_analysis::{
-    EmmyLuaAnalysis, Emmyrc, WorkspaceFileMatcher, WorkspaceFolder, load_configs,
-    read_file_with_encoding, uri_to_file_path,
+    CONFIG_FILE_NAMES, EmmyLuaAnalysis, Emmyrc, WorkspaceFileMatcher, WorkspaceFolder,
+    build_workspace_folders, collect_workspace_files, extend_scoped_library_folders,
+    find_config_scope_dirs, load_configs, read_file_with_encoding, uri_to_file_path,
 };
 use lsp_types::Uri;
 use tokio::sync::{Mutex as AsyncMutex, RwLock};
@@ -34,6 +35,9 @@ pub struct WorkspaceManager {
     pub workspace_folders: Vec<WorkspaceFolder>,
     pub watcher: Option<notify::RecommendedWatcher>,
     open_file_texts: HashMap<Uri, String>,
+    /// Open files whose incremental changes could not be applied. Their changes are ignored
+    /// until the client sends the full text again.
+    desynced_files: HashSet<Uri>,
     open_file_state_version: u64,
     pub match_file_pattern: WorkspaceFileMatcher,
     workspace_diagnostic_level: Arc<AtomicU8>,
@@ -60,6 +64,7 @@ impl WorkspaceManager {
             workspace_folders: Vec::new(),
             watcher: None,
             open_file_texts: HashMap::new(),
+            desynced_files: HashSet::new(),
             open_file_state_version: 0,
             match_file_pattern: WorkspaceFileMatcher::default(),
             workspace_diagnostic_level: Arc::new(AtomicU8::new(
@@ -91,15 +96,34 @@ impl WorkspaceManager {
     }
 
     pub fn sync_open_file(&mut self, uri: Uri, text: String) {
+        self.desynced_files.remove(&uri);
         self.open_file_texts.insert(uri, text);
         self.open_file_state_version = self.open_file_state_version.wrapping_add(1);
     }
 
     pub fn close_open_file(&mut self, uri: &Uri) {
+        self.desynced_files.remove(uri);
         self.open_file_texts.remove(uri);
         self.open_file_state_version = self.open_file_state_version.wrapping_add(1);
     }
 
+    pub fn get_open_file_text_mut(&mut self, uri: &Uri) -> Option<&mut String> {
+        let text = self.open_file_texts.get_mut(uri)?;
+        self.open_file_state_version = self.open_file_state_version.wrapping_add(1);
+        Some(text)
+    }
+
+    /// Forget the text of an open file that no longer matches the editor.
+    pub fn mark_file_desynced(&mut self, uri: &Uri) {
+        self.open_file_texts.remove(uri);
+        self.desynced_files.insert(uri.clone());
+        self.open_file_state_version = self.open_file_state_version.wrapping_add(1);
+    }
+
+    pub fn is_file_desynced(&self, uri: &Uri) -> bool {
+        self.desynced_files.contains(uri)
+    }
+
     pub fn is_open_file(&self, uri: &Uri) -> bool {
         self.open_file_texts.contains_key(uri)
     }
@@ -123,7 +147,13 @@ impl WorkspaceManager {
         let Some(config_root) = self.config_root() else {
             return;
         };
-        if config_path.parent() != Some(config_root.as_path()) {
+        // configs of other directories in the workspace apply to the files below them
+        let is_workspace_config = config_path.parent().is_some_and(|dir| {
+            self.workspace_folders
+                .iter()
+                .any(|folder| dir.starts_with(&folder.root))
+        });
+        if !is_workspace_config {
             return;
         }
 
@@ -160,6 +190,65 @@ impl WorkspaceManager {
         );
     }
 
+    /// Adds and removes workspace folders at runtime. Only the files of the changed folders
+    /// and the files requiring them are re-indexed, unless the first folder, which holds the
+    /// configuration, changes.
+    pub fn change_workspace_folders(
+        &mut self,
+        context: ServerContextSnapshot,
+        added: Vec<PathBuf>,
+        removed: Vec<PathBuf>,
+    ) {
+        let old_config_root = self.config_root();
+        let removed = removed
+            .into_iter()
+            .filter(|root| {
+                self.workspace_folders
+                    .iter()
+                    .any(|folder| folder.root == *root)
+            })
+            .collect::<Vec<_>>();
+        self.workspace_folders
+            .retain(|folder| !removed.contains(&folder.root));
+        let added = added
+            .into_iter()
+            .filter(|root| {
+                !self
+                    .workspace_folders
+                    .iter()
+                    .any(|folder| folder.root == *root)
+            })
+            .collect::<Vec<_>>();
+        self.workspace_folders.extend(
+            added
+                .iter()
+                .map(|root| WorkspaceFolder::new(root.clone(), false)),
+        );
+        log::info!("workspace folders changed: {:?}", self.workspace_folders);
+
+        if self.config_root() != old_config_root {
+            self.add_reload_workspace_task(context);
+            return;
+        }
+        if added.is_empty() && removed.is_empty() {
+            return;
+        }
+
+        let handles = self.reload_task_handles();
+        let client_config = self.client_config.clone();
+        tokio::spawn(async move {
+            let _reload_guard = handles.reload_lock.lock().await;
+            apply_workspace_folders_change(context, client_config, added, removed).await;
+            refresh_workspace_diagnostics(
+                handles.file_diagnostic,
+                handles.lsp_features,
+                handles.client,
+                handles.workspace_diagnostic_level,
+            )
+            .await;
+        });
+    }
+
     pub fn extend_reindex_delay(&self) {
         if let Some(token) = self.reindex_token.current() {
             token.set_resleep();
@@ -237,7 +326,6 @@ impl WorkspaceManager {
     }
 }
 
-const CONFIG_FILE_NAMES: [&str; 3] = [".luarc.json", ".emmyrc.json", ".emmyrc.lua"];
 const CONFIG_RELOAD_DELAY: Duration = Duration::from_secs(2);
 
 pub fn load_emmy_config(config_root: Option<PathBuf>, client_config: ClientConfig) -> Arc<Emmyrc> {
@@ -269,6 +357,25 @@ pub fn load_emmy_config(config_root: Option<PathBuf>, client_config: ClientConfi
     emmyrc.into()
 }
 
+/// Loads the configs of the directories of the workspace that have their own config file,
+/// see `find_config_scope_dirs`.
+pub fn load_scoped_emmy_configs(
+    workspace_folders: &[WorkspaceFolder],
+    emmyrc: &Emmyrc,
+    config_root: Option<&Path>,
+    client_config: &ClientConfig,
+) -> Vec<(PathBuf, Arc<Emmyrc>)> {
+    let workspace_folders = build_workspace_folders(workspace_folders, emmyrc);
+    find_config_scope_dirs(&workspace_folders, emmyrc, config_root)
+        .into_iter()
+        .map(|dir| {
+            log::info!("load scoped config for: {:?}", dir);
+            let scoped_emmyrc = load_emmy_config(Some(dir.clone()), client_config.clone());
+            (dir, scoped_emmyrc)
+        })
+        .collect()
+}
+
 fn merge_client_config(client_config: ClientConfig, emmyrc: &mut Emmyrc) -> Option<()> {
     emmyrc.runtime.extensions.extend(client_config.extensions);
     emmyrc.workspace.ignore_globs.extend(client_config.exclude);
@@ -427,10 +534,19 @@ async fn apply_workspace_reload(
     workspace_folders: Vec<WorkspaceFolder>,
     emmyrc: Arc<Emmyrc>,
 ) {
-    let open_files = {
+    let (open_files, scoped_emmyrcs) = {
         let mut workspace_manager = context.workspace_manager().write().await;
         workspace_manager.update_match_state(emmyrc.as_ref());
-        workspace_manager.workspace_open_files_snapshot()
+        let scoped_emmyrcs = load_scoped_emmy_configs(
+            &workspace_folders,
+            &emmyrc,
+            workspace_manager.config_root().as_deref(),
+            &workspace_manager.client_config,
+        );
+        (
+            workspace_manager.workspace_open_files_snapshot(),
+            scoped_emmyrcs,
+        )
     };
 
     {
@@ -445,6 +561,7 @@ async fn apply_workspace_reload(
         context.lsp_features(),
         workspace_folders,
         emmyrc,
+        scoped_emmyrcs,
         open_files.files.clone(),
     )
     .await;
@@ -453,6 +570,88 @@ async fn apply_workspace_reload(
     register_files_watch(context).await;
 }
 
+async fn apply_workspace_folders_change(
+    context: ServerContextSnapshot,
+    client_config: ClientConfig,
+    added: Vec<PathBuf>,
+    removed: Vec<PathBuf>,
+) {
+    let emmyrc = context.analysis().read().await.get_emmyrc();
+    let open_files = {
+        let mut workspace_manager = context.workspace_manager().write().await;
+        workspace_manager.update_match_state(emmyrc.as_ref());
+        workspace_manager.workspace_open_files()
+    };
+
+    let mut removed_uris = Vec::new();
+    {
+        let mut analysis = context.analysis().write().await;
+        for root in &removed {
+            log::info!("remove workspace root: {:?}", root);
+            removed_uris.extend(analysis.remove_workspace_folder(root));
+        }
+
+        // directories of the added folders with a config file, the folders themselves included
+        let mut scoped_emmyrcs = analysis.get_scoped_configs();
+        let mut added_folders = Vec::new();
+        for root in &added {
+            let folder_emmyrc = load_emmy_config(Some(root.clone()), client_config.clone());
+            let folder_scoped_emmyrcs = load_scoped_emmy_configs(
+                &[WorkspaceFolder::new(root.clone(), false)],
+                &folder_emmyrc,
+                None,
+                &client_config,
+            );
+            let mut workspace_folders = build_workspace_folders(
+                &[WorkspaceFolder::new(root.clone(), false)],
+                folder_emmyrc.as_ref(),
+            );
+            extend_scoped_library_folders(&mut workspace_folders, &folder_scoped_emmyrcs);
+            scoped_emmyrcs.extend(folder_scoped_emmyrcs);
+            added_folders.push((root, folder_emmyrc, workspace_folders));
+        }
+        analysis.update_scoped_configs(scoped_emmyrcs);
+
+        for (root, folder_emmyrc, workspace_folders) in added_folders {
+            log::info!("add workspace root: {:?}", root);
+            for workspace in &workspace_folders {
+                if workspace.is_library {
+                    analysis.add_library_workspace(workspace);
+                } else {
+                    analysis.add_main_workspace(workspace.root.clone());
+                }
+            }
+
+            let files = collect_workspace_files(&workspace_folders, &folder_emmyrc, None, None)
+                .into_iter()
+                .map(|file| file.into_tuple())
+                .collect();
+            analysis.add_workspace_files(files);
+        }
+
+        // the editor content of open files wins over the content on disk
+        let open_files = open_files
+            .into_iter()
+            .filter(|(uri, _)| {
+                uri_to_file_path(uri)
+                    .is_some_and(|path| added.iter().any(|root| path.starts_with(root)))
+            })
+            .map(|(uri, text)| (uri, Some(text)))
+            .collect::<Vec<_>>();
+        if !open_files.is_empty() {
+            analysis.update_files_by_uri(open_files);
+        }
+    }
+
+    if !context.lsp_features().supports_pull_diagnostic() {
+        for uri in removed_uris {
+            context.file_diagnostic().clear_push_file_diagnostics(uri);
+        }
+    }
+
+    register_files_watch(context).await;
+}
+
 async fn sync_reloaded_open_files(
     context: ServerContextSnapshot,
     mut applied_snapshot: OpenFilesSnapshot,
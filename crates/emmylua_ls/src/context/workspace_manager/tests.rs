@@ -1,6 +1,8 @@
 use super::*;
+use crate::cmd_args::{CmdArgs, CmdBool, Communication, LogLevel, NoneableString};
 use crate::context::{ServerContext, ServerContextSnapshot};
-use emmylua_code_analysis::{Emmyrc, file_path_to_uri};
+use crate::handlers::init_std_lib;
+use emmylua_code_analysis::{Emmyrc, FileId, file_path_to_uri};
 use lsp_server::{Connection, Message};
 use lsp_types::{
     ClientCapabilities, DidChangeWatchedFilesClientCapabilities, PublishDiagnosticsParams,
@@ -147,6 +149,20 @@ async fn file_text(snapshot: &ServerContextSnapshot, uri: &Uri) -> Option<String
         .map(|text| text.to_string())
 }
 
+async fn required_files(snapshot: &ServerContextSnapshot, uri: &Uri) -> HashSet<FileId> {
+    let analysis = snapshot.analysis().read().await;
+    let Some(file_id) = analysis.get_file_id(uri) else {
+        return HashSet::new();
+    };
+    analysis
+        .compilation
+        .get_db()
+        .get_file_dependencies_index()
+        .get_required_files(&file_id)
+        .map(|files| files.iter().copied().collect())
+        .unwrap_or_default()
+}
+
 // Run the same initialization path that startup uses for a single workspace root.
 #[allow(dead_code)]
 async fn run_init_analysis(snapshot: &ServerContextSnapshot, workspace_root: PathBuf) {
@@ -158,6 +174,7 @@ async fn run_init_analysis(snapshot: &ServerContextSnapshot, workspace_root: Pat
         vec![WorkspaceFolder::new(workspace_root, false)],
         Arc::new(Emmyrc::default()),
         Vec::new(),
+        Vec::new(),
     )
     .await;
 }
@@ -442,3 +459,190 @@ async fn apply_workspace_reload_preserves_unsaved_open_text_across_membership_ch
         .await;
     context.close().await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn workspace_folder_change_adds_and_removes_folder_with_its_libraries() {
+    let workspace = TestWorkspace::new();
+    let added_folder = TestWorkspace::new();
+    let external_library = TestWorkspace::new();
+    let main_file =
+        workspace.write_file_with_contents("main.lua", "local util = require(\"util\")\n");
+    let main_uri = file_path_to_uri(&main_file).unwrap();
+    let util_uri = file_path_to_uri(&added_folder.write_file("util.lua")).unwrap();
+    let library_uri = file_path_to_uri(&external_library.write_file("lib.lua")).unwrap();
+    added_folder.write_file_with_contents(
+        ".emmyrc.json",
+        &format!(
+            r#"{{
+                "workspace": {{
+                    "library": [{}]
+                }}
+            }}"#,
+            json_string(&to_string(&external_library.root)),
+        ),
+    );
+    let (server, _client) = Connection::memory();
+    let context = ServerContext::new(server, ClientCapabilities::default());
+    let snapshot = context.snapshot();
+    let workspace_folders = vec![WorkspaceFolder::new(workspace.root.clone(), false)];
+
+    {
+        let mut workspace_manager = snapshot.workspace_manager().write().await;
+        workspace_manager.workspace_folders = workspace_folders.clone();
+    }
+    apply_workspace_reload(
+        snapshot.clone(),
+        workspace_folders,
+        Arc::new(Emmyrc::default()),
+    )
+    .await;
+
+    assert!(required_files(&snapshot, &main_uri).await.is_empty());
+
+    snapshot
+        .workspace_manager()
+        .write()
+        .await
+        .workspace_folders
+        .push(WorkspaceFolder::new(added_folder.root.clone(), false));
+    apply_workspace_folders_change(
+        snapshot.clone(),
+        ClientConfig::default(),
+        vec![added_folder.root.clone()],
+        Vec::new(),
+    )
+    .await;
+
+    let util_file_id = snapshot
+        .analysis()
+        .read()
+        .await
+        .get_file_id(&util_uri)
+        .unwrap();
+    assert!(file_text(&snapshot, &library_uri).await.is_some());
+    assert!(
+        required_files(&snapshot, &main_uri)
+            .await
+            .contains(&util_file_id)
+    );
+
+    snapshot
+        .workspace_manager()
+        .write()
+        .await
+        .workspace_folders
+        .retain(|folder| folder.root != added_folder.root);
+    apply_workspace_folders_change(
+        snapshot.clone(),
+        ClientConfig::default(),
+        Vec::new(),
+        vec![added_folder.root.clone()],
+    )
+    .await;
+
+    assert!(file_text(&snapshot, &util_uri).await.is_none());
+    assert!(file_text(&snapshot, &library_uri).await.is_none());
+    assert!(file_text(&snapshot, &main_uri).await.is_some());
+    assert!(required_files(&snapshot, &main_uri).await.is_empty());
+    assert!(
+        snapshot
+            .analysis()
+            .read()
+            .await
+            .get_scoped_configs()
+            .is_empty()
+    );
+
+    snapshot
+        .file_diagnostic()
+        .cancel_workspace_diagnostic()
+        .await;
+    context.close().await;
+}
+
+async fn diagnostic_codes(snapshot: &ServerContextSnapshot, uri: &Uri) -> Vec<String> {
+    let analysis = snapshot.analysis().read().await;
+    let file_id = analysis.get_file_id(uri).unwrap();
+    analysis
+        .diagnose_file(file_id, CancellationToken::new())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|diagnostic| match diagnostic.code {
+            Some(lsp_types::NumberOrString::String(code)) => Some(code),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn workspace_folders_with_conflicting_configs_use_their_own_config() {
+    let server_folder = TestWorkspace::new();
+    let client_folder = TestWorkspace::new();
+    server_folder
+        .write_file_with_contents(".emmyrc.json", r#"{ "runtime": { "version": "Lua5.4" } }"#);
+    client_folder.write_file_with_contents(
+        ".emmyrc.json",
+        r#"{
+            "runtime": { "version": "LuaJIT" },
+            "diagnostics": { "disable": ["unused"] }
+        }"#,
+    );
+    let code = "local version = jit.version\n";
+    let server_uri =
+        file_path_to_uri(&server_folder.write_file_with_contents("main.lua", code)).unwrap();
+    let client_uri =
+        file_path_to_uri(&client_folder.write_file_with_contents("main.lua", code)).unwrap();
+    let (server, _client) = Connection::memory();
+    let context = ServerContext::new(server, ClientCapabilities::default());
+    let snapshot = context.snapshot();
+    let workspace_folders = vec![
+        WorkspaceFolder::new(server_folder.root.clone(), false),
+        WorkspaceFolder::new(client_folder.root.clone(), false),
+    ];
+
+    {
+        let mut workspace_manager = snapshot.workspace_manager().write().await;
+        workspace_manager.workspace_folders = workspace_folders.clone();
+    }
+    let client_config = ClientConfig::default();
+    let emmyrc = load_emmy_config(Some(server_folder.root.clone()), client_config.clone());
+    let scoped_emmyrcs = load_scoped_emmy_configs(
+        &workspace_folders,
+        &emmyrc,
+        Some(&server_folder.root),
+        &client_config,
+    );
+    let cmd_args = CmdArgs {
+        communication: Communication::Stdio,
+        ip: String::new(),
+        port: 0,
+        log_level: LogLevel::Info,
+        log_path: NoneableString(None),
+        resources_path: NoneableString(None),
+        load_stdlib: CmdBool(true),
+        editor: None,
+    };
+    init_std_lib(
+        snapshot.analysis(),
+        &cmd_args,
+        emmyrc.clone(),
+        scoped_emmyrcs,
+    )
+    .await;
+    apply_workspace_reload(snapshot.clone(), workspace_folders, emmyrc).await;
+
+    // the LuaJIT std modules are loaded for the LuaJIT folder only
+    let server_codes = diagnostic_codes(&snapshot, &server_uri).await;
+    assert!(server_codes.contains(&"access-invisible".to_string()));
+    assert!(server_codes.contains(&"unused".to_string()));
+    let client_codes = diagnostic_codes(&snapshot, &client_uri).await;
+    assert!(!client_codes.contains(&"access-invisible".to_string()));
+    assert!(!client_codes.contains(&"undefined-global".to_string()));
+    assert!(!client_codes.contains(&"unused".to_string()));
+
+    snapshot
+        .file_diagnostic()
+        .cancel_workspace_diagnostic()
+        .await;
+    context.close().await;
+}
@@ -0,0 +1,409 @@
+use std::collections::{HashMap, hash_map::Entry};
+
+use emmylua_code_analysis::{
+    EmmyLuaAnalysis, FileId, LuaSemanticDeclId, SemanticDeclLevel, SemanticModel,
+};
+use emmylua_parser::{
+    LuaAstNode, LuaAstToken, LuaCallExpr, LuaCommentOwner, LuaDocTag, LuaExpr, LuaFuncStat,
+    LuaIndexExpr, LuaIndexKey, LuaVarExpr,
+};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Location, Position, Range, TextEdit, Uri,
+    WorkspaceEdit,
+};
+use rowan::{TextRange, TextSize};
+
+use crate::handlers::command::make_convert_method_command;
+
+pub fn build_convert_method(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+) -> Option<()> {
+    let document = semantic_model.get_document();
+    let offset = document.get_offset(range.start.line as usize, range.start.character as usize)?;
+    let method = find_method(semantic_model, offset)?;
+    let name = method.index_expr.get_index_name_token()?;
+
+    let title = if method.is_colon {
+        t!("Convert '%{name}' to dot method", name = name.text())
+    } else {
+        t!("Convert '%{name}' to colon method", name = name.text())
+    }
+    .to_string();
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: title.clone(),
+        kind: Some(CodeActionKind::REFACTOR_REWRITE),
+        command: Some(make_convert_method_command(
+            &title,
+            document.get_uri(),
+            range.start,
+        )),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+#[derive(Debug)]
+pub struct MethodConversion {
+    pub edit: WorkspaceEdit,
+    /// Call sites left untouched because their receiver would be evaluated a different number
+    /// of times after the conversion.
+    pub flagged: Vec<Location>,
+}
+
+/// Converts the method defined at `position` between `function A.f(self)` and `function A:f()`
+/// and rewrites its call sites between `obj.f(obj)` and `obj:f()` accordingly.
+pub fn convert_method(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    position: Position,
+) -> Result<MethodConversion, String> {
+    let compilation = &analysis.compilation;
+    let semantic_model = compilation
+        .get_semantic_model(file_id)
+        .ok_or("file not found")?;
+    let document = semantic_model.get_document();
+    let offset = document
+        .get_offset(position.line as usize, position.character as usize)
+        .ok_or("invalid position")?;
+    let method = find_method(&semantic_model, offset).ok_or("no method definition found")?;
+    let name_token = method
+        .index_expr
+        .get_index_name_token()
+        .ok_or("no method definition found")?;
+    let Some(LuaSemanticDeclId::Member(member_id)) =
+        semantic_model.find_decl(name_token.into(), SemanticDeclLevel::default())
+    else {
+        return Err("the method is not a member".to_string());
+    };
+
+    let mut changes: HashMap<Uri, Vec<TextEdit>> = HashMap::new();
+    let mut flagged = Vec::new();
+    let definition_edits = get_definition_edits(&semantic_model, &method);
+    changes
+        .entry(document.get_uri())
+        .or_default()
+        .extend(definition_edits);
+
+    let db = compilation.get_db();
+    let member = db
+        .get_member_index()
+        .get_member(&member_id)
+        .ok_or("member not found")?;
+    let index_references = db
+        .get_reference_index()
+        .get_index_references(member.get_key())
+        .unwrap_or_default();
+    let semantic_id = LuaSemanticDeclId::Member(member_id);
+    let mut semantic_models = HashMap::new();
+    for in_filed_syntax_id in index_references {
+        let reference_file_id = in_filed_syntax_id.file_id;
+        if let Entry::Vacant(entry) = semantic_models.entry(reference_file_id) {
+            let Some(reference_model) = compilation.get_semantic_model(reference_file_id) else {
+                continue;
+            };
+            entry.insert(reference_model);
+        }
+        let reference_model = &semantic_models[&reference_file_id];
+        let root = reference_model.get_root();
+        let Some(node) = in_filed_syntax_id.value.to_node_from_root(root.syntax()) else {
+            continue;
+        };
+        let Some(index_expr) = LuaIndexExpr::cast(node.clone()) else {
+            continue;
+        };
+        let Some(call_expr) = index_expr.get_parent::<LuaCallExpr>() else {
+            continue;
+        };
+        if call_expr
+            .get_prefix_expr()
+            .is_none_or(|prefix| prefix.syntax() != index_expr.syntax())
+            || !reference_model.is_reference_to(
+                node,
+                semantic_id.clone(),
+                SemanticDeclLevel::default(),
+            )
+        {
+            continue;
+        }
+
+        let reference_document = reference_model.get_document();
+        let call_site = if method.is_colon {
+            convert_call_to_dot(&call_expr, &index_expr)
+        } else {
+            convert_call_to_colon(&call_expr, &index_expr)
+        };
+        match call_site {
+            CallSite::Unchanged => {}
+            CallSite::Converted(edits) => {
+                let edits = edits
+                    .into_iter()
+                    .filter_map(|(range, new_text)| {
+                        Some(TextEdit {
+                            range: reference_document.to_lsp_range(range)?,
+                            new_text,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                changes
+                    .entry(reference_document.get_uri())
+                    .or_default()
+                    .extend(edits);
+            }
+            CallSite::Flagged => {
+                if let Some(location) = reference_document.to_lsp_location(call_expr.get_range()) {
+                    flagged.push(location);
+                }
+            }
+        }
+    }
+    flagged.sort_by(|a, b| (a.uri.as_str(), a.range.start).cmp(&(b.uri.as_str(), b.range.start)));
+
+    Ok(MethodConversion {
+        edit: WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        },
+        flagged,
+    })
+}
+
+struct MethodDefinition {
+    func_stat: LuaFuncStat,
+    index_expr: LuaIndexExpr,
+    is_colon: bool,
+}
+
+/// Finds a `function A.f(self, ...)` or `function A:f(...)` statement whose header contains
+/// `offset`.
+fn find_method(semantic_model: &SemanticModel, offset: TextSize) -> Option<MethodDefinition> {
+    let root = semantic_model.get_root();
+    let token = root.syntax().token_at_offset(offset).left_biased()?;
+    let func_stat = token.parent_ancestors().find_map(LuaFuncStat::cast)?;
+    let LuaVarExpr::IndexExpr(index_expr) = func_stat.get_func_name()? else {
+        return None;
+    };
+    let params_list = func_stat.get_closure()?.get_params_list()?;
+    let header = TextRange::new(func_stat.get_range().start(), params_list.get_range().end());
+    if !header.contains_inclusive(offset) {
+        return None;
+    }
+
+    let index_token = index_expr.get_index_token()?;
+    let is_colon = if index_token.is_colon() {
+        true
+    } else if index_token.is_dot() {
+        let first_param = params_list.get_params().next()?;
+        if first_param.get_name_token()?.get_name_text() != "self" {
+            return None;
+        }
+        false
+    } else {
+        return None;
+    };
+
+    Some(MethodDefinition {
+        func_stat,
+        index_expr,
+        is_colon,
+    })
+}
+
+fn get_definition_edits(
+    semantic_model: &SemanticModel,
+    method: &MethodDefinition,
+) -> Vec<TextEdit> {
+    let document = semantic_model.get_document();
+    let mut edits = Vec::new();
+    let Some(index_token) = method.index_expr.get_index_token() else {
+        return Vec::new();
+    };
+    let Some(params_list) = method
+        .func_stat
+        .get_closure()
+        .and_then(|closure| closure.get_params_list())
+    else {
+        return Vec::new();
+    };
+    let params = params_list.get_params().collect::<Vec<_>>();
+
+    if method.is_colon {
+        edits.push((index_token.get_range(), ".".to_string()));
+        match params.first() {
+            Some(first_param) => edits.push((
+                TextRange::empty(first_param.get_range().start()),
+                "self, ".to_string(),
+            )),
+            None => edits.push((
+                TextRange::empty(params_list.get_range().start() + TextSize::from(1)),
+                "self".to_string(),
+            )),
+        }
+    } else {
+        edits.push((index_token.get_range(), ":".to_string()));
+        let self_range = match params.get(1) {
+            Some(second_param) => TextRange::new(
+                params[0].get_range().start(),
+                second_param.get_range().start(),
+            ),
+            None => params[0].get_range(),
+        };
+        edits.push((self_range, String::new()));
+
+        // `self` is implicit for colon methods, drop its `---@param` tag
+        let text = document.get_text();
+        for comment in method.func_stat.get_comments() {
+            for tag in comment.get_doc_tags() {
+                let LuaDocTag::Param(param_tag) = tag else {
+                    continue;
+                };
+                if param_tag
+                    .get_name_token()
+                    .is_none_or(|name| name.get_name_text() != "self")
+                {
+                    continue;
+                }
+                let range = param_tag.get_range();
+                let line_start = text[..usize::from(range.start())]
+                    .rfind('\n')
+                    .map(|pos| pos + 1)
+                    .unwrap_or(0);
+                let line_end = text[usize::from(range.end())..]
+                    .find('\n')
+                    .map(|pos| usize::from(range.end()) + pos + 1)
+                    .unwrap_or(text.len());
+                edits.push((
+                    TextRange::new(
+                        TextSize::from(line_start as u32),
+                        TextSize::from(line_end as u32),
+                    ),
+                    String::new(),
+                ));
+            }
+        }
+    }
+
+    edits
+        .into_iter()
+        .filter_map(|(range, new_text)| {
+            Some(TextEdit {
+                range: document.to_lsp_range(range)?,
+                new_text,
+            })
+        })
+        .collect()
+}
+
+enum CallSite {
+    Unchanged,
+    Converted(Vec<(TextRange, String)>),
+    Flagged,
+}
+
+/// `obj.f(obj, x)` => `obj:f(x)`
+fn convert_call_to_colon(call_expr: &LuaCallExpr, index_expr: &LuaIndexExpr) -> CallSite {
+    let (Some(index_token), Some(prefix), Some(args_list)) = (
+        index_expr.get_index_token(),
+        index_expr.get_prefix_expr(),
+        call_expr.get_args_list(),
+    ) else {
+        return CallSite::Unchanged;
+    };
+    if !index_token.is_dot() || args_list.is_single_arg_no_parens() {
+        return CallSite::Unchanged;
+    }
+    let args = args_list.get_args().collect::<Vec<_>>();
+    // calls passing another receiver, such as `Base.f(self)`, have no colon form
+    let Some(first_arg) = args.first() else {
+        return CallSite::Unchanged;
+    };
+    if get_expr_text(first_arg) != get_expr_text(&prefix) {
+        return CallSite::Unchanged;
+    }
+    if !is_side_effect_free(&prefix) {
+        return CallSite::Flagged;
+    }
+
+    let receiver_range = match args.get(1) {
+        Some(second_arg) => TextRange::new(
+            first_arg.get_range().start(),
+            second_arg.get_range().start(),
+        ),
+        None => first_arg.get_range(),
+    };
+    CallSite::Converted(vec![
+        (index_token.get_range(), ":".to_string()),
+        (receiver_range, String::new()),
+    ])
+}
+
+/// `obj:f(x)` => `obj.f(obj, x)`
+fn convert_call_to_dot(call_expr: &LuaCallExpr, index_expr: &LuaIndexExpr) -> CallSite {
+    let (Some(index_token), Some(prefix), Some(args_list)) = (
+        index_expr.get_index_token(),
+        index_expr.get_prefix_expr(),
+        call_expr.get_args_list(),
+    ) else {
+        return CallSite::Unchanged;
+    };
+    if !index_token.is_colon() {
+        return CallSite::Unchanged;
+    }
+    if !is_side_effect_free(&prefix) {
+        return CallSite::Flagged;
+    }
+
+    let receiver = prefix.syntax().text().to_string();
+    let mut edits = vec![(index_token.get_range(), ".".to_string())];
+    if args_list.is_single_arg_no_parens() {
+        // `obj:f "x"` => `obj.f(obj, "x")`
+        let args_range = TextRange::new(index_expr.get_range().end(), args_list.get_range().end());
+        edits.push((
+            args_range,
+            format!("({}, {})", receiver, args_list.syntax().text()),
+        ));
+    } else {
+        match args_list.get_args().next() {
+            Some(first_arg) => edits.push((
+                TextRange::empty(first_arg.get_range().start()),
+                format!("{}, ", receiver),
+            )),
+            None => edits.push((
+                TextRange::empty(args_list.get_range().start() + TextSize::from(1)),
+                receiver,
+            )),
+        }
+    }
+    CallSite::Converted(edits)
+}
+
+fn get_expr_text(expr: &LuaExpr) -> String {
+    expr.syntax()
+        .text()
+        .to_string()
+        .split_whitespace()
+        .collect()
+}
+
+/// Whether evaluating `expr` twice, or once instead of twice, cannot change the program.
+fn is_side_effect_free(expr: &LuaExpr) -> bool {
+    match expr {
+        LuaExpr::NameExpr(_) | LuaExpr::LiteralExpr(_) => true,
+        LuaExpr::ParenExpr(paren_expr) => paren_expr
+            .get_expr()
+            .is_some_and(|inner| is_side_effect_free(&inner)),
+        LuaExpr::IndexExpr(index_expr) => {
+            index_expr
+                .get_prefix_expr()
+                .is_some_and(|prefix| is_side_effect_free(&prefix))
+                && match index_expr.get_index_key() {
+                    Some(LuaIndexKey::Expr(key)) => is_side_effect_free(&key),
+                    Some(_) => true,
+                    None => false,
+                }
+        }
+        _ => false,
+    }
+}
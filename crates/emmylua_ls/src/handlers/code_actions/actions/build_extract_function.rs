@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+
+use emmylua_code_analysis::{LuaDecl, LuaDeclId, LuaType, SemanticModel};
+use emmylua_parser::{
+    LuaAstNode, LuaAstToken, LuaBlock, LuaClosureExpr, LuaExpr, LuaLocalName, LuaLoopStat,
+    LuaNameExpr, LuaStat, LuaSyntaxKind, LuaSyntaxNode,
+};
+use lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, Range, TextEdit, WorkspaceEdit};
+use rowan::{TextRange, TextSize};
+
+use super::refactor_utils::{
+    find_selected_expr, get_line_indent, get_name_expr_decl, get_stat_start, get_top_level_stat,
+    get_trimmed_selection, get_unique_name, humanize_doc_type, is_decl_write_at,
+    is_in_nested_closure, is_root_block, is_value_expr, is_vararg_expr, reindent,
+};
+
+enum ExtractTarget {
+    Stats(Vec<LuaStat>),
+    Expr(LuaExpr),
+}
+
+impl ExtractTarget {
+    fn nodes(&self) -> Vec<LuaSyntaxNode> {
+        match self {
+            ExtractTarget::Stats(stats) => stats.iter().map(|stat| stat.syntax().clone()).collect(),
+            ExtractTarget::Expr(expr) => vec![expr.syntax().clone()],
+        }
+    }
+}
+
+struct ExtractVariables<'a> {
+    params: Vec<&'a LuaDecl>,
+    /// Outer locals assigned inside the selection and read after it.
+    outer_returns: Vec<&'a LuaDecl>,
+    /// Locals declared by the selection and read after it.
+    inner_returns: Vec<LuaDeclId>,
+}
+
+pub fn build_extract_function(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+) -> Option<()> {
+    let selection = get_trimmed_selection(semantic_model, range)?;
+    if selection.is_empty() {
+        return None;
+    }
+    let target = match find_selected_stats(semantic_model, selection) {
+        Some(stats) => ExtractTarget::Stats(stats),
+        None => {
+            let expr = find_selected_expr(semantic_model, selection)?;
+            if !is_value_expr(&expr) || is_vararg_expr(expr.syntax()) {
+                return None;
+            }
+            ExtractTarget::Expr(expr)
+        }
+    };
+    let nodes = target.nodes();
+    let extract_range = TextRange::new(
+        nodes.first()?.text_range().start(),
+        nodes.last()?.text_range().end(),
+    );
+    if !nodes.iter().all(is_extractable) {
+        return None;
+    }
+
+    let insert_stat = get_top_level_stat(semantic_model, nodes.first()?)?;
+    let insert_offset = get_stat_start(&insert_stat);
+    let variables = collect_variables(semantic_model, &target, extract_range, insert_offset)?;
+
+    let document = semantic_model.get_document();
+    let name = get_unique_name(semantic_model, "new_function");
+    let base_indent = get_line_indent(semantic_model, insert_offset);
+    let body_indent = format!("{}    ", base_indent);
+    let old_indent = get_line_indent(semantic_model, extract_range.start());
+    let body_text = document.get_text_slice(extract_range);
+    let db = semantic_model.get_db();
+
+    let param_names = variables
+        .params
+        .iter()
+        .map(|decl| decl.get_name().to_string())
+        .collect::<Vec<_>>();
+    let mut return_names = variables
+        .outer_returns
+        .iter()
+        .map(|decl| decl.get_name().to_string())
+        .collect::<Vec<_>>();
+    let mut return_types = variables
+        .outer_returns
+        .iter()
+        .map(|decl| semantic_model.get_type(decl.get_id().into()))
+        .collect::<Vec<_>>();
+    let mut inner_names = Vec::new();
+    for decl_id in &variables.inner_returns {
+        let decl = db.get_decl_index().get_decl(decl_id)?;
+        inner_names.push(decl.get_name().to_string());
+        return_types.push(semantic_model.get_type((*decl_id).into()));
+    }
+    return_names.extend(inner_names.iter().cloned());
+
+    let mut body = match &target {
+        ExtractTarget::Stats(_) => reindent(body_text, &old_indent, &body_indent),
+        ExtractTarget::Expr(expr) => {
+            return_types.push(
+                semantic_model
+                    .infer_expr(expr.clone())
+                    .unwrap_or(LuaType::Unknown),
+            );
+            reindent(&format!("return {}", body_text), &old_indent, &body_indent)
+        }
+    };
+    if !return_names.is_empty() {
+        body.push_str(&format!(
+            "\n{}return {}",
+            body_indent,
+            return_names.join(", ")
+        ));
+    }
+
+    let mut lines = Vec::new();
+    for decl in &variables.params {
+        let typ = semantic_model.get_type(decl.get_id().into());
+        lines.push(format!(
+            "---@param {} {}",
+            decl.get_name(),
+            humanize_doc_type(semantic_model, &typ)
+        ));
+    }
+    for typ in &return_types {
+        lines.push(format!(
+            "---@return {}",
+            humanize_doc_type(semantic_model, typ)
+        ));
+    }
+    lines.push(format!(
+        "local function {}({})",
+        name,
+        param_names.join(", ")
+    ));
+    let mut function_text = lines.join(&format!("\n{}", base_indent));
+    function_text.push_str(&format!(
+        "\n{}\n{}end\n\n{}",
+        body, base_indent, base_indent
+    ));
+
+    let call_text = format!("{}({})", name, param_names.join(", "));
+    let call_text = match (variables.outer_returns.is_empty(), inner_names.is_empty()) {
+        (true, true) => call_text,
+        (true, false) => format!("local {} = {}", inner_names.join(", "), call_text),
+        (false, true) => format!("{} = {}", return_names.join(", "), call_text),
+        (false, false) => format!(
+            "local {}\n{}{} = {}",
+            inner_names.join(", "),
+            old_indent,
+            return_names.join(", "),
+            call_text
+        ),
+    };
+
+    let edits = vec![
+        TextEdit {
+            range: document.to_lsp_range(TextRange::empty(insert_offset))?,
+            new_text: function_text,
+        },
+        TextEdit {
+            range: document.to_lsp_range(extract_range)?,
+            new_text: call_text,
+        },
+    ];
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Extract into function").to_string(),
+        kind: Some(CodeActionKind::REFACTOR_EXTRACT),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(document.get_uri(), edits)])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+/// Finds the statements of a single block that are completely covered by the selection.
+fn find_selected_stats(
+    semantic_model: &SemanticModel,
+    selection: TextRange,
+) -> Option<Vec<LuaStat>> {
+    let root = semantic_model.get_root().syntax();
+    if !root.text_range().contains_range(selection) {
+        return None;
+    }
+    let block = match root.covering_element(selection) {
+        rowan::NodeOrToken::Node(node) => node.ancestors().find_map(LuaBlock::cast),
+        rowan::NodeOrToken::Token(token) => token.parent_ancestors().find_map(LuaBlock::cast),
+    }?;
+
+    let stats = block
+        .get_stats()
+        .filter(|stat| {
+            stat.get_range()
+                .intersect(selection)
+                .is_some_and(|it| !it.is_empty())
+        })
+        .collect::<Vec<_>>();
+    if stats.is_empty()
+        || !stats
+            .iter()
+            .all(|stat| selection.contains_range(stat.get_range()))
+    {
+        return None;
+    }
+
+    Some(stats)
+}
+
+/// Control flow leaving the selection can not be moved into a function.
+fn is_extractable(node: &LuaSyntaxNode) -> bool {
+    for descendant in node.descendants() {
+        match descendant.kind().into() {
+            LuaSyntaxKind::ReturnStat | LuaSyntaxKind::GotoStat | LuaSyntaxKind::LabelStat => {
+                if !is_in_nested_closure(&descendant, node) {
+                    return false;
+                }
+            }
+            LuaSyntaxKind::BreakStat | LuaSyntaxKind::ContinueStat => {
+                let owner = descendant.ancestors().find(|ancestor| {
+                    LuaLoopStat::can_cast(ancestor.kind().into())
+                        || LuaClosureExpr::can_cast(ancestor.kind().into())
+                });
+                if !owner.is_some_and(|owner| node.text_range().contains_range(owner.text_range()))
+                {
+                    return false;
+                }
+            }
+            _ => {
+                if is_vararg_expr(&descendant) && !is_in_nested_closure(&descendant, node) {
+                    return false;
+                }
+            }
+        }
+    }
+
+    true
+}
+
+fn collect_variables<'a>(
+    semantic_model: &'a SemanticModel,
+    target: &ExtractTarget,
+    extract_range: TextRange,
+    insert_offset: TextSize,
+) -> Option<ExtractVariables<'a>> {
+    let db = semantic_model.get_db();
+    let file_id = semantic_model.get_file_id();
+    let root = semantic_model.get_root().syntax();
+    let mut params: Vec<&LuaDecl> = Vec::new();
+    let mut written = Vec::new();
+    for node in target.nodes() {
+        let name_exprs = std::iter::once(node.clone())
+            .chain(node.descendants())
+            .filter_map(LuaNameExpr::cast);
+        for name_expr in name_exprs {
+            let Some(decl) = get_name_expr_decl(semantic_model, &name_expr) else {
+                continue;
+            };
+            if !(decl.is_local() || decl.is_implicit_self())
+                || extract_range.contains(decl.get_position())
+            {
+                continue;
+            }
+            // locals of the chunk declared before the new function are its upvalues
+            if decl.get_position() < insert_offset && is_chunk_local(semantic_model, root, decl) {
+                continue;
+            }
+
+            let decl_id = decl.get_id();
+            if is_decl_write_at(semantic_model, &decl_id, name_expr.get_range())
+                && !written.contains(&decl_id)
+            {
+                written.push(decl_id);
+            }
+            if !params.iter().any(|param| param.get_id() == decl_id) {
+                params.push(decl);
+            }
+        }
+    }
+
+    let is_used_outside = |decl_id: &LuaDeclId| {
+        db.get_reference_index()
+            .get_decl_references(&file_id, decl_id)
+            .is_some_and(|decl_ref| {
+                decl_ref
+                    .cells
+                    .iter()
+                    .any(|cell| !extract_range.contains_range(cell.range))
+            })
+    };
+    let outer_returns = params
+        .iter()
+        .filter(|decl| written.contains(&decl.get_id()) && is_used_outside(&decl.get_id()))
+        .copied()
+        .collect();
+
+    let mut inner_returns = Vec::new();
+    if let ExtractTarget::Stats(stats) = target {
+        for stat in stats {
+            let local_names: Vec<LuaLocalName> = match stat {
+                LuaStat::LocalStat(local_stat) => local_stat.get_local_name_list().collect(),
+                LuaStat::LocalFuncStat(local_func_stat) => {
+                    local_func_stat.get_local_name().into_iter().collect()
+                }
+                _ => continue,
+            };
+            for local_name in local_names {
+                let name_token = local_name.get_name_token()?;
+                let decl_id = LuaDeclId::new(file_id, name_token.get_position());
+                if is_used_outside(&decl_id) {
+                    inner_returns.push(decl_id);
+                }
+            }
+        }
+    }
+
+    Some(ExtractVariables {
+        params,
+        outer_returns,
+        inner_returns,
+    })
+}
+
+fn is_chunk_local(semantic_model: &SemanticModel, root: &LuaSyntaxNode, decl: &LuaDecl) -> bool {
+    if !decl.is_local() || decl.is_param() {
+        return false;
+    }
+
+    let element = root.covering_element(decl.get_range());
+    let stat = match element {
+        rowan::NodeOrToken::Node(node) => node.ancestors().find_map(LuaStat::cast),
+        rowan::NodeOrToken::Token(token) => token.parent_ancestors().find_map(LuaStat::cast),
+    };
+    stat.and_then(|stat| stat.get_parent_block())
+        .is_some_and(|block| is_root_block(semantic_model, &block))
+}
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use emmylua_code_analysis::SemanticModel;
+use emmylua_parser::{LuaAstNode, LuaExpr, LuaStat, LuaSyntaxKind};
+use lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, Range, TextEdit, WorkspaceEdit};
+use rowan::TextRange;
+
+use super::refactor_utils::{
+    find_selected_expr, get_line_indent, get_stat_start, get_trimmed_selection, get_unique_name,
+    is_conditionally_evaluated, is_value_expr, is_vararg_expr,
+};
+
+pub fn build_extract_variable(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+) -> Option<()> {
+    let selection = get_trimmed_selection(semantic_model, range)?;
+    let expr = find_selected_expr(semantic_model, selection)?;
+    let stat = expr.syntax().ancestors().find_map(LuaStat::cast)?;
+    if !can_extract_expr(&expr, &stat) {
+        return None;
+    }
+
+    let document = semantic_model.get_document();
+    let name = get_unique_name(semantic_model, "new_var");
+    let insert_offset = get_stat_start(&stat);
+    let indent = get_line_indent(semantic_model, insert_offset);
+    let insert_text = format!("local {} = {}\n{}", name, expr.syntax().text(), indent);
+    let edits = vec![
+        TextEdit {
+            range: document.to_lsp_range(TextRange::empty(insert_offset))?,
+            new_text: insert_text,
+        },
+        TextEdit {
+            range: document.to_lsp_range(expr.get_range())?,
+            new_text: name,
+        },
+    ];
+
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Extract into variable").to_string(),
+        kind: Some(CodeActionKind::REFACTOR_EXTRACT),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(document.get_uri(), edits)])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+fn can_extract_expr(expr: &LuaExpr, stat: &LuaStat) -> bool {
+    if !is_value_expr(expr) || is_vararg_expr(expr.syntax()) {
+        return false;
+    }
+
+    match stat {
+        // the condition is evaluated on every iteration
+        LuaStat::WhileStat(_) | LuaStat::RepeatStat(_) => return false,
+        LuaStat::CallExprStat(call_expr_stat) => {
+            if call_expr_stat
+                .get_call_expr()
+                .is_some_and(|call_expr| call_expr.syntax() == expr.syntax())
+            {
+                return false;
+            }
+        }
+        _ => {}
+    }
+
+    if expr
+        .syntax()
+        .ancestors()
+        .take_while(|node| node != stat.syntax())
+        .any(|node| node.kind() == LuaSyntaxKind::ElseIfClauseStat.into())
+    {
+        return false;
+    }
+
+    !is_conditionally_evaluated(expr.syntax(), stat.syntax())
+}
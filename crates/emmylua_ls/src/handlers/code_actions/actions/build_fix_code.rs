@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use crate::handlers::command::make_auto_doc_tag_command;
 use emmylua_code_analysis::SemanticModel;
-use emmylua_parser::{LuaAstNode, LuaExpr};
+use emmylua_parser::{LuaAstNode, LuaExpr, LuaIfStat, LuaTableExpr, LuaTokenKind};
 use lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, Range, TextEdit, WorkspaceEdit};
 use rowan::{NodeOrToken, TokenAtOffset};
 
@@ -101,3 +101,169 @@ pub fn build_preferred_local_alias_fix(
 
     Some(())
 }
+
+pub fn build_add_missing_branches(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+    data: &Option<serde_json::Value>,
+) -> Option<()> {
+    let data = data.as_ref()?;
+    let subject = data.get("subject")?.as_str()?;
+    let missing = data
+        .get("missing")?
+        .as_array()?
+        .iter()
+        .filter_map(|value| value.as_str())
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return None;
+    }
+
+    let document = semantic_model.get_document();
+    let offset = document.get_offset(range.start.line as usize, range.start.character as usize)?;
+    let root = semantic_model.get_root();
+    let token = match root.syntax().token_at_offset(offset) {
+        TokenAtOffset::Single(token) => token,
+        TokenAtOffset::Between(_, token) => token,
+        _ => return None,
+    };
+    let if_stat = token.parent_ancestors().find_map(LuaIfStat::cast)?;
+    let end_token = if_stat.syntax().last_token()?;
+    if end_token.kind() != LuaTokenKind::TkEnd.into() {
+        return None;
+    }
+
+    // `end` 所在行的缩进
+    let indent = match end_token.prev_token() {
+        Some(prev) if prev.kind() == LuaTokenKind::TkWhitespace.into() => prev.text().to_string(),
+        _ => String::new(),
+    };
+    let new_text = missing
+        .iter()
+        .map(|value| format!("elseif {} == {} then\n{}", subject, value, indent))
+        .collect::<String>();
+    let end_position = document.to_lsp_position(end_token.text_range().start())?;
+    let text_edit = TextEdit {
+        range: Range {
+            start: end_position,
+            end: end_position,
+        },
+        new_text,
+    };
+
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Add missing branches").to_string(),
+        kind: Some(CodeActionKind::QUICKFIX),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(document.get_uri(), vec![text_edit])])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+/// 在分派表的最后一个字段之后补上缺少的键, 值使用诊断给出的空实现
+pub fn build_add_missing_table_keys(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+    data: &Option<serde_json::Value>,
+) -> Option<()> {
+    let data = data.as_ref()?;
+    let value = data.get("value")?.as_str()?;
+    let missing = data
+        .get("missing")?
+        .as_array()?
+        .iter()
+        .filter_map(|value| value.as_str())
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return None;
+    }
+
+    let document = semantic_model.get_document();
+    let offset = document.get_offset(range.start.line as usize, range.start.character as usize)?;
+    let root = semantic_model.get_root();
+    let token = match root.syntax().token_at_offset(offset) {
+        TokenAtOffset::Single(token) => token,
+        TokenAtOffset::Between(_, token) => token,
+        _ => return None,
+    };
+    let table_expr = token.parent_ancestors().find_map(LuaTableExpr::cast)?;
+    let last_field = table_expr.get_fields().last()?;
+
+    // 最后一个字段之后是否已经有分隔符
+    let separator = last_field
+        .syntax()
+        .siblings_with_tokens(rowan::Direction::Next)
+        .skip(1)
+        .find(|element| {
+            !matches!(
+                element.kind().into(),
+                LuaTokenKind::TkWhitespace | LuaTokenKind::TkEndOfLine
+            )
+        })
+        .filter(|element| {
+            matches!(
+                element.kind().into(),
+                LuaTokenKind::TkComma | LuaTokenKind::TkSemicolon
+            )
+        });
+    let insert_offset = match &separator {
+        Some(separator) => separator.text_range().end(),
+        None => last_field.syntax().text_range().end(),
+    };
+
+    // 多行的表沿用最后一个字段的缩进, 每个键单独一行
+    let (is_multiline, indent) = match last_field.syntax().prev_sibling_or_token() {
+        Some(prev) if prev.kind() == LuaTokenKind::TkEndOfLine.into() => (true, String::new()),
+        Some(NodeOrToken::Token(prev)) if prev.kind() == LuaTokenKind::TkWhitespace.into() => {
+            let after_line_break = prev
+                .prev_token()
+                .is_some_and(|token| token.kind() == LuaTokenKind::TkEndOfLine.into());
+            (after_line_break, prev.text().to_string())
+        }
+        _ => (false, String::new()),
+    };
+    let mut new_text = String::new();
+    if is_multiline {
+        if separator.is_none() {
+            new_text.push(',');
+        }
+        for key in &missing {
+            new_text.push_str(&format!("\n{}[{}] = {},", indent, key, value));
+        }
+    } else {
+        for key in &missing {
+            if separator.is_some() {
+                new_text.push_str(&format!(" [{}] = {},", key, value));
+            } else {
+                new_text.push_str(&format!(", [{}] = {}", key, value));
+            }
+        }
+    }
+
+    let insert_position = document.to_lsp_position(insert_offset)?;
+    let text_edit = TextEdit {
+        range: Range {
+            start: insert_position,
+            end: insert_position,
+        },
+        new_text,
+    };
+
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Add missing keys").to_string(),
+        kind: Some(CodeActionKind::QUICKFIX),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(document.get_uri(), vec![text_edit])])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }));
+
+    Some(())
+}
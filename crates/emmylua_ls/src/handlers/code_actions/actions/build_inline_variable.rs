@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+
+use emmylua_code_analysis::{LuaDeclId, SemanticModel};
+use emmylua_parser::{
+    LuaAstNode, LuaAstToken, LuaCallExpr, LuaExpr, LuaIndexExpr, LuaLocalName, LuaLocalStat,
+    LuaNameExpr, LuaStat, LuaSyntaxKind, LuaSyntaxNode, LuaTokenKind,
+};
+use lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, Range, TextEdit, WorkspaceEdit};
+use rowan::{TextRange, TokenAtOffset};
+
+use super::refactor_utils::{
+    get_name_expr_decl, get_stat_delete_range, get_trimmed_selection, is_conditionally_evaluated,
+    is_in_nested_closure, is_vararg_expr,
+};
+
+pub fn build_inline_variable(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+) -> Option<()> {
+    let selection = get_trimmed_selection(semantic_model, range)?;
+    let decl_id = find_local_decl_at(semantic_model, selection)?;
+    let db = semantic_model.get_db();
+    let decl = db.get_decl_index().get_decl(&decl_id)?;
+    if !decl.is_local() || decl.is_param() {
+        return None;
+    }
+
+    let root = semantic_model.get_root().syntax();
+    let local_name = root
+        .covering_element(decl.get_range())
+        .ancestors()
+        .find_map(LuaLocalName::cast)?;
+    if local_name
+        .get_attrib()
+        .is_some_and(|attrib| attrib.is_close())
+    {
+        return None;
+    }
+    let local_stat = local_name.get_parent::<LuaLocalStat>()?;
+    if local_stat.get_local_name_list().count() != 1 {
+        return None;
+    }
+    let mut value_exprs = local_stat.get_value_exprs();
+    let value_expr = value_exprs.next()?;
+    if value_exprs.next().is_some() || is_vararg_expr(value_expr.syntax()) {
+        return None;
+    }
+
+    let file_id = semantic_model.get_file_id();
+    let decl_ref = db
+        .get_reference_index()
+        .get_decl_references(&file_id, &decl_id)?;
+    if decl_ref.mutable || decl_ref.cells.is_empty() {
+        return None;
+    }
+    let ref_nodes = decl_ref
+        .cells
+        .iter()
+        .map(|cell| {
+            root.covering_element(cell.range)
+                .ancestors()
+                .find_map(LuaNameExpr::cast)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    if !is_pure_expr(semantic_model, &value_expr)
+        && !can_move_expr(&local_stat, &value_expr, &ref_nodes)
+    {
+        return None;
+    }
+    if is_shadowed(semantic_model, &value_expr, &ref_nodes) {
+        return None;
+    }
+
+    let document = semantic_model.get_document();
+    let value_text = value_expr.syntax().text().to_string();
+    let mut edits = Vec::new();
+    for ref_node in &ref_nodes {
+        let new_text = if need_paren(&value_expr, ref_node) {
+            format!("({})", value_text)
+        } else {
+            value_text.clone()
+        };
+        edits.push(TextEdit {
+            range: document.to_lsp_range(ref_node.get_range())?,
+            new_text,
+        });
+    }
+    let stat = LuaStat::LocalStat(local_stat);
+    edits.push(TextEdit {
+        range: document.to_lsp_range(get_stat_delete_range(semantic_model, &stat))?,
+        new_text: String::new(),
+    });
+
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Inline variable '%{name}'", name = decl.get_name()).to_string(),
+        kind: Some(CodeActionKind::REFACTOR_INLINE),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(document.get_uri(), edits)])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+fn find_local_decl_at(semantic_model: &SemanticModel, selection: TextRange) -> Option<LuaDeclId> {
+    let root = semantic_model.get_root().syntax();
+    let token = match root.token_at_offset(selection.start()) {
+        TokenAtOffset::Single(token) => token,
+        TokenAtOffset::Between(left, right) => {
+            if left.kind() == LuaTokenKind::TkName.into() {
+                left
+            } else {
+                right
+            }
+        }
+        TokenAtOffset::None => return None,
+    };
+    let parent = token.parent()?;
+    if let Some(local_name) = LuaLocalName::cast(parent.clone()) {
+        let name_token = local_name.get_name_token()?;
+        return Some(LuaDeclId::new(
+            semantic_model.get_file_id(),
+            name_token.get_position(),
+        ));
+    }
+
+    let name_expr = LuaNameExpr::cast(parent)?;
+    get_name_expr_decl(semantic_model, &name_expr).map(|decl| decl.get_id())
+}
+
+/// Expressions without side effects whose value can not change between the declaration and
+/// the references.
+fn is_pure_expr(semantic_model: &SemanticModel, expr: &LuaExpr) -> bool {
+    match expr {
+        LuaExpr::LiteralExpr(_) => !is_vararg_expr(expr.syntax()),
+        LuaExpr::NameExpr(name_expr) => {
+            let Some(decl) = get_name_expr_decl(semantic_model, name_expr) else {
+                return false;
+            };
+            if !decl.is_local() {
+                return false;
+            }
+            semantic_model
+                .get_db()
+                .get_reference_index()
+                .get_decl_references(&semantic_model.get_file_id(), &decl.get_id())
+                .is_some_and(|decl_ref| !decl_ref.mutable)
+        }
+        LuaExpr::ParenExpr(paren_expr) => paren_expr
+            .get_expr()
+            .is_some_and(|inner| is_pure_expr(semantic_model, &inner)),
+        LuaExpr::BinaryExpr(binary_expr) => binary_expr.get_exprs().is_some_and(|(left, right)| {
+            is_pure_expr(semantic_model, &left) && is_pure_expr(semantic_model, &right)
+        }),
+        LuaExpr::UnaryExpr(unary_expr) => unary_expr
+            .get_expr()
+            .is_some_and(|inner| is_pure_expr(semantic_model, &inner)),
+        _ => false,
+    }
+}
+
+/// An impure initializer may only be moved into its single use in the next statement, as
+/// long as it is still evaluated exactly once and before anything else with side effects.
+fn can_move_expr(
+    local_stat: &LuaLocalStat,
+    value_expr: &LuaExpr,
+    ref_nodes: &[LuaNameExpr],
+) -> bool {
+    let [ref_node] = ref_nodes else {
+        return false;
+    };
+    let Some(next_stat) = local_stat.syntax().next_sibling().and_then(LuaStat::cast) else {
+        return false;
+    };
+    let ref_syntax = ref_node.syntax();
+    if !next_stat
+        .get_range()
+        .contains_range(ref_syntax.text_range())
+    {
+        return false;
+    }
+    if is_in_nested_closure(ref_syntax, next_stat.syntax())
+        || is_conditionally_evaluated(ref_syntax, next_stat.syntax())
+    {
+        return false;
+    }
+    // branches and loop bodies may run any number of times
+    if ref_syntax
+        .ancestors()
+        .take_while(|node| node != next_stat.syntax())
+        .any(|node| {
+            matches!(
+                node.kind().into(),
+                LuaSyntaxKind::Block | LuaSyntaxKind::ElseIfClauseStat
+            )
+        })
+    {
+        return false;
+    }
+    if let LuaStat::WhileStat(_) | LuaStat::RepeatStat(_) = next_stat {
+        return false;
+    }
+    if matches!(value_expr, LuaExpr::ClosureExpr(_) | LuaExpr::TableExpr(_)) {
+        return true;
+    }
+
+    !next_stat
+        .syntax()
+        .descendants()
+        .filter(|node| node.text_range().end() <= ref_syntax.text_range().start())
+        .any(|node| LuaCallExpr::can_cast(node.kind().into()))
+}
+
+fn is_shadowed(
+    semantic_model: &SemanticModel,
+    value_expr: &LuaExpr,
+    ref_nodes: &[LuaNameExpr],
+) -> bool {
+    let Some(decl_tree) = semantic_model
+        .get_db()
+        .get_decl_index()
+        .get_decl_tree(&semantic_model.get_file_id())
+    else {
+        return true;
+    };
+
+    let mut name_exprs = value_expr.descendants::<LuaNameExpr>().collect::<Vec<_>>();
+    if let LuaExpr::NameExpr(name_expr) = value_expr {
+        name_exprs.push(name_expr.clone());
+    }
+    for name_expr in name_exprs {
+        let Some(name) = name_expr.get_name_text() else {
+            return true;
+        };
+        let decl_id = get_name_expr_decl(semantic_model, &name_expr).map(|decl| decl.get_id());
+        for ref_node in ref_nodes {
+            let visible = decl_tree
+                .find_local_decl(&name, ref_node.get_position())
+                .map(|decl| decl.get_id());
+            if visible != decl_id {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+fn need_paren(value_expr: &LuaExpr, ref_node: &LuaNameExpr) -> bool {
+    let Some(parent) = ref_node.syntax().parent() else {
+        return false;
+    };
+    let is_prefix = is_prefix_of(&parent, ref_node.syntax());
+    match value_expr {
+        LuaExpr::BinaryExpr(_) | LuaExpr::UnaryExpr(_) | LuaExpr::TernaryExpr(_) => {
+            is_prefix
+                || matches!(
+                    parent.kind().into(),
+                    LuaSyntaxKind::BinaryExpr | LuaSyntaxKind::UnaryExpr
+                )
+        }
+        LuaExpr::LiteralExpr(_) | LuaExpr::TableExpr(_) | LuaExpr::ClosureExpr(_) => is_prefix,
+        _ => false,
+    }
+}
+
+fn is_prefix_of(parent: &LuaSyntaxNode, node: &LuaSyntaxNode) -> bool {
+    if let Some(call_expr) = LuaCallExpr::cast(parent.clone()) {
+        return call_expr
+            .get_prefix_expr()
+            .is_some_and(|prefix| prefix.syntax() == node);
+    }
+    if let Some(index_expr) = LuaIndexExpr::cast(parent.clone()) {
+        return index_expr
+            .get_prefix_expr()
+            .is_some_and(|prefix| prefix.syntax() == node);
+    }
+
+    false
+}
@@ -0,0 +1,908 @@
+use std::collections::HashMap;
+
+use emmylua_code_analysis::{EmmyLuaAnalysis, FileId, LuaDeclId, LuaType, SemanticModel};
+use emmylua_parser::{
+    LuaAstNode, LuaAstToken, LuaCallExpr, LuaExpr, LuaIndexExpr, LuaLocalStat, LuaNameExpr,
+    LuaStat, LuaVarExpr,
+};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Position, Range, TextEdit, Uri, WorkspaceEdit,
+};
+use rowan::{TextRange, TextSize};
+
+use super::refactor_utils::{
+    get_name_expr_decl, get_stat_delete_range, get_stat_start, get_unique_name,
+};
+use crate::handlers::command::{
+    build_require_stat_text, find_require_insert_line, format_require_path,
+    make_move_symbol_command,
+};
+
+pub fn build_move_symbol(
+    semantic_model: &SemanticModel,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+) -> Option<()> {
+    let document = semantic_model.get_document();
+    let offset = document.get_offset(range.start.line as usize, range.start.character as usize)?;
+    let export = get_module_export(semantic_model)?;
+    let symbol = find_move_symbol(semantic_model, &export, offset)?;
+
+    let title = t!(
+        "Move '%{name}' to another module",
+        name = symbol.member_name
+    )
+    .to_string();
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: title.clone(),
+        kind: Some(CodeActionKind::from("refactor.move")),
+        command: Some(make_move_symbol_command(
+            &title,
+            document.get_uri(),
+            range.start,
+        )),
+        ..Default::default()
+    }));
+
+    Some(())
+}
+
+/// Moves the module member defined at `position` into the module `target_file_id`, and
+/// rewrites every `require` and access of the member in the workspace.
+pub fn move_symbol(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    position: Position,
+    target_file_id: FileId,
+) -> Result<WorkspaceEdit, String> {
+    if file_id == target_file_id {
+        return Err("cannot move a symbol into its own module".to_string());
+    }
+    let compilation = &analysis.compilation;
+    let db = compilation.get_db();
+    let module_index = db.get_module_index();
+    let source_model = compilation
+        .get_semantic_model(file_id)
+        .ok_or("source file not found")?;
+    let target_model = compilation
+        .get_semantic_model(target_file_id)
+        .ok_or("target file not found")?;
+    let source_module = module_index
+        .get_module(file_id)
+        .ok_or("the source file is not a module")?;
+    let target_module = module_index
+        .get_module(target_file_id)
+        .ok_or("the target file is not a module")?;
+
+    let source_document = source_model.get_document();
+    let offset = source_document
+        .get_offset(position.line as usize, position.character as usize)
+        .ok_or("invalid position")?;
+    let source_export = get_module_export(&source_model)
+        .ok_or("the source module does not return a local table")?;
+    let symbol = find_move_symbol(&source_model, &source_export, offset)
+        .ok_or("there is no module member to move at this position")?;
+    let moved = collect_moved_code(&source_model, &source_export, &symbol)?;
+
+    let target_export = get_module_export(&target_model);
+    let is_new_target = target_model
+        .get_root()
+        .get_block()
+        .is_none_or(|block| block.get_stats().next().is_none());
+    let target_export_name = match &target_export {
+        Some(target_export) => target_export.name.clone(),
+        None if is_new_target => "M".to_string(),
+        None => return Err("the target module does not return a local table".to_string()),
+    };
+    if let Some(target_export) = &target_export {
+        check_target_conflicts(&target_model, target_export, &symbol, &source_model)?;
+    }
+
+    #[allow(clippy::mutable_key_type)]
+    let mut changes: HashMap<Uri, Vec<TextEdit>> = HashMap::new();
+
+    // the target module
+    let existing_source_alias = find_require_alias(&target_model, file_id);
+    let source_alias = match &existing_source_alias {
+        Some((name, _)) => name.clone(),
+        None => get_unique_name(&target_model, &source_module.name),
+    };
+    let moved_text = moved.render(&target_export_name, &source_alias);
+    let mut require_lines = Vec::new();
+    for stat in &moved.required_stats {
+        let name = get_local_stat_name(stat).unwrap_or_default();
+        if !has_chunk_local(&target_model, &name) {
+            require_lines.push(stat.syntax().text().to_string());
+        }
+    }
+    if moved.uses_source_alias && existing_source_alias.is_none() {
+        require_lines.push(build_require_stat_text(
+            &target_model,
+            &source_alias,
+            &source_module.full_module_name,
+            None,
+        ));
+    }
+
+    let target_document = target_model.get_document();
+    let mut target_edits = Vec::new();
+    match &target_export {
+        Some(target_export) => {
+            if !require_lines.is_empty() {
+                let text = target_document.get_text();
+                let line = find_require_insert_line(&target_model, TextSize::of(text))
+                    .ok_or("invalid target module")?;
+                target_edits.push(insert_at_line(
+                    line,
+                    format!("{}\n", require_lines.join("\n")),
+                ));
+            }
+            let insert_offset = get_stat_start(&target_export.return_stat);
+            target_edits.push(TextEdit {
+                range: to_lsp_range(&target_model, TextRange::empty(insert_offset))?,
+                new_text: format!("{}\n\n", moved_text),
+            });
+            target_edits.extend(rewrite_requirer(
+                &target_model,
+                file_id,
+                &symbol.member_name,
+                target_module.file_id,
+                Some(&target_export_name),
+                moved.uses_source_alias,
+            ));
+        }
+        None => {
+            let mut text = String::new();
+            if !require_lines.is_empty() {
+                text.push_str(&format!("{}\n\n", require_lines.join("\n")));
+            }
+            text.push_str(&format!(
+                "local {} = {{}}\n\n{}\n\nreturn {}\n",
+                target_export_name, moved_text, target_export_name
+            ));
+            let end = TextSize::of(target_document.get_text());
+            target_edits.push(TextEdit {
+                range: to_lsp_range(&target_model, TextRange::empty(end))?,
+                new_text: text,
+            });
+        }
+    }
+    changes.insert(target_document.get_uri(), target_edits);
+
+    // the source module
+    let mut source_edits = Vec::new();
+    for stat in &symbol.stats {
+        source_edits.push(TextEdit {
+            range: to_lsp_range(&source_model, get_stat_delete_range(&source_model, stat))?,
+            new_text: String::new(),
+        });
+    }
+    let remaining_refs = collect_remaining_refs(&source_model, &source_export, &symbol);
+    if !remaining_refs.is_empty() {
+        let target_alias = match find_require_alias(&source_model, target_file_id) {
+            Some((name, _)) => name,
+            None => {
+                let name = get_unique_name(&source_model, &target_module.name);
+                let line =
+                    find_require_insert_line(&source_model, symbol.stats[0].get_range().start())
+                        .ok_or("invalid source module")?;
+                source_edits.push(insert_at_line(
+                    line,
+                    format!(
+                        "{}\n",
+                        build_require_stat_text(
+                            &source_model,
+                            &name,
+                            &target_module.full_module_name,
+                            None
+                        )
+                    ),
+                ));
+                name
+            }
+        };
+        for (range, is_member_access) in remaining_refs {
+            let new_text = if is_member_access {
+                target_alias.clone()
+            } else {
+                format!("{}.{}", target_alias, symbol.member_name)
+            };
+            source_edits.push(TextEdit {
+                range: to_lsp_range(&source_model, range)?,
+                new_text,
+            });
+        }
+    }
+    changes.insert(source_document.get_uri(), source_edits);
+
+    // every other module requiring the source module
+    for other_file_id in module_index.get_main_workspace_file_ids() {
+        if other_file_id == file_id || other_file_id == target_file_id {
+            continue;
+        }
+        let Some(semantic_model) = compilation.get_semantic_model(other_file_id) else {
+            continue;
+        };
+        let edits = rewrite_requirer(
+            &semantic_model,
+            file_id,
+            &symbol.member_name,
+            target_file_id,
+            None,
+            false,
+        );
+        if !edits.is_empty() {
+            changes.insert(semantic_model.get_document().get_uri(), edits);
+        }
+    }
+
+    Ok(WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    })
+}
+
+struct ModuleExport {
+    name: String,
+    decl_id: LuaDeclId,
+    return_stat: LuaStat,
+}
+
+struct MoveSymbol {
+    member_name: String,
+    /// The local holding the moved value, e.g. a class exported as `M.Class = Class`.
+    local_decl_id: Option<LuaDeclId>,
+    stats: Vec<LuaStat>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MovedRef {
+    /// The export table of the source module, which becomes the one of the target module.
+    TargetExport,
+    /// Any other use of the source module's export table.
+    SourceAlias,
+}
+
+struct MovedCode {
+    text: String,
+    parts: Vec<(TextRange, Vec<(TextRange, MovedRef)>)>,
+    required_stats: Vec<LuaStat>,
+    uses_source_alias: bool,
+}
+
+impl MovedCode {
+    fn render(&self, target_export_name: &str, source_alias: &str) -> String {
+        self.parts
+            .iter()
+            .map(|(range, refs)| {
+                let mut text = String::new();
+                let mut last = range.start();
+                for (ref_range, moved_ref) in refs {
+                    text.push_str(&self.text[TextRange::new(last, ref_range.start())]);
+                    text.push_str(match moved_ref {
+                        MovedRef::TargetExport => target_export_name,
+                        MovedRef::SourceAlias => source_alias,
+                    });
+                    last = ref_range.end();
+                }
+                text.push_str(&self.text[TextRange::new(last, range.end())]);
+                text
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+fn get_module_export(semantic_model: &SemanticModel) -> Option<ModuleExport> {
+    let block = semantic_model.get_root().get_block()?;
+    let stat = block.get_stats().last()?;
+    let LuaStat::ReturnStat(return_stat) = &stat else {
+        return None;
+    };
+    let mut exprs = return_stat.get_expr_list();
+    let LuaExpr::NameExpr(name_expr) = exprs.next()? else {
+        return None;
+    };
+    if exprs.next().is_some() {
+        return None;
+    }
+    let decl = get_name_expr_decl(semantic_model, &name_expr)?;
+    if !decl.is_local() {
+        return None;
+    }
+
+    Some(ModuleExport {
+        name: decl.get_name().to_string(),
+        decl_id: decl.get_id(),
+        return_stat: stat,
+    })
+}
+
+fn find_move_symbol(
+    semantic_model: &SemanticModel,
+    export: &ModuleExport,
+    offset: TextSize,
+) -> Option<MoveSymbol> {
+    let root_block = semantic_model.get_root().get_block()?;
+    let stat = root_block
+        .get_stats()
+        .find(|stat| stat.get_range().contains_inclusive(offset))?;
+    let header_range = match &stat {
+        LuaStat::FuncStat(func_stat) => func_stat.get_func_name()?.get_range(),
+        LuaStat::AssignStat(assign_stat) => {
+            assign_stat.get_var_and_expr_list().0.first()?.get_range()
+        }
+        LuaStat::LocalStat(local_stat) => local_stat.get_local_name_list().next()?.get_range(),
+        LuaStat::LocalFuncStat(local_func_stat) => local_func_stat.get_local_name()?.get_range(),
+        _ => return None,
+    };
+    if !header_range.contains_inclusive(offset) {
+        return None;
+    }
+
+    let (member_name, local_decl_id) = match get_member_def(semantic_model, &stat, &export.decl_id)
+    {
+        Some((member_name, value)) => {
+            let local_decl_id = match value {
+                Some(LuaExpr::NameExpr(name_expr)) => {
+                    get_name_expr_decl(semantic_model, &name_expr)
+                        .filter(|decl| decl.is_local() && decl.get_id() != export.decl_id)
+                        .map(|decl| decl.get_id())
+                }
+                _ => None,
+            };
+            (member_name, local_decl_id)
+        }
+        None => {
+            let local_decl_id = get_declared_local(semantic_model, &stat)?;
+            let member_name = root_block.get_stats().find_map(|stat| {
+                let (member_name, value) = get_member_def(semantic_model, &stat, &export.decl_id)?;
+                let LuaExpr::NameExpr(name_expr) = value? else {
+                    return None;
+                };
+                let decl = get_name_expr_decl(semantic_model, &name_expr)?;
+                (decl.get_id() == local_decl_id).then_some(member_name)
+            })?;
+            (member_name, Some(local_decl_id))
+        }
+    };
+
+    let mut stats = Vec::new();
+    for stat in root_block.get_stats() {
+        if get_member_def(semantic_model, &stat, &export.decl_id)
+            .is_some_and(|(name, _)| name == member_name)
+        {
+            stats.push(stat);
+            continue;
+        }
+        let Some(local_decl_id) = &local_decl_id else {
+            continue;
+        };
+        if let LuaStat::LocalStat(local_stat) = &stat
+            && local_stat.get_local_name_list().count() > 1
+            && local_stat
+                .get_local_name_list()
+                .any(|local_name| local_name.get_position() == local_decl_id.position)
+        {
+            // `local A, B = ...` can not be split
+            return None;
+        }
+        if get_declared_local(semantic_model, &stat).as_ref() == Some(local_decl_id)
+            || is_local_member_def(semantic_model, &stat, local_decl_id)
+        {
+            stats.push(stat);
+        }
+    }
+
+    Some(MoveSymbol {
+        member_name,
+        local_decl_id,
+        stats,
+    })
+}
+
+/// Returns the member name and the assigned value of `export.name = value` or
+/// `function export.name() end`.
+fn get_member_def(
+    semantic_model: &SemanticModel,
+    stat: &LuaStat,
+    table_decl_id: &LuaDeclId,
+) -> Option<(String, Option<LuaExpr>)> {
+    let (var, value) = match stat {
+        LuaStat::FuncStat(func_stat) => (func_stat.get_func_name()?, None),
+        LuaStat::AssignStat(assign_stat) => {
+            let (vars, values) = assign_stat.get_var_and_expr_list();
+            let ([var], [value]) = (&vars[..], &values[..]) else {
+                return None;
+            };
+            (var.clone(), Some(value.clone()))
+        }
+        _ => return None,
+    };
+    let LuaVarExpr::IndexExpr(index_expr) = var else {
+        return None;
+    };
+    if !is_decl_ref(
+        semantic_model,
+        &index_expr.get_prefix_expr()?,
+        table_decl_id,
+    ) {
+        return None;
+    }
+
+    Some((
+        index_expr.get_name_token()?.get_name_text().to_string(),
+        value,
+    ))
+}
+
+fn is_local_member_def(
+    semantic_model: &SemanticModel,
+    stat: &LuaStat,
+    decl_id: &LuaDeclId,
+) -> bool {
+    let vars = match stat {
+        LuaStat::FuncStat(func_stat) => func_stat.get_func_name().into_iter().collect(),
+        LuaStat::AssignStat(assign_stat) => assign_stat.get_var_and_expr_list().0,
+        _ => return false,
+    };
+    !vars.is_empty()
+        && vars.iter().all(|var| match var {
+            LuaVarExpr::IndexExpr(index_expr) => index_expr
+                .get_prefix_expr()
+                .is_some_and(|prefix| is_decl_ref(semantic_model, &prefix, decl_id)),
+            LuaVarExpr::NameExpr(_) => false,
+        })
+}
+
+fn get_declared_local(semantic_model: &SemanticModel, stat: &LuaStat) -> Option<LuaDeclId> {
+    let local_name = match stat {
+        LuaStat::LocalStat(local_stat) => {
+            let mut local_names = local_stat.get_local_name_list();
+            let local_name = local_names.next()?;
+            if local_names.next().is_some() {
+                return None;
+            }
+            local_name
+        }
+        LuaStat::LocalFuncStat(local_func_stat) => local_func_stat.get_local_name()?,
+        _ => return None,
+    };
+    Some(LuaDeclId::new(
+        semantic_model.get_file_id(),
+        local_name.get_name_token()?.get_position(),
+    ))
+}
+
+fn get_local_stat_name(stat: &LuaStat) -> Option<String> {
+    let LuaStat::LocalStat(local_stat) = stat else {
+        return None;
+    };
+    Some(
+        local_stat
+            .get_local_name_list()
+            .next()?
+            .get_name_token()?
+            .get_name_text()
+            .to_string(),
+    )
+}
+
+fn is_decl_ref(semantic_model: &SemanticModel, expr: &LuaExpr, decl_id: &LuaDeclId) -> bool {
+    let LuaExpr::NameExpr(name_expr) = expr else {
+        return false;
+    };
+    get_name_expr_decl(semantic_model, name_expr).is_some_and(|decl| &decl.get_id() == decl_id)
+}
+
+fn is_chunk_level_stat(semantic_model: &SemanticModel, stat: &LuaStat) -> bool {
+    stat.get_parent_block() == semantic_model.get_root().get_block()
+}
+
+/// Collects the text of the moved statements and checks that they only depend on code that
+/// moves with them, the source module itself or `require`d modules.
+fn collect_moved_code(
+    semantic_model: &SemanticModel,
+    export: &ModuleExport,
+    symbol: &MoveSymbol,
+) -> Result<MovedCode, String> {
+    let document = semantic_model.get_document();
+    let mut parts = Vec::new();
+    let mut required_stats: Vec<LuaStat> = Vec::new();
+    let mut uses_source_alias = false;
+    for stat in &symbol.stats {
+        let mut refs = Vec::new();
+        for name_expr in stat.descendants::<LuaNameExpr>() {
+            let Some(decl) = get_name_expr_decl(semantic_model, &name_expr) else {
+                continue;
+            };
+            if !decl.is_local()
+                || symbol
+                    .stats
+                    .iter()
+                    .any(|stat| stat.get_range().contains(decl.get_position()))
+            {
+                continue;
+            }
+
+            if decl.get_id() == export.decl_id {
+                let is_member = name_expr
+                    .get_parent::<LuaIndexExpr>()
+                    .filter(|index_expr| {
+                        index_expr
+                            .get_prefix_expr()
+                            .is_some_and(|prefix| prefix.syntax() == name_expr.syntax())
+                    })
+                    .and_then(|index_expr| index_expr.get_name_token())
+                    .is_some_and(|name_token| name_token.get_name_text() == symbol.member_name);
+                let moved_ref = if is_member {
+                    MovedRef::TargetExport
+                } else {
+                    uses_source_alias = true;
+                    MovedRef::SourceAlias
+                };
+                refs.push((name_expr.get_range(), moved_ref));
+                continue;
+            }
+
+            let decl_stat = semantic_model
+                .get_root()
+                .syntax()
+                .covering_element(decl.get_range())
+                .ancestors()
+                .find_map(LuaStat::cast);
+            match decl_stat {
+                Some(decl_stat) if is_chunk_level_stat(semantic_model, &decl_stat) => {
+                    if !is_require_local_stat(&decl_stat) {
+                        return Err(format!(
+                            "'{}' depends on the local '{}' which can not be moved with it",
+                            symbol.member_name,
+                            decl.get_name()
+                        ));
+                    }
+                    if !required_stats.contains(&decl_stat) {
+                        required_stats.push(decl_stat);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let range = TextRange::new(get_stat_start(stat), stat.get_range().end());
+        parts.push((range, refs));
+    }
+
+    Ok(MovedCode {
+        parts,
+        required_stats,
+        uses_source_alias,
+        text: document.get_text().to_string(),
+    })
+}
+
+fn is_require_local_stat(stat: &LuaStat) -> bool {
+    let LuaStat::LocalStat(local_stat) = stat else {
+        return false;
+    };
+    let mut values = local_stat.get_value_exprs();
+    let is_require = match values.next() {
+        Some(LuaExpr::CallExpr(call_expr)) => call_expr.is_require(),
+        Some(LuaExpr::IndexExpr(index_expr)) => matches!(
+            index_expr.get_prefix_expr(),
+            Some(LuaExpr::CallExpr(call_expr)) if call_expr.is_require()
+        ),
+        _ => false,
+    };
+    is_require && values.next().is_none() && local_stat.get_local_name_list().count() == 1
+}
+
+fn check_target_conflicts(
+    target_model: &SemanticModel,
+    target_export: &ModuleExport,
+    symbol: &MoveSymbol,
+    source_model: &SemanticModel,
+) -> Result<(), String> {
+    let root_block = target_model
+        .get_root()
+        .get_block()
+        .ok_or("invalid target module")?;
+    if root_block.get_stats().any(|stat| {
+        get_member_def(target_model, &stat, &target_export.decl_id)
+            .is_some_and(|(name, _)| name == symbol.member_name)
+    }) {
+        return Err(format!(
+            "the target module already defines '{}'",
+            symbol.member_name
+        ));
+    }
+    if let Some(local_decl_id) = &symbol.local_decl_id
+        && let Some(decl) = source_model
+            .get_db()
+            .get_decl_index()
+            .get_decl(local_decl_id)
+        && has_chunk_local(target_model, decl.get_name())
+    {
+        return Err(format!(
+            "the target module already has a local named '{}'",
+            decl.get_name()
+        ));
+    }
+
+    Ok(())
+}
+
+fn has_chunk_local(semantic_model: &SemanticModel, name: &str) -> bool {
+    let Some(decl_tree) = semantic_model
+        .get_db()
+        .get_decl_index()
+        .get_decl_tree(&semantic_model.get_file_id())
+    else {
+        return false;
+    };
+    let end = semantic_model.get_root().get_range().end();
+    decl_tree.find_local_decl(name, end).is_some()
+}
+
+/// Returns the file required by a `require` call.
+fn get_required_file_id(semantic_model: &SemanticModel, call_expr: &LuaCallExpr) -> Option<FileId> {
+    if !call_expr.is_require() {
+        return None;
+    }
+    let arg_expr = call_expr.get_args_list()?.get_args().next()?;
+    let LuaType::StringConst(module_path) = semantic_model.infer_expr(arg_expr).ok()? else {
+        return None;
+    };
+    semantic_model
+        .get_db()
+        .get_module_index()
+        .find_module(&module_path)
+        .map(|module_info| module_info.file_id)
+}
+
+/// Finds a chunk level `local name = require("module")` of `module_file_id`.
+fn find_require_alias(
+    semantic_model: &SemanticModel,
+    module_file_id: FileId,
+) -> Option<(String, LuaLocalStat)> {
+    let root_block = semantic_model.get_root().get_block()?;
+    root_block.get_stats().find_map(|stat| {
+        let LuaStat::LocalStat(local_stat) = stat else {
+            return None;
+        };
+        let mut values = local_stat.get_value_exprs();
+        let LuaExpr::CallExpr(call_expr) = values.next()? else {
+            return None;
+        };
+        if values.next().is_some()
+            || get_required_file_id(semantic_model, &call_expr)? != module_file_id
+        {
+            return None;
+        }
+        let name = get_local_stat_name(&LuaStat::LocalStat(local_stat.clone()))?;
+        Some((name, local_stat))
+    })
+}
+
+/// Rewrites `require("source").member` and `alias.member` in a file requiring the source
+/// module. In the target module `target_export_name` replaces the access, otherwise the
+/// access goes through a `require` of the target module.
+fn rewrite_requirer(
+    semantic_model: &SemanticModel,
+    source_file_id: FileId,
+    member_name: &str,
+    target_file_id: FileId,
+    target_export_name: Option<&str>,
+    keep_source_require: bool,
+) -> Vec<TextEdit> {
+    let mut edits = Vec::new();
+    let root = semantic_model.get_root();
+    for call_expr in root.descendants::<LuaCallExpr>() {
+        if get_required_file_id(semantic_model, &call_expr) != Some(source_file_id) {
+            continue;
+        }
+        rewrite_require_call(
+            semantic_model,
+            &call_expr,
+            member_name,
+            target_file_id,
+            target_export_name,
+            keep_source_require,
+            &mut edits,
+        );
+    }
+
+    edits
+}
+
+fn rewrite_require_call(
+    semantic_model: &SemanticModel,
+    call_expr: &LuaCallExpr,
+    member_name: &str,
+    target_file_id: FileId,
+    target_export_name: Option<&str>,
+    keep_source_require: bool,
+    edits: &mut Vec<TextEdit>,
+) -> Option<()> {
+    let db = semantic_model.get_db();
+    let file_id = semantic_model.get_file_id();
+    let document = semantic_model.get_document();
+
+    // require("source").member
+    if let Some(index_expr) = call_expr.get_parent::<LuaIndexExpr>()
+        && index_expr.get_name_token()?.get_name_text() == member_name
+    {
+        match target_export_name {
+            Some(target_export_name) => edits.push(TextEdit {
+                range: document.to_lsp_range(call_expr.get_range())?,
+                new_text: target_export_name.to_string(),
+            }),
+            None => edits.push(rewrite_require_path(
+                semantic_model,
+                call_expr,
+                target_file_id,
+            )?),
+        }
+        return Some(());
+    }
+
+    // local alias = require("source")
+    let local_stat = call_expr.get_parent::<LuaLocalStat>()?;
+    let stat = LuaStat::LocalStat(local_stat.clone());
+    if !is_chunk_level_stat(semantic_model, &stat) || !is_require_local_stat(&stat) {
+        return None;
+    }
+    let decl_id = get_declared_local(semantic_model, &stat)?;
+    let decl_ref = db
+        .get_reference_index()
+        .get_decl_references(&file_id, &decl_id)?;
+    let root = semantic_model.get_root().syntax();
+    let member_refs = decl_ref
+        .cells
+        .iter()
+        .filter(|cell| {
+            root.covering_element(cell.range)
+                .ancestors()
+                .find_map(LuaNameExpr::cast)
+                .and_then(|name_expr| {
+                    let index_expr = name_expr.get_parent::<LuaIndexExpr>()?;
+                    let prefix = index_expr.get_prefix_expr()?;
+                    (prefix.syntax() == name_expr.syntax()
+                        && index_expr.get_name_token()?.get_name_text() == member_name)
+                        .then_some(())
+                })
+                .is_some()
+        })
+        .map(|cell| cell.range)
+        .collect::<Vec<_>>();
+    if member_refs.is_empty() {
+        return Some(());
+    }
+    let all_refs_moved = member_refs.len() == decl_ref.cells.len();
+
+    let target_alias = target_export_name
+        .map(str::to_string)
+        .or_else(|| find_require_alias(semantic_model, target_file_id).map(|(name, _)| name));
+    let target_alias = match target_alias {
+        Some(target_alias) => {
+            if all_refs_moved && !keep_source_require {
+                edits.push(TextEdit {
+                    range: document.to_lsp_range(get_stat_delete_range(semantic_model, &stat))?,
+                    new_text: String::new(),
+                });
+            }
+            target_alias
+        }
+        None if all_refs_moved => {
+            edits.push(rewrite_require_path(
+                semantic_model,
+                call_expr,
+                target_file_id,
+            )?);
+            return Some(());
+        }
+        None => {
+            let target_module = db.get_module_index().get_module(target_file_id)?;
+            let name = get_unique_name(semantic_model, &target_module.name);
+            let line = document.get_line(stat.get_range().end())? + 1;
+            edits.push(insert_at_line(
+                line,
+                format!(
+                    "{}\n",
+                    build_require_stat_text(
+                        semantic_model,
+                        &name,
+                        &target_module.full_module_name,
+                        None
+                    )
+                ),
+            ));
+            name
+        }
+    };
+
+    for range in member_refs {
+        edits.push(TextEdit {
+            range: document.to_lsp_range(range)?,
+            new_text: target_alias.clone(),
+        });
+    }
+
+    Some(())
+}
+
+fn rewrite_require_path(
+    semantic_model: &SemanticModel,
+    call_expr: &LuaCallExpr,
+    target_file_id: FileId,
+) -> Option<TextEdit> {
+    let target_module = semantic_model
+        .get_db()
+        .get_module_index()
+        .get_module(target_file_id)?;
+    let arg_expr = call_expr.get_args_list()?.get_args().next()?;
+    let quote = match arg_expr.syntax().text().char_at(0.into()) {
+        Some('\'') => '\'',
+        _ => '"',
+    };
+    Some(TextEdit {
+        range: semantic_model
+            .get_document()
+            .to_lsp_range(arg_expr.get_range())?,
+        new_text: format!(
+            "{}{}{}",
+            quote,
+            format_require_path(semantic_model, &target_module.full_module_name),
+            quote
+        ),
+    })
+}
+
+/// Finds the accesses of the moved member left in the source module: `export.member` yields
+/// the range of `export` and `true`, a use of the moved local yields its range and `false`.
+fn collect_remaining_refs(
+    semantic_model: &SemanticModel,
+    export: &ModuleExport,
+    symbol: &MoveSymbol,
+) -> Vec<(TextRange, bool)> {
+    let mut refs = Vec::new();
+    for name_expr in semantic_model.get_root().descendants::<LuaNameExpr>() {
+        let range = name_expr.get_range();
+        if symbol
+            .stats
+            .iter()
+            .any(|stat| stat.get_range().contains_range(range))
+        {
+            continue;
+        }
+        let Some(decl) = get_name_expr_decl(semantic_model, &name_expr) else {
+            continue;
+        };
+        if Some(decl.get_id()) == symbol.local_decl_id {
+            refs.push((range, false));
+        } else if decl.get_id() == export.decl_id
+            && name_expr
+                .get_parent::<LuaIndexExpr>()
+                .and_then(|index_expr| index_expr.get_name_token())
+                .is_some_and(|name_token| name_token.get_name_text() == symbol.member_name)
+        {
+            refs.push((range, true));
+        }
+    }
+
+    refs
+}
+
+fn insert_at_line(line: usize, new_text: String) -> TextEdit {
+    let position = Position::new(line as u32, 0);
+    TextEdit {
+        range: Range::new(position, position),
+        new_text,
+    }
+}
+
+fn to_lsp_range(semantic_model: &SemanticModel, range: TextRange) -> Result<Range, String> {
+    semantic_model
+        .get_document()
+        .to_lsp_range(range)
+        .ok_or_else(|| "invalid range".to_string())
+}
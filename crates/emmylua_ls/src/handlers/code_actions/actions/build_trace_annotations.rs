@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use emmylua_code_analysis::{SemanticModel, build_closure_trace_annotation};
+use emmylua_parser::{LuaAstNode, LuaClosureExpr, LuaStat};
+use lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, Range, TextEdit, WorkspaceEdit};
+
+use crate::context::RuntimeTraceCache;
+
+pub fn build_add_trace_annotations(
+    semantic_model: &SemanticModel,
+    trace_cache: &RuntimeTraceCache,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+) -> Option<()> {
+    let trace_files = &semantic_model.get_emmyrc().code_action.trace_files;
+    if trace_files.is_empty() {
+        return None;
+    }
+
+    let document = semantic_model.get_document();
+    let offset = document.get_offset(range.start.line as usize, range.start.character as usize)?;
+    let trace = trace_cache.get(trace_files);
+    let token = semantic_model
+        .get_root()
+        .syntax()
+        .token_at_offset(offset)
+        .right_biased()?;
+    // 从内到外查找光标所在语句定义的函数, 只为第一个有记录的函数生成注解
+    let edit = token
+        .parent_ancestors()
+        .filter(|node| LuaStat::can_cast(node.kind().into()))
+        .flat_map(|stat| stat.children().filter_map(LuaClosureExpr::cast))
+        .find_map(|closure_expr| {
+            build_closure_trace_annotation(semantic_model, &closure_expr, &trace)
+        })?;
+
+    let text_edit = TextEdit {
+        range: document.to_lsp_range(edit.range)?,
+        new_text: edit.new_text,
+    };
+    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+        title: t!("Add annotations from runtime trace").to_string(),
+        kind: Some(CodeActionKind::REFACTOR_REWRITE),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(document.get_uri(), vec![text_edit])])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }));
+
+    Some(())
+}
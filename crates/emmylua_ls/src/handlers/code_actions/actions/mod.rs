@@ -1,5 +1,18 @@
+mod build_convert_method;
 mod build_disable_code;
+mod build_extract_function;
+mod build_extract_variable;
 mod build_fix_code;
+mod build_inline_variable;
+mod build_move_symbol;
+mod build_trace_annotations;
+mod refactor_utils;
 
+pub use build_convert_method::*;
 pub use build_disable_code::*;
+pub use build_extract_function::*;
+pub use build_extract_variable::*;
 pub use build_fix_code::*;
+pub use build_inline_variable::*;
+pub use build_move_symbol::*;
+pub use build_trace_annotations::*;
@@ -0,0 +1,265 @@
+use std::collections::HashSet;
+
+use emmylua_code_analysis::{
+    LuaDecl, LuaDeclId, LuaType, RenderLevel, SemanticModel, humanize_type,
+};
+use emmylua_parser::{
+    BinaryOperator, LuaAstNode, LuaAstToken, LuaBinaryExpr, LuaBlock, LuaClosureExpr,
+    LuaCommentOwner, LuaExpr, LuaLiteralExpr, LuaLiteralToken, LuaNameExpr, LuaStat, LuaSyntaxNode,
+    LuaTernaryExpr, LuaTokenKind,
+};
+use lsp_types::Range;
+use rowan::{TextRange, TextSize};
+
+/// Converts the lsp selection into a text range without surrounding whitespace.
+pub fn get_trimmed_selection(semantic_model: &SemanticModel, range: Range) -> Option<TextRange> {
+    let document = semantic_model.get_document();
+    let range = document.to_rowan_range(range)?;
+    let text = document.get_text_slice(range);
+    let start = range.start() + TextSize::of(&text[..text.len() - text.trim_start().len()]);
+    let end = range.end() - TextSize::of(&text[text.trim_end().len()..]);
+    if start >= end {
+        return Some(TextRange::empty(range.start()));
+    }
+
+    Some(TextRange::new(start, end))
+}
+
+/// Finds the expression whose range is exactly the selection.
+pub fn find_selected_expr(semantic_model: &SemanticModel, range: TextRange) -> Option<LuaExpr> {
+    if range.is_empty() {
+        return None;
+    }
+
+    let root = semantic_model.get_root().syntax();
+    if !root.text_range().contains_range(range) {
+        return None;
+    }
+    let element = root.covering_element(range);
+    let node = match element {
+        rowan::NodeOrToken::Node(node) => node,
+        rowan::NodeOrToken::Token(token) => token.parent()?,
+    };
+    node.ancestors()
+        .take_while(|node| node.text_range() == range)
+        .filter_map(LuaExpr::cast)
+        .last()
+}
+
+/// Returns a name based on `base` that is not used anywhere in the file.
+pub fn get_unique_name(semantic_model: &SemanticModel, base: &str) -> String {
+    let used_names: HashSet<String> = semantic_model
+        .get_root()
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.kind() == LuaTokenKind::TkName.into())
+        .map(|token| token.text().to_string())
+        .collect();
+    if !used_names.contains(base) {
+        return base.to_string();
+    }
+
+    let mut index = 1;
+    loop {
+        let name = format!("{}{}", base, index);
+        if !used_names.contains(&name) {
+            return name;
+        }
+        index += 1;
+    }
+}
+
+/// Returns the whitespace at the beginning of the line containing `offset`.
+pub fn get_line_indent(semantic_model: &SemanticModel, offset: TextSize) -> String {
+    let document = semantic_model.get_document();
+    let Some(line) = document.get_line(offset) else {
+        return String::new();
+    };
+    let Some(line_range) = document.get_line_range(line) else {
+        return String::new();
+    };
+    document
+        .get_text_slice(line_range)
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect()
+}
+
+/// Removes `old_indent` from each line of `text` and prefixes it with `new_indent` instead.
+/// The first line of `text` is assumed to start right after `old_indent`.
+pub fn reindent(text: &str, old_indent: &str, new_indent: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if line.trim().is_empty() {
+                return String::new();
+            }
+            let line = if i == 0 {
+                line
+            } else {
+                line.strip_prefix(old_indent).unwrap_or(line.trim_start())
+            };
+            format!("{}{}", new_indent, line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders a type the way it would be written in a `---@param`/`---@return` tag.
+pub fn humanize_doc_type(semantic_model: &SemanticModel, typ: &LuaType) -> String {
+    let typ = match typ {
+        LuaType::IntegerConst(_) | LuaType::DocIntegerConst(_) => LuaType::Integer,
+        LuaType::FloatConst(_) => LuaType::Number,
+        LuaType::StringConst(_) | LuaType::DocStringConst(_) => LuaType::String,
+        LuaType::BooleanConst(_) | LuaType::DocBooleanConst(_) => LuaType::Boolean,
+        LuaType::TableConst(_) => LuaType::Table,
+        LuaType::Unknown | LuaType::SelfInfer => LuaType::Any,
+        typ => typ.clone(),
+    };
+    humanize_type(semantic_model.get_db(), &typ, RenderLevel::Simple)
+}
+
+pub fn get_name_expr_decl<'a>(
+    semantic_model: &'a SemanticModel,
+    name_expr: &LuaNameExpr,
+) -> Option<&'a LuaDecl> {
+    let db = semantic_model.get_db();
+    let name_token = name_expr.get_name_token()?;
+    let decl_id = db
+        .get_reference_index()
+        .get_var_reference_decl(&semantic_model.get_file_id(), name_token.get_range())?;
+    db.get_decl_index().get_decl(&decl_id)
+}
+
+pub fn is_decl_write_at(
+    semantic_model: &SemanticModel,
+    decl_id: &LuaDeclId,
+    range: TextRange,
+) -> bool {
+    semantic_model
+        .get_db()
+        .get_reference_index()
+        .get_decl_references(&semantic_model.get_file_id(), decl_id)
+        .is_some_and(|decl_ref| {
+            decl_ref
+                .cells
+                .iter()
+                .any(|cell| cell.range == range && cell.is_write)
+        })
+}
+
+/// Whether `node` is nested in a function that starts inside `root`.
+pub fn is_in_nested_closure(node: &LuaSyntaxNode, root: &LuaSyntaxNode) -> bool {
+    node.ancestors()
+        .take_while(|ancestor| ancestor != root)
+        .any(|ancestor| LuaClosureExpr::can_cast(ancestor.kind().into()))
+}
+
+pub fn is_vararg_expr(node: &LuaSyntaxNode) -> bool {
+    LuaLiteralExpr::cast(node.clone())
+        .and_then(|literal| literal.get_literal())
+        .is_some_and(|literal| matches!(literal, LuaLiteralToken::Dots(_)))
+}
+
+/// Returns the start of `stat` including the comments attached above it.
+pub fn get_stat_start(stat: &LuaStat) -> TextSize {
+    stat.get_comments()
+        .iter()
+        .map(|comment| comment.get_range().start())
+        .fold(stat.get_range().start(), TextSize::min)
+}
+
+/// Returns the range to delete for `stat`, including its attached comments and, when the
+/// statement is alone on its lines, the surrounding indentation and line break.
+pub fn get_stat_delete_range(semantic_model: &SemanticModel, stat: &LuaStat) -> TextRange {
+    let document = semantic_model.get_document();
+    let text = document.get_text();
+    let mut start = get_stat_start(stat);
+    let mut end = stat.get_range().end();
+
+    let before = &text[..usize::from(start)];
+    let line_start = before.rfind('\n').map(|pos| pos + 1).unwrap_or(0);
+    let after = &text[usize::from(end)..];
+    let line_end = after.find('\n').map(|pos| pos + 1).unwrap_or(after.len());
+    if before[line_start..].trim().is_empty() && after[..line_end].trim().is_empty() {
+        start = TextSize::from(line_start as u32);
+        end += TextSize::from(line_end as u32);
+
+        // avoid leaving two blank lines behind
+        let before = before[..line_start].trim_end_matches([' ', '\t']);
+        let after = &after[line_end..];
+        let blank_line_end = after.find('\n').map(|pos| pos + 1);
+        if (before.is_empty() || before.ends_with("\n\n"))
+            && let Some(blank_line_end) = blank_line_end
+            && after[..blank_line_end].trim().is_empty()
+        {
+            end += TextSize::from(blank_line_end as u32);
+        }
+    }
+
+    TextRange::new(start, end)
+}
+
+/// Returns the statement of the chunk's root block that contains `node`.
+pub fn get_top_level_stat(semantic_model: &SemanticModel, node: &LuaSyntaxNode) -> Option<LuaStat> {
+    let root_block = semantic_model.get_root().get_block()?;
+    node.ancestors()
+        .filter_map(LuaStat::cast)
+        .find(|stat| stat.get_parent_block().as_ref() == Some(&root_block))
+}
+
+pub fn is_root_block(semantic_model: &SemanticModel, block: &LuaBlock) -> bool {
+    semantic_model.get_root().get_block().as_ref() == Some(block)
+}
+
+/// Whether `expr` is evaluated as a value, i.e. it is not an assignment target or the name of
+/// a function statement.
+pub fn is_value_expr(expr: &LuaExpr) -> bool {
+    let Some(stat) = expr.syntax().ancestors().find_map(LuaStat::cast) else {
+        return false;
+    };
+    match &stat {
+        LuaStat::AssignStat(assign_stat) => {
+            let (vars, _) = assign_stat.get_var_and_expr_list();
+            !vars.iter().any(|var| var.syntax() == expr.syntax())
+        }
+        LuaStat::FuncStat(func_stat) => func_stat
+            .get_func_name()
+            .is_none_or(|name| !name.syntax().text_range().contains_range(expr.get_range())),
+        _ => true,
+    }
+}
+
+/// Whether `node` is only evaluated depending on a condition inside `root`, such as the
+/// right operand of `and`/`or` or a branch of a ternary expression.
+pub fn is_conditionally_evaluated(node: &LuaSyntaxNode, root: &LuaSyntaxNode) -> bool {
+    let mut child = node.clone();
+    while let Some(parent) = child.parent() {
+        if &child == root {
+            break;
+        }
+        if let Some(binary_expr) = LuaBinaryExpr::cast(parent.clone())
+            && let Some(op_token) = binary_expr.get_op_token()
+            && matches!(
+                op_token.get_op(),
+                BinaryOperator::OpAnd | BinaryOperator::OpOr | BinaryOperator::OpNilCoalescing
+            )
+            && binary_expr
+                .get_exprs()
+                .is_some_and(|(_, right)| right.syntax() == &child)
+        {
+            return true;
+        }
+        if let Some(ternary_expr) = LuaTernaryExpr::cast(parent.clone())
+            && ternary_expr
+                .get_exprs()
+                .is_some_and(|(condition, _, _)| condition.syntax() != &child)
+        {
+            return true;
+        }
+        child = parent;
+    }
+
+    false
+}
@@ -7,13 +7,21 @@ use lsp_types::{
 };
 
 use super::actions::{
-    build_add_doc_tag, build_disable_file_changes, build_disable_next_line_changes,
-    build_need_check_nil, build_preferred_local_alias_fix,
+    build_add_doc_tag, build_add_missing_branches, build_add_missing_table_keys,
+    build_add_trace_annotations, build_convert_method, build_disable_file_changes,
+    build_disable_next_line_changes, build_extract_function, build_extract_variable,
+    build_inline_variable, build_move_symbol, build_need_check_nil,
+    build_preferred_local_alias_fix,
+};
+use crate::{
+    context::RuntimeTraceCache,
+    handlers::command::{DisableAction, make_disable_code_command},
 };
-use crate::handlers::command::{DisableAction, make_disable_code_command};
 
 pub fn build_actions(
     semantic_model: &SemanticModel,
+    trace_cache: &RuntimeTraceCache,
+    range: Range,
     diagnostics: Vec<Diagnostic>,
 ) -> Option<CodeActionResponse> {
     let mut actions = Vec::new();
@@ -50,6 +58,8 @@ pub fn build_actions(
         }
     }
 
+    add_refactor_actions(semantic_model, trace_cache, &mut actions, range);
+
     if actions.is_empty() {
         return None;
     }
@@ -57,6 +67,20 @@ pub fn build_actions(
     Some(actions)
 }
 
+fn add_refactor_actions(
+    semantic_model: &SemanticModel,
+    trace_cache: &RuntimeTraceCache,
+    actions: &mut Vec<CodeActionOrCommand>,
+    range: Range,
+) {
+    build_extract_variable(semantic_model, actions, range);
+    build_extract_function(semantic_model, actions, range);
+    build_inline_variable(semantic_model, actions, range);
+    build_move_symbol(semantic_model, actions, range);
+    build_convert_method(semantic_model, actions, range);
+    build_add_trace_annotations(semantic_model, trace_cache, actions, range);
+}
+
 #[allow(unused_variables)]
 fn add_fix_code_action(
     semantic_model: &SemanticModel,
@@ -72,6 +96,10 @@ fn add_fix_code_action(
         DiagnosticCode::PreferredLocalAlias => {
             build_preferred_local_alias_fix(semantic_model, actions, range, data)
         }
+        DiagnosticCode::NonExhaustiveBranches => {
+            build_add_missing_branches(semantic_model, actions, range, data);
+            build_add_missing_table_keys(semantic_model, actions, range, data)
+        }
         _ => Some(()),
     }
 }
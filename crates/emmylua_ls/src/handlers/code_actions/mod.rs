@@ -1,15 +1,16 @@
 mod actions;
 mod build_actions;
 
+pub use actions::{convert_method, move_symbol};
 use build_actions::build_actions;
 use emmylua_code_analysis::{EmmyLuaAnalysis, FileId};
 use lsp_types::{
     ClientCapabilities, CodeActionParams, CodeActionProviderCapability, CodeActionResponse,
-    Diagnostic, ServerCapabilities,
+    Diagnostic, Range, ServerCapabilities,
 };
 use tokio_util::sync::CancellationToken;
 
-use crate::context::ServerContextSnapshot;
+use crate::context::{RuntimeTraceCache, ServerContextSnapshot};
 
 use super::RegisterCapabilities;
 
@@ -23,17 +24,25 @@ pub async fn on_code_action_handler(
     let diagnostics = params.context.diagnostics;
     let analysis = context.analysis().read().await;
     let file_id = analysis.get_file_id(&uri)?;
-    code_action(&analysis, file_id, diagnostics)
+    code_action(
+        &analysis,
+        context.runtime_trace_cache(),
+        file_id,
+        params.range,
+        diagnostics,
+    )
 }
 
 pub fn code_action(
     analysis: &EmmyLuaAnalysis,
+    trace_cache: &RuntimeTraceCache,
     file_id: FileId,
+    range: Range,
     diagnostics: Vec<Diagnostic>,
 ) -> Option<CodeActionResponse> {
     let semantic_model = analysis.compilation.get_semantic_model(file_id)?;
 
-    build_actions(&semantic_model, diagnostics)
+    build_actions(&semantic_model, trace_cache, range, diagnostics)
 }
 
 pub struct CodeActionsCapabilities;
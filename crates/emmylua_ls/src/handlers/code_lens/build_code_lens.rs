@@ -1,6 +1,14 @@
-use emmylua_code_analysis::{LuaDeclId, LuaMemberId, SemanticModel};
-use emmylua_parser::{LuaAst, LuaAstNode, LuaAstToken, LuaFuncStat, LuaLocalFuncStat, LuaVarExpr};
-use lsp_types::CodeLens;
+use emmylua_code_analysis::{InferGuard, LuaDeclId, LuaMemberId, LuaType, SemanticModel};
+use emmylua_parser::{
+    LuaAst, LuaAstNode, LuaAstToken, LuaDocTagClass, LuaFuncStat, LuaIndexExpr, LuaLocalFuncStat,
+    LuaVarExpr,
+};
+use lsp_types::{CodeLens, Range};
+
+use crate::handlers::{
+    implementation::{find_override_member_ids, find_sub_type_ids},
+    inlay_hint::get_super_member_id,
+};
 
 use super::CodeLensData;
 
@@ -15,6 +23,9 @@ pub fn build_code_lens(semantic_model: &SemanticModel) -> Option<Vec<CodeLens>>
             LuaAst::LuaLocalFuncStat(local_func_stat) => {
                 add_local_func_stat_code_lens(semantic_model, &mut result, local_func_stat)?;
             }
+            LuaAst::LuaDocTagClass(class) => {
+                add_class_code_lens(semantic_model, &mut result, class);
+            }
             _ => {}
         }
     }
@@ -41,6 +52,7 @@ fn add_func_stat_code_lens(
                 command: None,
                 data: Some(serde_json::to_value(data).unwrap()),
             });
+            add_override_code_lens(semantic_model, result, &index_expr, member_id, range);
         }
         LuaVarExpr::NameExpr(name_expr) => {
             let name_token = name_expr.get_name_token()?;
@@ -77,3 +89,95 @@ fn add_local_func_stat_code_lens(
     });
     Some(())
 }
+
+fn add_class_code_lens(
+    semantic_model: &SemanticModel,
+    result: &mut Vec<CodeLens>,
+    class: LuaDocTagClass,
+) -> Option<()> {
+    let file_id = semantic_model.get_file_id();
+    let name_token = class.get_name_token()?;
+    let name_range = name_token.get_range();
+    let db = semantic_model.get_db();
+    let type_decl = db
+        .get_type_index()
+        .get_file_type_decls(file_id)
+        .into_iter()
+        .find(|type_decl| {
+            type_decl
+                .get_locations()
+                .iter()
+                .any(|location| location.file_id == file_id && location.range == name_range)
+        })?;
+    let type_decl_id = type_decl.get_id();
+    let range = semantic_model.get_document().to_lsp_range(name_range)?;
+
+    result.push(CodeLens {
+        range,
+        command: None,
+        data: Some(
+            serde_json::to_value(CodeLensData::TypeReferences(file_id, type_decl_id.clone()))
+                .unwrap(),
+        ),
+    });
+    if !find_sub_type_ids(db, &type_decl_id).is_empty() {
+        result.push(CodeLens {
+            range,
+            command: None,
+            data: Some(
+                serde_json::to_value(CodeLensData::SubTypes(file_id, type_decl_id)).unwrap(),
+            ),
+        });
+    }
+
+    Some(())
+}
+
+/// 为类方法添加 `implements X.foo` 与 `overridden in N classes`
+fn add_override_code_lens(
+    semantic_model: &SemanticModel,
+    result: &mut Vec<CodeLens>,
+    index_expr: &LuaIndexExpr,
+    member_id: LuaMemberId,
+    range: Range,
+) -> Option<()> {
+    let prefix_type = semantic_model
+        .infer_expr(index_expr.get_prefix_expr()?)
+        .ok()?;
+    let LuaType::Def(type_decl_id) = prefix_type else {
+        return None;
+    };
+    let member_key = semantic_model.get_member_key(&index_expr.get_index_key()?)?;
+    let db = semantic_model.get_db();
+
+    if let Some(supers) = db.get_type_index().get_super_types(&type_decl_id) {
+        let guard = InferGuard::new();
+        for super_type in supers {
+            if let Some(super_member_id) =
+                get_super_member_id(semantic_model, super_type, &member_key, &guard)
+            {
+                result.push(CodeLens {
+                    range,
+                    command: None,
+                    data: Some(
+                        serde_json::to_value(CodeLensData::Implements(member_id, super_member_id))
+                            .unwrap(),
+                    ),
+                });
+                break;
+            }
+        }
+    }
+
+    if !find_override_member_ids(db, &type_decl_id, &member_key).is_empty() {
+        result.push(CodeLens {
+            range,
+            command: None,
+            data: Some(
+                serde_json::to_value(CodeLensData::Overrides(member_id, type_decl_id)).unwrap(),
+            ),
+        });
+    }
+
+    Some(())
+}
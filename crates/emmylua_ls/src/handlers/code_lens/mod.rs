@@ -1,12 +1,12 @@
 mod build_code_lens;
 mod resolve_code_lens;
 
-use build_code_lens::build_code_lens;
-use emmylua_code_analysis::{LuaDeclId, LuaMemberId};
+pub use build_code_lens::build_code_lens;
+use emmylua_code_analysis::{FileId, LuaDeclId, LuaMemberId, LuaTypeDeclId};
 use lsp_types::{
     ClientCapabilities, CodeLens, CodeLensOptions, CodeLensParams, ServerCapabilities,
 };
-use resolve_code_lens::resolve_code_lens;
+pub use resolve_code_lens::resolve_code_lens;
 use serde::{Deserialize, Serialize};
 use tokio_util::sync::CancellationToken;
 
@@ -52,6 +52,13 @@ pub async fn on_resolve_code_lens_handler(
 pub enum CodeLensData {
     Member(LuaMemberId),
     DeclId(LuaDeclId),
+    /// 类声明的引用, `FileId` 为 code lens 所在文件
+    TypeReferences(FileId, LuaTypeDeclId),
+    SubTypes(FileId, LuaTypeDeclId),
+    /// 被子类重写的方法及其所属类
+    Overrides(LuaMemberId, LuaTypeDeclId),
+    /// 重写方法及其在父类中的成员
+    Implements(LuaMemberId, LuaMemberId),
 }
 
 pub struct CodeLensCapabilities;
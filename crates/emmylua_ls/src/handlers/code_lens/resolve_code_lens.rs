@@ -1,9 +1,15 @@
-use emmylua_code_analysis::LuaCompilation;
+use emmylua_code_analysis::{LuaCompilation, LuaMemberOwner};
 use lsp_types::{CodeLens, Command, Location, Range, Uri};
 
 use crate::{
     context::ClientId,
-    handlers::references::{search_decl_references, search_member_references},
+    handlers::{
+        implementation::{find_override_member_ids, search_sub_type_implementations},
+        inlay_hint::get_override_lsp_location,
+        references::{
+            search_decl_references, search_member_references, search_type_decl_references,
+        },
+    },
 };
 
 use super::CodeLensData;
@@ -52,6 +58,84 @@ pub fn resolve_code_lens(
                 data: None,
             })
         }
+        CodeLensData::TypeReferences(file_id, type_decl_id) => {
+            let semantic_model = compilation.get_semantic_model(file_id)?;
+            let mut results = Vec::new();
+            search_type_decl_references(&semantic_model, type_decl_id, false, &mut results);
+            let title = make_count_title(results.len(), "reference", "references");
+            let uri = semantic_model.get_document().get_uri();
+            let command = make_locations_command(uri, code_lens.range, title, client_id, results);
+            Some(CodeLens {
+                range: code_lens.range,
+                command: Some(command),
+                data: None,
+            })
+        }
+        CodeLensData::SubTypes(file_id, type_decl_id) => {
+            let semantic_model = compilation.get_semantic_model(file_id)?;
+            let mut results = Vec::new();
+            search_sub_type_implementations(&semantic_model, &type_decl_id, &mut results);
+            let title = make_count_title(results.len(), "subclass", "subclasses");
+            let uri = semantic_model.get_document().get_uri();
+            let command = make_locations_command(uri, code_lens.range, title, client_id, results);
+            Some(CodeLens {
+                range: code_lens.range,
+                command: Some(command),
+                data: None,
+            })
+        }
+        CodeLensData::Overrides(member_id, type_decl_id) => {
+            let semantic_model = compilation.get_semantic_model(member_id.file_id)?;
+            let db = semantic_model.get_db();
+            let member = db.get_member_index().get_member(&member_id)?;
+            let results = find_override_member_ids(db, &type_decl_id, member.get_key())
+                .into_iter()
+                .filter_map(|override_id| {
+                    let override_member = db.get_member_index().get_member(&override_id)?;
+                    get_override_lsp_location(
+                        &semantic_model,
+                        override_member.get_file_id(),
+                        override_member.get_syntax_id(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let title = format!(
+                "overridden in {}",
+                make_count_title(results.len(), "class", "classes")
+            );
+            let uri = semantic_model.get_document().get_uri();
+            let command = make_locations_command(uri, code_lens.range, title, client_id, results);
+            Some(CodeLens {
+                range: code_lens.range,
+                command: Some(command),
+                data: None,
+            })
+        }
+        CodeLensData::Implements(member_id, super_member_id) => {
+            let semantic_model = compilation.get_semantic_model(member_id.file_id)?;
+            let db = semantic_model.get_db();
+            let super_member = db.get_member_index().get_member(&super_member_id)?;
+            let location = get_override_lsp_location(
+                &semantic_model,
+                super_member.get_file_id(),
+                super_member.get_syntax_id(),
+            )?;
+            let member_name = super_member.get_key().to_path();
+            let title = match db.get_member_index().get_current_owner(&super_member_id) {
+                Some(LuaMemberOwner::Type(owner_id)) => {
+                    format!("implements {}.{}", owner_id.get_simple_name(), member_name)
+                }
+                _ => format!("implements {}", member_name),
+            };
+            let uri = semantic_model.get_document().get_uri();
+            let command =
+                make_locations_command(uri, code_lens.range, title, client_id, vec![location]);
+            Some(CodeLens {
+                range: code_lens.range,
+                command: Some(command),
+                data: None,
+            })
+        }
     }
 }
 
@@ -69,11 +153,22 @@ fn make_usage_command(
     client_id: ClientId,
     refs: Vec<Location>,
 ) -> Command {
-    let title = format!(
-        "{} usage{}",
-        ref_count,
-        if ref_count == 1 { "" } else { "s" }
-    );
+    let title = make_count_title(ref_count, "usage", "usages");
+    make_locations_command(uri, range, title, client_id, refs)
+}
+
+fn make_count_title(count: usize, singular: &str, plural: &str) -> String {
+    format!("{} {}", count, if count == 1 { singular } else { plural })
+}
+
+/// 点击后以 peek 列表展示给定的位置
+fn make_locations_command(
+    uri: Uri,
+    range: Range,
+    title: String,
+    client_id: ClientId,
+    refs: Vec<Location>,
+) -> Command {
     let args = vec![
         serde_json::to_value(uri).unwrap(),
         serde_json::to_value(range.start).unwrap(),
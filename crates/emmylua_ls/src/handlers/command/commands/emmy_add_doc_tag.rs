@@ -14,10 +14,10 @@ pub struct AddDocTagCommand;
 impl CommandSpec for AddDocTagCommand {
     const COMMAND: &str = "emmy.add.doctag";
 
-    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<()> {
+    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<Value> {
         let tag_name: String = serde_json::from_value(args.first()?.clone()).ok()?;
         add_doc_tag(context.workspace_manager(), tag_name).await;
-        Some(())
+        Some(Value::Null)
     }
 }
 
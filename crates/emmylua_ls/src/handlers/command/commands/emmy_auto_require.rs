@@ -1,8 +1,9 @@
 use std::{collections::HashMap, time::Duration};
 
-use emmylua_code_analysis::FileId;
+use emmylua_code_analysis::{FileId, SemanticModel};
 use emmylua_parser::{LuaAstNode, LuaExpr, LuaStat};
 use lsp_types::{ApplyWorkspaceEditParams, Command, Position, TextEdit, WorkspaceEdit};
+use rowan::TextSize;
 use serde_json::Value;
 
 use crate::{context::ServerContextSnapshot, util::time_cancel_token};
@@ -14,7 +15,7 @@ pub struct AutoRequireCommand;
 impl CommandSpec for AutoRequireCommand {
     const COMMAND: &str = "emmy.auto.require";
 
-    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<()> {
+    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<Value> {
         let add_to: FileId = serde_json::from_value(args.first()?.clone()).ok()?;
         let need_require_file_id: FileId = serde_json::from_value(args.get(1)?.clone()).ok()?;
         let position: Position = serde_json::from_value(args.get(2)?.clone()).ok()?;
@@ -27,52 +28,16 @@ impl CommandSpec for AutoRequireCommand {
             .get_db()
             .get_module_index()
             .get_module(need_require_file_id)?;
-        let emmyrc = semantic_model.get_emmyrc();
-        let require_like_func = &emmyrc.runtime.require_like_function;
-        let auto_require_func = emmyrc.completion.auto_require_function.clone();
-        let require_separator = emmyrc.completion.auto_require_separator.clone();
-        let full_module_path = match require_separator.as_str() {
-            "." | "" => module_info.full_module_name.clone(),
-            _ => module_info
-                .full_module_name
-                .replace(".", &require_separator),
-        };
-
-        let require_str = format!(
-            "local {} = {}(\"{}\"){}",
-            if member_name.is_empty() {
-                local_name
-            } else {
-                member_name.clone()
-            },
-            auto_require_func,
-            full_module_path,
-            if !member_name.is_empty() {
-                format!(".{}", member_name)
-            } else {
-                "".to_string()
-            }
+        let member_name = (!member_name.is_empty()).then_some(member_name.as_str());
+        let require_str = build_require_stat_text(
+            &semantic_model,
+            member_name.unwrap_or(&local_name),
+            &module_info.full_module_name,
+            member_name,
         );
         let document = semantic_model.get_document();
         let offset = document.get_offset(position.line as usize, position.character as usize)?;
-        let root_block = semantic_model.get_root().get_block()?;
-        let mut last_require_stat: Option<LuaStat> = None;
-        for stat in root_block.get_stats() {
-            if stat.get_position() > offset {
-                break;
-            }
-
-            if is_require_stat(stat.clone(), require_like_func).unwrap_or(false) {
-                last_require_stat = Some(stat);
-            }
-        }
-
-        let line = if let Some(last_require_stat) = last_require_stat {
-            let last_require_stat_end = last_require_stat.get_range().end();
-            document.get_line(last_require_stat_end)? + 1
-        } else {
-            0
-        };
+        let line = find_require_insert_line(&semantic_model, offset)?;
 
         let text_edit = TextEdit {
             range: lsp_types::Range {
@@ -116,7 +81,67 @@ impl CommandSpec for AutoRequireCommand {
             }
         });
 
-        Some(())
+        Some(Value::Null)
+    }
+}
+
+/// Builds `local name = require("module")`, using the configured require function and
+/// separator. `member_name` is appended as `require("module").member`.
+pub fn build_require_stat_text(
+    semantic_model: &SemanticModel,
+    local_name: &str,
+    full_module_name: &str,
+    member_name: Option<&str>,
+) -> String {
+    let emmyrc = semantic_model.get_emmyrc();
+    let auto_require_func = &emmyrc.completion.auto_require_function;
+    format!(
+        "local {} = {}(\"{}\"){}",
+        local_name,
+        auto_require_func,
+        format_require_path(semantic_model, full_module_name),
+        member_name
+            .map(|member_name| format!(".{}", member_name))
+            .unwrap_or_default()
+    )
+}
+
+/// Converts a full module name into the path written in `require`, using the configured
+/// separator.
+pub fn format_require_path(semantic_model: &SemanticModel, full_module_name: &str) -> String {
+    let require_separator = &semantic_model
+        .get_emmyrc()
+        .completion
+        .auto_require_separator;
+    match require_separator.as_str() {
+        "." | "" => full_module_name.to_string(),
+        _ => full_module_name.replace(".", require_separator),
+    }
+}
+
+/// Returns the line after the last top-level `require` statement before `offset`, or the first
+/// line when there is none.
+pub fn find_require_insert_line(semantic_model: &SemanticModel, offset: TextSize) -> Option<usize> {
+    let require_like_func = &semantic_model.get_emmyrc().runtime.require_like_function;
+    let document = semantic_model.get_document();
+    let root_block = semantic_model.get_root().get_block()?;
+    let mut last_require_stat: Option<LuaStat> = None;
+    for stat in root_block.get_stats() {
+        if stat.get_position() > offset {
+            break;
+        }
+
+        if is_require_stat(stat.clone(), require_like_func).unwrap_or(false) {
+            last_require_stat = Some(stat);
+        }
+    }
+
+    match last_require_stat {
+        Some(last_require_stat) => {
+            let last_require_stat_end = last_require_stat.get_range().end();
+            Some(document.get_line(last_require_stat_end)? + 1)
+        }
+        None => Some(0),
     }
 }
 
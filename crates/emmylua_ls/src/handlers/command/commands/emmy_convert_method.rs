@@ -0,0 +1,92 @@
+use lsp_types::{ApplyWorkspaceEditParams, Command, MessageType, Position, ShowMessageParams, Uri};
+use serde_json::Value;
+use tokio_util::sync::CancellationToken;
+
+use crate::{context::ServerContextSnapshot, handlers::code_actions::convert_method};
+
+use super::CommandSpec;
+
+/// Converts a method between colon and dot syntax, together with its call sites.
+///
+/// Arguments: the uri of the file and the position of the method definition. Call sites that
+/// cannot be rewritten safely are reported to the user.
+pub struct ConvertMethodCommand;
+
+impl CommandSpec for ConvertMethodCommand {
+    const COMMAND: &str = "emmy.convert.method";
+
+    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<Value> {
+        let uri: Uri = serde_json::from_value(args.first()?.clone()).ok()?;
+        let position: Position = serde_json::from_value(args.get(1)?.clone()).ok()?;
+
+        let analysis = context.analysis().read().await;
+        let file_id = analysis.get_file_id(&uri)?;
+        let result = convert_method(&analysis, file_id, position);
+        drop(analysis);
+
+        let conversion = match result {
+            Ok(conversion) => conversion,
+            Err(err) => {
+                context.client().show_message(ShowMessageParams {
+                    typ: MessageType::ERROR,
+                    message: err,
+                });
+                return Some(Value::Null);
+            }
+        };
+
+        let res = context
+            .client()
+            .apply_edit(
+                ApplyWorkspaceEditParams {
+                    label: None,
+                    edit: conversion.edit,
+                },
+                CancellationToken::new(),
+            )
+            .await;
+        if let Some(res) = res
+            && !res.applied
+        {
+            log::error!("Failed to apply edit: {:?}", res.failure_reason);
+        }
+
+        if !conversion.flagged.is_empty() {
+            let locations = conversion
+                .flagged
+                .iter()
+                .map(|location| {
+                    format!(
+                        "{}:{}",
+                        location.uri.as_str(),
+                        location.range.start.line + 1
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            context.client().show_message(ShowMessageParams {
+                typ: MessageType::WARNING,
+                message: format!(
+                    "{}\n{}",
+                    t!("These call sites were not converted because their receiver may have side effects:"),
+                    locations
+                ),
+            });
+        }
+
+        Some(Value::Null)
+    }
+}
+
+pub fn make_convert_method_command(title: &str, uri: Uri, position: Position) -> Command {
+    let args = vec![
+        serde_json::to_value(uri).unwrap(),
+        serde_json::to_value(position).unwrap(),
+    ];
+
+    Command {
+        title: title.to_string(),
+        command: ConvertMethodCommand::COMMAND.to_string(),
+        arguments: Some(args),
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use emmylua_code_analysis::{EmmyLuaAnalysis, FileId, build_doc_stubs};
+use lsp_types::{ApplyWorkspaceEditParams, TextEdit, Uri, WorkspaceEdit};
+use serde_json::Value;
+use tokio_util::sync::CancellationToken;
+
+use crate::context::ServerContextSnapshot;
+
+use super::CommandSpec;
+
+/// Generates `---@param`/`---@return`/`---@class` stubs for undocumented code and applies them
+/// to the workspace.
+///
+/// Arguments: optionally the uri of the only file to annotate. Without a uri every file of the
+/// main workspace is annotated.
+pub struct GenerateDocStubsCommand;
+
+impl CommandSpec for GenerateDocStubsCommand {
+    const COMMAND: &str = "emmy.generateDocStubs";
+
+    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<Value> {
+        let uri: Option<Uri> = args
+            .first()
+            .and_then(|value| serde_json::from_value(value.clone()).ok());
+
+        let analysis = context.analysis().read().await;
+        let file_ids = match uri {
+            Some(uri) => vec![analysis.get_file_id(&uri)?],
+            None => analysis
+                .compilation
+                .get_db()
+                .get_module_index()
+                .get_main_workspace_file_ids(),
+        };
+        let edit = generate_doc_stubs(&analysis, file_ids);
+        drop(analysis);
+        if edit
+            .changes
+            .as_ref()
+            .is_none_or(|changes| changes.is_empty())
+        {
+            return Some(Value::Null);
+        }
+
+        let res = context
+            .client()
+            .apply_edit(
+                ApplyWorkspaceEditParams { label: None, edit },
+                CancellationToken::new(),
+            )
+            .await;
+        if let Some(res) = res
+            && !res.applied
+        {
+            log::error!("Failed to apply edit: {:?}", res.failure_reason);
+        }
+
+        Some(Value::Null)
+    }
+}
+
+fn generate_doc_stubs(analysis: &EmmyLuaAnalysis, file_ids: Vec<FileId>) -> WorkspaceEdit {
+    #[allow(clippy::mutable_key_type)]
+    let mut changes = HashMap::new();
+    for file_id in file_ids {
+        let Some(semantic_model) = analysis.compilation.get_semantic_model(file_id) else {
+            continue;
+        };
+        let document = semantic_model.get_document();
+        let edits = build_doc_stubs(&semantic_model)
+            .into_iter()
+            .filter_map(|edit| {
+                Some(TextEdit {
+                    range: document.to_lsp_range(edit.range)?,
+                    new_text: edit.new_text,
+                })
+            })
+            .collect::<Vec<_>>();
+        if !edits.is_empty() {
+            changes.insert(document.get_uri(), edits);
+        }
+    }
+
+    WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    }
+}
@@ -10,7 +10,7 @@ impl CommandSpec for FixFormatCommand {
     const COMMAND: &str = "emmy.fix.format";
 
     #[allow(unused)]
-    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<()> {
-        Some(())
+    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<Value> {
+        Some(Value::Null)
     }
 }
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use emmylua_code_analysis::{EmmyLuaAnalysis, FileId, uri_to_file_path};
+use lsp_types::{
+    ApplyWorkspaceEditParams, Command, CreateFile, DocumentChangeOperation, DocumentChanges,
+    MessageActionItem, MessageType, OneOf3, OptionalVersionedTextDocumentIdentifier, Position,
+    ResourceOp, ShowMessageParams, ShowMessageRequestParams, TextDocumentEdit, Uri, WorkspaceEdit,
+};
+use serde_json::Value;
+use tokio_util::sync::CancellationToken;
+
+use crate::{context::ServerContextSnapshot, handlers::code_actions::move_symbol};
+
+use super::CommandSpec;
+
+/// Moves a module member into another module.
+///
+/// Arguments: the uri of the source file, the position of the member definition and
+/// optionally the uri of the target module. A target uri without a file creates a new module.
+/// Without a target the user picks one of the modules nearest to the source module.
+pub struct MoveSymbolCommand;
+
+impl CommandSpec for MoveSymbolCommand {
+    const COMMAND: &str = "emmy.move.symbol";
+
+    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<Value> {
+        let uri: Uri = serde_json::from_value(args.first()?.clone()).ok()?;
+        let position: Position = serde_json::from_value(args.get(1)?.clone()).ok()?;
+        let target_uri: Option<Uri> = args
+            .get(2)
+            .and_then(|value| serde_json::from_value(value.clone()).ok());
+        let file_id = context.analysis().read().await.get_file_id(&uri)?;
+
+        let (target_file_id, is_new_target) = match target_uri {
+            Some(target_uri) => match load_target(&context, &target_uri).await {
+                Some(target) => target,
+                None => {
+                    context.client().show_message(ShowMessageParams {
+                        typ: MessageType::ERROR,
+                        message: "the target file is not part of the workspace".to_string(),
+                    });
+                    return Some(Value::Null);
+                }
+            },
+            None => {
+                let analysis = context.analysis().read().await;
+                let candidates = get_target_candidates(&analysis, file_id);
+                drop(analysis);
+                (pick_target(&context, candidates).await?, false)
+            }
+        };
+
+        move_to(context, uri, position, target_file_id, is_new_target).await
+    }
+}
+
+/// Returns the file of the target module, loading a target uri that does not exist yet as a new
+/// empty module.
+async fn load_target(context: &ServerContextSnapshot, target_uri: &Uri) -> Option<(FileId, bool)> {
+    let mut analysis = context.analysis().write().await;
+    if let Some(file_id) = analysis.get_file_id(target_uri) {
+        return Some((file_id, false));
+    }
+    // 已存在但未加载的文件不在工作区中, 不能作为目标
+    if uri_to_file_path(target_uri)?.exists() {
+        return None;
+    }
+    let file_id = analysis.update_file_by_uri(target_uri, Some(String::new()))?;
+    Some((file_id, true))
+}
+
+async fn move_to(
+    context: ServerContextSnapshot,
+    uri: Uri,
+    position: Position,
+    target_file_id: FileId,
+    is_new_target: bool,
+) -> Option<Value> {
+    let analysis = context.analysis().read().await;
+    let file_id = analysis.get_file_id(&uri)?;
+    let target_uri = analysis.get_uri(target_file_id)?;
+    let result = move_symbol(&analysis, file_id, position, target_file_id);
+    drop(analysis);
+
+    let applied = match result {
+        Ok(edit) => {
+            let edit = if is_new_target {
+                create_target_first(edit, &target_uri)
+            } else {
+                edit
+            };
+            let res = context
+                .client()
+                .apply_edit(
+                    ApplyWorkspaceEditParams { label: None, edit },
+                    CancellationToken::new(),
+                )
+                .await;
+            match res {
+                Some(res) if !res.applied => {
+                    log::error!("Failed to apply edit: {:?}", res.failure_reason);
+                    false
+                }
+                Some(_) => true,
+                None => false,
+            }
+        }
+        Err(err) => {
+            context.client().show_message(ShowMessageParams {
+                typ: MessageType::ERROR,
+                message: err,
+            });
+            false
+        }
+    };
+
+    // 新模块未能创建时移除预先加载的空文件
+    if is_new_target && !applied {
+        context
+            .analysis()
+            .write()
+            .await
+            .update_file_by_uri(&target_uri, None);
+    }
+
+    Some(Value::Null)
+}
+
+/// Creates the new target module before editing it, clients cannot edit a missing file.
+fn create_target_first(edit: WorkspaceEdit, target_uri: &Uri) -> WorkspaceEdit {
+    let mut operations = vec![DocumentChangeOperation::Op(ResourceOp::Create(
+        CreateFile {
+            uri: target_uri.clone(),
+            options: None,
+            annotation_id: None,
+        },
+    ))];
+    for (uri, edits) in edit.changes.unwrap_or_default() {
+        operations.push(DocumentChangeOperation::Edit(TextDocumentEdit {
+            text_document: OptionalVersionedTextDocumentIdentifier { uri, version: None },
+            edits: edits.into_iter().map(OneOf3::Left).collect(),
+        }));
+    }
+
+    WorkspaceEdit {
+        document_changes: Some(DocumentChanges::Operations(operations)),
+        ..Default::default()
+    }
+}
+
+/// Main workspace modules, the ones in the same directory as the source module first.
+fn get_target_candidates(analysis: &EmmyLuaAnalysis, file_id: FileId) -> Vec<(String, FileId)> {
+    let module_index = analysis.compilation.get_db().get_module_index();
+    let Some(source_module) = module_index.get_module(file_id) else {
+        return Vec::new();
+    };
+    let parent = source_module
+        .full_module_name
+        .rsplit_once('.')
+        .map(|(parent, _)| parent)
+        .unwrap_or("");
+    let mut candidates = module_index
+        .get_main_workspace_file_ids()
+        .into_iter()
+        .filter(|candidate| *candidate != file_id)
+        .filter_map(|candidate| {
+            let module_info = module_index.get_module(candidate)?;
+            Some((module_info.full_module_name.clone(), candidate))
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|(name, _)| {
+        let candidate_parent = name
+            .rsplit_once('.')
+            .map(|(parent, _)| parent)
+            .unwrap_or("");
+        (candidate_parent != parent, name.clone())
+    });
+    candidates
+}
+
+async fn pick_target(
+    context: &ServerContextSnapshot,
+    candidates: Vec<(String, FileId)>,
+) -> Option<FileId> {
+    // 消息按钮放不下所有模块, 其他模块需要通过命令参数传入目标 uri
+    const MAX_CANDIDATES: usize = 10;
+    if candidates.is_empty() {
+        return None;
+    }
+    let message = if candidates.len() > MAX_CANDIDATES {
+        t!(
+            "Move to which module? Only the %{shown} nearest of %{total} modules are listed, pass the target uri to the '%{command}' command to choose another module or create a new one.",
+            shown = MAX_CANDIDATES,
+            total = candidates.len(),
+            command = MoveSymbolCommand::COMMAND
+        )
+        .to_string()
+    } else {
+        t!("Move to which module?").to_string()
+    };
+    let params = ShowMessageRequestParams {
+        typ: MessageType::INFO,
+        message,
+        actions: Some(
+            candidates
+                .iter()
+                .take(MAX_CANDIDATES)
+                .map(|(name, _)| MessageActionItem {
+                    title: name.clone(),
+                    properties: HashMap::new(),
+                })
+                .collect(),
+        ),
+    };
+    let selected = context
+        .client()
+        .show_message_request(params, CancellationToken::new())
+        .await?;
+    candidates
+        .into_iter()
+        .find(|(name, _)| *name == selected.title)
+        .map(|(_, file_id)| file_id)
+}
+
+pub fn make_move_symbol_command(title: &str, uri: Uri, position: Position) -> Command {
+    let args = vec![
+        serde_json::to_value(uri).unwrap(),
+        serde_json::to_value(position).unwrap(),
+    ];
+
+    Command {
+        title: title.to_string(),
+        command: MoveSymbolCommand::COMMAND.to_string(),
+        arguments: Some(args),
+    }
+}
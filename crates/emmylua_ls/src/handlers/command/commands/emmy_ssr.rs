@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use emmylua_code_analysis::{EmmyLuaAnalysis, FileId, SsrRule};
+use lsp_types::{
+    ApplyWorkspaceEditParams, MessageType, ShowMessageParams, TextEdit, Uri, WorkspaceEdit,
+};
+use serde_json::Value;
+use tokio_util::sync::CancellationToken;
+
+use crate::context::ServerContextSnapshot;
+
+use super::CommandSpec;
+
+/// Runs a structural search and replace rule and asks the client to apply the result.
+///
+/// Arguments: the rule text, and optionally the uri of the only file to rewrite. Without
+/// a uri every file of the main workspace is rewritten.
+pub struct StructuralReplaceCommand;
+
+impl CommandSpec for StructuralReplaceCommand {
+    const COMMAND: &str = "emmy.ssr";
+
+    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<Value> {
+        let rule: String = serde_json::from_value(args.first()?.clone()).ok()?;
+        let uri: Option<Uri> = args
+            .get(1)
+            .and_then(|value| serde_json::from_value(value.clone()).ok());
+        let rule = match SsrRule::parse(&rule) {
+            Ok(rule) => rule,
+            Err(err) => {
+                context.client().show_message(ShowMessageParams {
+                    typ: MessageType::ERROR,
+                    message: format!("Invalid structural replace rule: {}", err),
+                });
+                return Some(Value::Null);
+            }
+        };
+
+        let analysis = context.analysis().read().await;
+        let file_ids = match uri {
+            Some(uri) => vec![analysis.get_file_id(&uri)?],
+            None => analysis
+                .compilation
+                .get_db()
+                .get_module_index()
+                .get_main_workspace_file_ids(),
+        };
+        let edit = structural_replace(&analysis, &rule, file_ids);
+        drop(analysis);
+
+        let res = context
+            .client()
+            .apply_edit(
+                ApplyWorkspaceEditParams { label: None, edit },
+                CancellationToken::new(),
+            )
+            .await;
+        if let Some(res) = res
+            && !res.applied
+        {
+            log::error!("Failed to apply edit: {:?}", res.failure_reason);
+        }
+
+        Some(Value::Null)
+    }
+}
+
+fn structural_replace(
+    analysis: &EmmyLuaAnalysis,
+    rule: &SsrRule,
+    file_ids: Vec<FileId>,
+) -> WorkspaceEdit {
+    #[allow(clippy::mutable_key_type)]
+    let mut changes = HashMap::new();
+    for file_id in file_ids {
+        let Some(semantic_model) = analysis.compilation.get_semantic_model(file_id) else {
+            continue;
+        };
+        let document = semantic_model.get_document();
+        let edits = rule
+            .replace(&semantic_model)
+            .into_iter()
+            .filter_map(|(range, new_text)| {
+                Some(TextEdit {
+                    range: document.to_lsp_range(range)?,
+                    new_text,
+                })
+            })
+            .collect::<Vec<_>>();
+        if !edits.is_empty() {
+            changes.insert(document.get_uri(), edits);
+        }
+    }
+
+    WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    }
+}
@@ -2,25 +2,37 @@ use std::sync::LazyLock;
 
 use emmy_add_doc_tag::AddDocTagCommand;
 use emmy_auto_require::AutoRequireCommand;
+use emmy_convert_method::ConvertMethodCommand;
 use emmy_disable_code::DisableCodeCommand;
+use emmy_doc_stubs::GenerateDocStubsCommand;
 use emmy_fix_format::FixFormatCommand;
+use emmy_move_symbol::MoveSymbolCommand;
+use emmy_ssr::StructuralReplaceCommand;
 use serde_json::Value;
 
 use crate::context::ServerContextSnapshot;
 
 mod emmy_add_doc_tag;
 mod emmy_auto_require;
+mod emmy_convert_method;
 mod emmy_disable_code;
+mod emmy_doc_stubs;
 mod emmy_fix_format;
+mod emmy_move_symbol;
+mod emmy_ssr;
 
 pub use emmy_add_doc_tag::make_auto_doc_tag_command;
-pub use emmy_auto_require::make_auto_require;
+pub use emmy_auto_require::{
+    build_require_stat_text, find_require_insert_line, format_require_path, make_auto_require,
+};
+pub use emmy_convert_method::make_convert_method_command;
 pub use emmy_disable_code::{DisableAction, make_disable_code_command};
+pub use emmy_move_symbol::make_move_symbol_command;
 
 pub trait CommandSpec {
     const COMMAND: &str;
 
-    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<()>;
+    async fn handle(context: ServerContextSnapshot, args: Vec<Value>) -> Option<Value>;
 }
 
 static COMMANDS: LazyLock<Vec<String>> = LazyLock::new(|| {
@@ -29,6 +41,10 @@ static COMMANDS: LazyLock<Vec<String>> = LazyLock::new(|| {
         DisableCodeCommand::COMMAND.to_string(),
         FixFormatCommand::COMMAND.to_string(),
         AddDocTagCommand::COMMAND.to_string(),
+        StructuralReplaceCommand::COMMAND.to_string(),
+        MoveSymbolCommand::COMMAND.to_string(),
+        ConvertMethodCommand::COMMAND.to_string(),
+        GenerateDocStubsCommand::COMMAND.to_string(),
     ]
 });
 
@@ -40,12 +56,16 @@ pub async fn dispatch_command(
     context: ServerContextSnapshot,
     command_name: &str,
     args: Vec<Value>,
-) -> Option<()> {
+) -> Option<Value> {
     match command_name {
         AutoRequireCommand::COMMAND => AutoRequireCommand::handle(context, args).await,
         DisableCodeCommand::COMMAND => DisableCodeCommand::handle(context, args).await,
         FixFormatCommand::COMMAND => FixFormatCommand::handle(context, args).await,
         AddDocTagCommand::COMMAND => AddDocTagCommand::handle(context, args).await,
-        _ => Some(()),
+        StructuralReplaceCommand::COMMAND => StructuralReplaceCommand::handle(context, args).await,
+        MoveSymbolCommand::COMMAND => MoveSymbolCommand::handle(context, args).await,
+        ConvertMethodCommand::COMMAND => ConvertMethodCommand::handle(context, args).await,
+        GenerateDocStubsCommand::COMMAND => GenerateDocStubsCommand::handle(context, args).await,
+        _ => None,
     }
 }
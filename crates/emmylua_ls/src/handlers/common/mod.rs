@@ -1,6 +1,8 @@
 mod find_origin;
+mod scope_label;
 
 pub(crate) use find_origin::{
     find_all_same_named_members, find_decl_origin_owners, find_member_origin_owner,
     find_member_origin_owners,
 };
+pub(crate) use scope_label::get_scope_label;
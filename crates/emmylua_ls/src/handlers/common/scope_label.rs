@@ -0,0 +1,130 @@
+use emmylua_parser::{
+    LuaAssignStat, LuaAstNode, LuaClosureExpr, LuaExpr, LuaFuncStat, LuaIfClauseStat,
+    LuaLocalFuncStat, LuaLocalStat, LuaStat, LuaSyntaxNode, LuaTableField,
+};
+
+const MAX_SCOPE_LABEL_LEN: usize = 40;
+
+/// Returns a short description of the statement or closure that opens a block, such as
+/// `function Player:update` or `if state == "idle"`.
+pub fn get_scope_label(node: &LuaSyntaxNode) -> Option<String> {
+    if let Some(closure) = LuaClosureExpr::cast(node.clone()) {
+        return get_closure_label(&closure);
+    }
+    if let Some(clause) = LuaIfClauseStat::cast(node.clone()) {
+        return Some(truncate_label(&match clause {
+            LuaIfClauseStat::ElseIf(else_if) => format!(
+                "elseif {}",
+                expr_text(else_if.get_condition_expr()).unwrap_or_default()
+            ),
+            LuaIfClauseStat::Else(_) => "else".to_string(),
+        }));
+    }
+
+    let label = match LuaStat::cast(node.clone())? {
+        LuaStat::FuncStat(func_stat) => get_func_stat_label(&func_stat)?,
+        LuaStat::LocalFuncStat(local_func_stat) => get_local_func_stat_label(&local_func_stat)?,
+        LuaStat::IfStat(if_stat) => format!(
+            "if {}",
+            expr_text(if_stat.get_condition_expr()).unwrap_or_default()
+        ),
+        LuaStat::WhileStat(while_stat) => format!(
+            "while {}",
+            expr_text(while_stat.get_condition_expr()).unwrap_or_default()
+        ),
+        LuaStat::RepeatStat(_) => "repeat".to_string(),
+        LuaStat::DoStat(_) => "do".to_string(),
+        LuaStat::ForStat(for_stat) => format!(
+            "for {} = {}",
+            for_stat.get_var_name()?.get_name_text(),
+            join_exprs(for_stat.get_iter_expr())
+        ),
+        LuaStat::ForRangeStat(for_range_stat) => format!(
+            "for {} in {}",
+            for_range_stat
+                .get_var_name_list()
+                .map(|name| name.get_name_text().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            join_exprs(for_range_stat.get_expr_list())
+        ),
+        _ => return None,
+    };
+
+    Some(truncate_label(label.trim_end()))
+}
+
+fn get_func_stat_label(func_stat: &LuaFuncStat) -> Option<String> {
+    let func_name = func_stat.get_func_name()?;
+    Some(format!(
+        "function {}",
+        normalize_text(&func_name.syntax().text().to_string())
+    ))
+}
+
+fn get_local_func_stat_label(local_func_stat: &LuaLocalFuncStat) -> Option<String> {
+    let name = local_func_stat.get_local_name()?.get_name_token()?;
+    Some(format!("local function {}", name.get_name_text()))
+}
+
+fn get_closure_label(closure: &LuaClosureExpr) -> Option<String> {
+    let parent = closure.syntax().parent()?;
+    if let Some(func_stat) = LuaFuncStat::cast(parent.clone()) {
+        return get_func_stat_label(&func_stat);
+    }
+    if let Some(local_func_stat) = LuaLocalFuncStat::cast(parent.clone()) {
+        return get_local_func_stat_label(&local_func_stat);
+    }
+
+    let closure_expr = LuaExpr::ClosureExpr(closure.clone());
+    let name = if let Some(local_stat) = LuaLocalStat::cast(parent.clone()) {
+        let index = local_stat
+            .get_value_exprs()
+            .position(|expr| expr == closure_expr)?;
+        local_stat
+            .get_local_name_list()
+            .nth(index)
+            .and_then(|name| name.get_name_token())
+            .map(|token| token.get_name_text().to_string())
+    } else if let Some(assign_stat) = LuaAssignStat::cast(parent.clone()) {
+        let (vars, exprs) = assign_stat.get_var_and_expr_list();
+        let index = exprs.iter().position(|expr| *expr == closure_expr)?;
+        vars.get(index)
+            .map(|var| normalize_text(&var.syntax().text().to_string()))
+    } else {
+        LuaTableField::cast(parent)
+            .and_then(|field| field.get_field_key())
+            .map(|key| key.get_path_part())
+    };
+
+    Some(truncate_label(&match name {
+        Some(name) => format!("function {}", name),
+        None => "function".to_string(),
+    }))
+}
+
+fn expr_text(expr: Option<LuaExpr>) -> Option<String> {
+    expr.map(|expr| normalize_text(&expr.syntax().text().to_string()))
+}
+
+fn join_exprs(exprs: impl Iterator<Item = LuaExpr>) -> String {
+    exprs
+        .map(|expr| normalize_text(&expr.syntax().text().to_string()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Collapses line breaks and indentation of multi-line expressions.
+fn normalize_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn truncate_label(label: &str) -> String {
+    if label.chars().count() <= MAX_SCOPE_LABEL_LEN {
+        return label.to_string();
+    }
+
+    let mut truncated: String = label.chars().take(MAX_SCOPE_LABEL_LEN).collect();
+    truncated.push_str("...");
+    truncated
+}
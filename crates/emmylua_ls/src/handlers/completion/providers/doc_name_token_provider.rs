@@ -249,6 +249,7 @@ enum TypeFlagCompletion {
     Partial,
     Exact,
     Constructor,
+    Opaque,
     Public,
     Internal,
     File,
@@ -262,6 +263,7 @@ impl TypeFlagCompletion {
             Self::Partial,
             Self::Exact,
             Self::Constructor,
+            Self::Opaque,
             Self::Public,
             Self::Internal,
             Self::File,
@@ -276,6 +278,7 @@ impl TypeFlagCompletion {
             Self::Partial => LuaTypeFlag::Partial,
             Self::Exact => LuaTypeFlag::Exact,
             Self::Constructor => LuaTypeFlag::Constructor,
+            Self::Opaque => LuaTypeFlag::Opaque,
             Self::Public => LuaTypeFlag::Public,
             Self::Internal => LuaTypeFlag::Internal,
             Self::File | Self::Private => LuaTypeFlag::File,
@@ -288,6 +291,7 @@ impl TypeFlagCompletion {
             Self::Partial => "partial",
             Self::Exact => "exact",
             Self::Constructor => "constructor",
+            Self::Opaque => "opaque",
             Self::Public => "public",
             Self::Internal => "internal",
             Self::File => "file",
@@ -316,6 +320,7 @@ fn add_tag_type_flag_completion(
             TypeFlagCompletion::Internal,
             TypeFlagCompletion::Exact,
             TypeFlagCompletion::Constructor,
+            TypeFlagCompletion::Opaque,
             TypeFlagCompletion::File,
             TypeFlagCompletion::Public,
             TypeFlagCompletion::Private,
@@ -2,7 +2,7 @@ use emmylua_code_analysis::{
     DbIndex, GenericTpl, InferGuard, InferGuardRef, LuaAliasCallKind, LuaAliasCallType,
     LuaDeclLocation, LuaFunctionType, LuaMemberKey, LuaMemberOwner, LuaMultiLineUnion,
     LuaStringTplType, LuaType, LuaTypeCache, LuaTypeDeclId, LuaUnionType, RenderLevel,
-    filter_callable_overloads, get_real_type,
+    TypeSubstitutor, filter_callable_overloads, get_real_type, instantiate_type_generic,
 };
 use emmylua_parser::{
     LuaAssignStat, LuaAst, LuaAstNode, LuaAstToken, LuaCallArgList, LuaCallExpr, LuaClosureExpr,
@@ -153,6 +153,9 @@ pub fn dispatch_type(
             return add_tpl_ref_completion(builder, &tpl, infer_guard);
         }
         LuaType::Call(special_call) => {
+            if let Some(resolved) = resolve_string_literal_call(builder, &special_call) {
+                return dispatch_type(builder, resolved, infer_guard);
+            }
             add_special_call_completion(builder, &special_call);
         }
         _ => {}
@@ -161,6 +164,34 @@ pub fn dispatch_type(
     Some(ProviderDecision::Continue)
 }
 
+// 模板字面量与 Uppercase 等字符串操作, 能展开为字面量联合时按联合补全
+fn resolve_string_literal_call(
+    builder: &CompletionBuilder,
+    alias_call: &LuaAliasCallType,
+) -> Option<LuaType> {
+    if !matches!(
+        alias_call.get_call_kind(),
+        LuaAliasCallKind::Template
+            | LuaAliasCallKind::Uppercase
+            | LuaAliasCallKind::Lowercase
+            | LuaAliasCallKind::Capitalize
+    ) || alias_call.contain_tpl()
+    {
+        return None;
+    }
+
+    let typ = LuaType::Call(alias_call.clone().into());
+    let resolved = instantiate_type_generic(
+        builder.semantic_model.get_db(),
+        &typ,
+        &TypeSubstitutor::new(),
+    );
+    match resolved {
+        LuaType::Union(_) | LuaType::DocStringConst(_) => Some(resolved),
+        _ => None,
+    }
+}
+
 fn add_type_ref_completion(
     builder: &mut CompletionBuilder,
     type_ref_id: LuaTypeDeclId,
@@ -17,7 +17,8 @@ use lsp_types::{
 use stats::{
     IfSymbolContext, build_assign_stat_symbol, build_do_stat_symbol, build_for_range_stat_symbol,
     build_for_stat_symbol, build_func_stat_symbol, build_if_stat_symbol,
-    build_local_func_stat_symbol, build_local_stat_symbol,
+    build_local_func_stat_symbol, build_local_stat_symbol, build_repeat_stat_symbol,
+    build_while_stat_symbol,
 };
 use tokio_util::sync::CancellationToken;
 
@@ -42,7 +43,7 @@ pub async fn on_document_symbol(
     Some(response)
 }
 
-fn build_document_symbol(semantic_model: &SemanticModel) -> Option<DocumentSymbol> {
+pub fn build_document_symbol(semantic_model: &SemanticModel) -> Option<DocumentSymbol> {
     let document = semantic_model.get_document();
     let root = semantic_model.get_root();
     let file_id = semantic_model.get_file_id();
@@ -186,19 +187,21 @@ fn process_stat(
             process_if_clauses(builder, ctx)?;
         }
         LuaStat::WhileStat(while_stat) => {
+            let while_id = build_while_stat_symbol(builder, while_stat.clone(), parent_id)?;
             if let Some(condition) = while_stat.get_condition_expr() {
-                process_expr(builder, condition, parent_id, false)?;
+                process_expr(builder, condition, while_id, false)?;
             }
             if let Some(block) = while_stat.get_block() {
-                process_block(builder, block, parent_id)?;
+                process_block(builder, block, while_id)?;
             }
         }
         LuaStat::RepeatStat(repeat_stat) => {
+            let repeat_id = build_repeat_stat_symbol(builder, repeat_stat.clone(), parent_id)?;
             if let Some(block) = repeat_stat.get_block() {
-                process_block(builder, block, parent_id)?;
+                process_block(builder, block, repeat_id)?;
             }
             if let Some(condition) = repeat_stat.get_condition_expr() {
-                process_expr(builder, condition, parent_id, false)?;
+                process_expr(builder, condition, repeat_id, false)?;
             }
         }
         LuaStat::DoStat(do_stat) => {
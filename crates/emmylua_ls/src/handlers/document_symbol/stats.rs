@@ -1,11 +1,13 @@
 use emmylua_code_analysis::{LuaDeclId, LuaSignatureId, LuaType};
 use emmylua_parser::{
     LuaAssignStat, LuaAstNode, LuaAstToken, LuaDoStat, LuaExpr, LuaForRangeStat, LuaForStat,
-    LuaFuncStat, LuaIfClauseStat, LuaIfStat, LuaLocalFuncStat, LuaLocalStat, LuaSyntaxId,
+    LuaFuncStat, LuaIfClauseStat, LuaIfStat, LuaLocalFuncStat, LuaLocalStat, LuaRepeatStat,
+    LuaSyntaxId, LuaWhileStat,
 };
 use lsp_types::SymbolKind;
 
 use super::builder::{DocumentSymbolBuilder, LuaSymbol};
+use crate::handlers::common::get_scope_label;
 
 #[derive(Clone)]
 pub struct SymbolBinding {
@@ -96,7 +98,7 @@ pub fn build_for_stat_symbol(
 ) -> Option<LuaSyntaxId> {
     let file_id = builder.get_file_id();
     let for_symbol = LuaSymbol::new(
-        "for".to_string(),
+        get_scope_label(for_stat.syntax()).unwrap_or_else(|| "for".to_string()),
         None,
         SymbolKind::MODULE,
         for_stat.get_range(),
@@ -127,7 +129,7 @@ pub fn build_for_range_stat_symbol(
 ) -> Option<LuaSyntaxId> {
     let file_id = builder.get_file_id();
     let for_symbol = LuaSymbol::new(
-        "for in".to_string(),
+        get_scope_label(for_range_stat.syntax()).unwrap_or_else(|| "for in".to_string()),
         None,
         SymbolKind::MODULE,
         for_range_stat.get_range(),
@@ -210,7 +212,7 @@ pub fn build_if_stat_symbol(
     parent_id: LuaSyntaxId,
 ) -> Option<IfSymbolContext> {
     let if_symbol = LuaSymbol::new(
-        "if".to_string(),
+        get_scope_label(if_stat.syntax()).unwrap_or_else(|| "if".to_string()),
         None,
         SymbolKind::MODULE,
         if_stat.get_range(),
@@ -220,17 +222,12 @@ pub fn build_if_stat_symbol(
     let mut clause_symbols = Vec::new();
 
     for branch in if_stat.get_all_clause() {
-        let name = match &branch {
-            LuaIfClauseStat::Else(_) => "else",
-            LuaIfClauseStat::ElseIf(_) => "elseif",
-        };
+        let name = get_scope_label(branch.syntax()).unwrap_or_else(|| match &branch {
+            LuaIfClauseStat::Else(_) => "else".to_string(),
+            LuaIfClauseStat::ElseIf(_) => "elseif".to_string(),
+        });
 
-        let symbol = LuaSymbol::new(
-            name.to_string(),
-            None,
-            SymbolKind::MODULE,
-            branch.get_range(),
-        );
+        let symbol = LuaSymbol::new(name, None, SymbolKind::MODULE, branch.get_range());
 
         let clause_id = builder.add_node_symbol(branch.syntax().clone(), symbol, Some(if_id));
         clause_symbols.push((branch, clause_id));
@@ -257,3 +254,35 @@ pub fn build_do_stat_symbol(
     let do_id = builder.add_node_symbol(do_stat.syntax().clone(), symbol, Some(parent_id));
     Some(do_id)
 }
+
+pub fn build_while_stat_symbol(
+    builder: &mut DocumentSymbolBuilder,
+    while_stat: LuaWhileStat,
+    parent_id: LuaSyntaxId,
+) -> Option<LuaSyntaxId> {
+    let symbol = LuaSymbol::new(
+        get_scope_label(while_stat.syntax()).unwrap_or_else(|| "while".to_string()),
+        None,
+        SymbolKind::MODULE,
+        while_stat.get_range(),
+    );
+
+    let while_id = builder.add_node_symbol(while_stat.syntax().clone(), symbol, Some(parent_id));
+    Some(while_id)
+}
+
+pub fn build_repeat_stat_symbol(
+    builder: &mut DocumentSymbolBuilder,
+    repeat_stat: LuaRepeatStat,
+    parent_id: LuaSyntaxId,
+) -> Option<LuaSyntaxId> {
+    let symbol = LuaSymbol::new(
+        "repeat".to_string(),
+        None,
+        SymbolKind::MODULE,
+        repeat_stat.get_range(),
+    );
+
+    let repeat_id = builder.add_node_symbol(repeat_stat.syntax().clone(), symbol, Some(parent_id));
+    Some(repeat_id)
+}
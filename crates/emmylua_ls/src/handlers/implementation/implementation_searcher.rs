@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use emmylua_code_analysis::{
-    LuaCompilation, LuaDeclId, LuaMemberId, LuaSemanticDeclId, LuaType, LuaTypeDeclId,
-    SemanticDeclLevel, SemanticModel,
+    DbIndex, LuaCompilation, LuaDeclId, LuaMemberId, LuaMemberKey, LuaMemberOwner,
+    LuaSemanticDeclId, LuaType, LuaTypeDeclId, SemanticDeclLevel, SemanticModel,
 };
 use emmylua_parser::{
     LuaAstNode, LuaDocTagField, LuaExpr, LuaIndexExpr, LuaStat, LuaSyntaxNode, LuaSyntaxToken,
@@ -262,3 +262,56 @@ pub fn search_decl_implementations(
 
     Some(())
 }
+
+/// 查找类型的所有子类, 包括间接继承的子类
+pub fn find_sub_type_ids(db: &DbIndex, type_decl_id: &LuaTypeDeclId) -> Vec<LuaTypeDeclId> {
+    let mut visited = HashSet::new();
+    db.get_type_index()
+        .get_all_sub_types(type_decl_id)
+        .into_iter()
+        .map(|sub_type| sub_type.get_id())
+        .filter(|sub_type_id| visited.insert(sub_type_id.clone()))
+        .collect()
+}
+
+pub fn search_sub_type_implementations(
+    semantic_model: &SemanticModel,
+    type_decl_id: &LuaTypeDeclId,
+    result: &mut Vec<Location>,
+) -> Option<()> {
+    let db = semantic_model.get_db();
+    for sub_type_id in find_sub_type_ids(db, type_decl_id) {
+        let Some(location) = db
+            .get_type_index()
+            .get_type_decl(&sub_type_id)
+            .and_then(|sub_type| sub_type.get_locations().first().cloned())
+        else {
+            continue;
+        };
+        if let Some(location) = semantic_model
+            .get_document_by_file_id(location.file_id)
+            .and_then(|document| document.to_lsp_location(location.range))
+        {
+            result.push(location);
+        }
+    }
+
+    Some(())
+}
+
+/// 查找所有子类中重写了指定成员的成员, 每个子类只取一个
+pub fn find_override_member_ids(
+    db: &DbIndex,
+    type_decl_id: &LuaTypeDeclId,
+    member_key: &LuaMemberKey,
+) -> Vec<LuaMemberId> {
+    let member_index = db.get_member_index();
+    find_sub_type_ids(db, type_decl_id)
+        .into_iter()
+        .filter_map(|sub_type_id| {
+            member_index
+                .get_member_item(&LuaMemberOwner::Type(sub_type_id), member_key)
+                .and_then(|item| item.get_member_ids().into_iter().next())
+        })
+        .collect()
+}
@@ -4,6 +4,9 @@ use crate::context::ServerContextSnapshot;
 use emmylua_code_analysis::{EmmyLuaAnalysis, FileId};
 use emmylua_parser::LuaAstNode;
 use implementation_searcher::search_implementations;
+pub use implementation_searcher::{
+    find_override_member_ids, find_sub_type_ids, search_sub_type_implementations,
+};
 use lsp_types::{
     ClientCapabilities, GotoDefinitionResponse, ImplementationProviderCapability, Position,
     ServerCapabilities, request::GotoImplementationParams,
@@ -8,7 +8,7 @@ use crate::{
     cmd_args::CmdArgs,
     context::{
         FileDiagnostic, LspFeatures, ProgressTask, ServerContextSnapshot, StatusBar, get_client_id,
-        load_emmy_config,
+        load_emmy_config, load_scoped_emmy_configs,
     },
     handlers::{
         initialized::std_i18n::try_generate_translated_std, text_document::register_files_watch,
@@ -18,7 +18,7 @@ use crate::{
 pub use client_config::{ClientConfig, get_client_config};
 use emmylua_code_analysis::{
     EmmyLuaAnalysis, Emmyrc, WorkspaceFolder, build_workspace_folders, collect_workspace_files,
-    uri_to_file_path,
+    extend_scoped_library_folders, uri_to_file_path,
 };
 use lsp_types::InitializeParams;
 use tokio::sync::RwLock;
@@ -66,14 +66,26 @@ pub async fn initialized_handler(
     let params_json = serde_json::to_string_pretty(&params).unwrap();
     log::info!("initialization_params: {}", params_json);
 
-    // init config
-    // todo! support multi config
+    // init config, the config of the first folder applies to the whole workspace unless a
+    // directory has its own config file
     let config_root: Option<PathBuf> = main_root.map(PathBuf::from);
 
-    let emmyrc = load_emmy_config(config_root, client_config.clone());
+    let emmyrc = load_emmy_config(config_root.clone(), client_config.clone());
+    let scoped_emmyrcs = load_scoped_emmy_configs(
+        &workspace_folders,
+        &emmyrc,
+        config_root.as_deref(),
+        &client_config,
+    );
 
     // init std lib
-    init_std_lib(context.analysis(), &cmd_args, emmyrc.clone()).await;
+    init_std_lib(
+        context.analysis(),
+        &cmd_args,
+        emmyrc.clone(),
+        scoped_emmyrcs.clone(),
+    )
+    .await;
 
     {
         let mut workspace_manager = context.workspace_manager().write().await;
@@ -89,6 +101,7 @@ pub async fn initialized_handler(
         context.lsp_features(),
         workspace_folders,
         emmyrc.clone(),
+        scoped_emmyrcs,
         Vec::new(),
     )
     .await;
@@ -104,12 +117,14 @@ pub async fn init_analysis(
     lsp_features: &LspFeatures,
     workspace_folders: Vec<WorkspaceFolder>,
     emmyrc: Arc<Emmyrc>,
+    scoped_emmyrcs: Vec<(PathBuf, Arc<Emmyrc>)>,
     open_files: Vec<(lsp_types::Uri, String)>,
 ) {
     let mut mut_analysis = analysis.write().await;
 
     // update config
     mut_analysis.update_config(emmyrc.clone());
+    mut_analysis.update_scoped_configs(scoped_emmyrcs.clone());
 
     if let Ok(emmyrc_json) = serde_json::to_string_pretty(emmyrc.as_ref()) {
         log::info!("current config : {}", emmyrc_json);
@@ -124,7 +139,8 @@ pub async fn init_analysis(
         Some("Loading workspace files".to_string()),
     );
 
-    let workspace_folders = build_workspace_folders(&workspace_folders, emmyrc.as_ref());
+    let mut workspace_folders = build_workspace_folders(&workspace_folders, emmyrc.as_ref());
+    extend_scoped_library_folders(&mut workspace_folders, &scoped_emmyrcs);
     for workspace in &workspace_folders {
         if workspace.is_library {
             log::info!("add library workspace: {:?}", workspace);
@@ -208,6 +224,7 @@ pub async fn init_std_lib(
     analysis: &RwLock<EmmyLuaAnalysis>,
     cmd_args: &CmdArgs,
     emmyrc: Arc<Emmyrc>,
+    scoped_emmyrcs: Vec<(PathBuf, Arc<Emmyrc>)>,
 ) {
     log::info!(
         "initializing std lib with resources path: {:?}",
@@ -217,6 +234,7 @@ pub async fn init_std_lib(
     if cmd_args.load_stdlib.0 {
         // double update config
         analysis.update_config(emmyrc);
+        analysis.update_scoped_configs(scoped_emmyrcs);
         try_generate_translated_std();
         analysis.init_std_lib(cmd_args.resources_path.0.clone());
     }
@@ -3,13 +3,13 @@ use std::sync::Arc;
 
 use emmylua_code_analysis::{
     AsyncState, FileId, InferGuard, LuaFunctionType, LuaMember, LuaMemberId, LuaMemberKey,
-    LuaMemberOwner, LuaOperatorId, LuaOperatorMetaMethod, LuaSemanticDeclId, LuaType, LuaTypeDecl,
-    SemanticModel,
+    LuaMemberOwner, LuaOperatorId, LuaOperatorMetaMethod, LuaSemanticDeclId, LuaSignatureId,
+    LuaType, LuaTypeDecl, RenderLevel, SemanticModel, humanize_type,
 };
 use emmylua_parser::{
-    LuaAst, LuaAstNode, LuaCallExpr, LuaExpr, LuaFuncStat, LuaIndexExpr, LuaIndexKey,
-    LuaLiteralToken, LuaLocalFuncStat, LuaLocalName, LuaLocalStat, LuaStat, LuaSyntaxId,
-    LuaVarExpr,
+    LuaAssignStat, LuaAst, LuaAstNode, LuaCallExpr, LuaClosureExpr, LuaExpr, LuaFuncStat,
+    LuaIndexExpr, LuaIndexKey, LuaLiteralToken, LuaLocalFuncStat, LuaLocalName, LuaLocalStat,
+    LuaStat, LuaSyntaxId, LuaSyntaxKind, LuaSyntaxNode, LuaTableField, LuaVarExpr,
 };
 use emmylua_parser::{LuaAstToken, LuaTokenKind};
 use lsp_types::{InlayHint, InlayHintKind, InlayHintLabel, InlayHintLabelPart, Location};
@@ -18,6 +18,7 @@ use rowan::NodeOrToken;
 use rowan::TokenAtOffset;
 
 use crate::context::ClientId;
+use crate::handlers::common::get_scope_label;
 use crate::handlers::completion::get_index_alias_name;
 use crate::handlers::definition::compare_function_types;
 use crate::handlers::inlay_hint::build_function_hint::{build_closure_hint, build_label_parts};
@@ -31,12 +32,23 @@ pub fn build_inlay_hints(
     for node in root.clone().descendants::<LuaAst>() {
         match node {
             LuaAst::LuaClosureExpr(closure) => {
+                build_block_end_hint(semantic_model, &mut result, closure.syntax());
+                build_self_hint(semantic_model, &mut result, closure.clone());
                 build_closure_hint(semantic_model, &mut result, closure);
             }
+            LuaAst::LuaIfStat(_)
+            | LuaAst::LuaWhileStat(_)
+            | LuaAst::LuaRepeatStat(_)
+            | LuaAst::LuaDoStat(_)
+            | LuaAst::LuaForStat(_)
+            | LuaAst::LuaForRangeStat(_) => {
+                build_block_end_hint(semantic_model, &mut result, node.syntax());
+            }
             LuaAst::LuaCallExpr(call_expr) => {
                 build_call_expr_param_hint(semantic_model, &mut result, call_expr.clone());
                 build_call_expr_await_hint(semantic_model, &mut result, call_expr.clone());
                 build_call_expr_meta_call_hint(semantic_model, &mut result, call_expr.clone());
+                build_chain_call_hint(semantic_model, &mut result, call_expr.clone());
                 build_enum_param_hint(semantic_model, &mut result, call_expr);
             }
             LuaAst::LuaLocalName(local_name) => {
@@ -51,6 +63,12 @@ pub fn build_inlay_hints(
             LuaAst::LuaIndexExpr(index_expr) => {
                 build_index_expr_hint(semantic_model, &mut result, index_expr);
             }
+            LuaAst::LuaBinaryExpr(binary_expr) => {
+                build_const_value_hint(semantic_model, &mut result, binary_expr.into());
+            }
+            LuaAst::LuaUnaryExpr(unary_expr) => {
+                build_const_value_hint(semantic_model, &mut result, unary_expr.into());
+            }
             _ => {}
         }
     }
@@ -683,6 +701,253 @@ fn build_index_expr_hint(
     Some(())
 }
 
+fn build_const_value_hint(
+    semantic_model: &SemanticModel,
+    result: &mut Vec<InlayHint>,
+    expr: LuaExpr,
+) -> Option<()> {
+    if !semantic_model.get_emmyrc().hint.const_value_hint {
+        return Some(());
+    }
+
+    // 只处理赋值语句和表字段的值
+    let parent = expr.syntax().parent()?;
+    if !LuaLocalStat::can_cast(parent.kind().into())
+        && !LuaAssignStat::can_cast(parent.kind().into())
+        && !LuaTableField::can_cast(parent.kind().into())
+    {
+        return Some(());
+    }
+
+    let typ = semantic_model.infer_expr(expr.clone()).ok()?;
+    let value = match &typ {
+        LuaType::IntegerConst(i) => i.to_string(),
+        LuaType::FloatConst(f) if f.fract() == 0.0 => format!("{:.1}", f),
+        LuaType::FloatConst(f) => f.to_string(),
+        // 与 hover 一致, 按 Lua 字符串的转义显示
+        LuaType::StringConst(_) => {
+            humanize_type(semantic_model.get_db(), &typ, RenderLevel::Detailed)
+        }
+        _ => return Some(()),
+    };
+    // `-1` 之类的表达式不需要提示
+    if value == expr.syntax().text().to_string() {
+        return Some(());
+    }
+
+    let document = semantic_model.get_document();
+    let lsp_range = document.to_lsp_range(expr.get_range())?;
+    let hint = InlayHint {
+        kind: None,
+        label: InlayHintLabel::String(format!("= {}", value)),
+        position: lsp_range.end,
+        text_edits: None,
+        tooltip: None,
+        padding_left: Some(true),
+        padding_right: None,
+        data: None,
+    };
+
+    result.push(hint);
+    Some(())
+}
+
+fn build_block_end_hint(
+    semantic_model: &SemanticModel,
+    result: &mut Vec<InlayHint>,
+    node: &LuaSyntaxNode,
+) -> Option<()> {
+    let hint_config = &semantic_model.get_emmyrc().hint;
+    if !hint_config.block_end_hint {
+        return Some(());
+    }
+
+    // 只有语句完整时才提示
+    let end_kind = if node.kind() == LuaSyntaxKind::RepeatStat.into() {
+        LuaTokenKind::TkUntil
+    } else {
+        LuaTokenKind::TkEnd
+    };
+    if !node
+        .children_with_tokens()
+        .any(|child| child.kind() == end_kind.into())
+    {
+        return Some(());
+    }
+
+    let document = semantic_model.get_document();
+    let lsp_range = document.to_lsp_range(node.text_range())?;
+    let line_count = (lsp_range.end.line - lsp_range.start.line + 1) as usize;
+    if line_count < hint_config.block_end_hint_min_lines {
+        return Some(());
+    }
+
+    let label = get_scope_label(node)?;
+    let hint = InlayHint {
+        kind: None,
+        label: InlayHintLabel::String(format!("-- {}", label)),
+        // repeat 语句的末尾即 until 条件的末尾
+        position: lsp_range.end,
+        text_edits: None,
+        tooltip: None,
+        padding_left: Some(true),
+        padding_right: None,
+        data: None,
+    };
+
+    result.push(hint);
+    Some(())
+}
+
+fn build_chain_call_hint(
+    semantic_model: &SemanticModel,
+    result: &mut Vec<InlayHint>,
+    call_expr: LuaCallExpr,
+) -> Option<()> {
+    if !semantic_model.get_emmyrc().hint.chain_call_hint {
+        return Some(());
+    }
+
+    if !is_in_multi_line_chain(semantic_model, &call_expr) {
+        return Some(());
+    }
+
+    let typ = semantic_model.infer_expr(call_expr.clone().into()).ok()?;
+    if typ.is_unknown() || typ.is_any() || typ.is_nil() {
+        return Some(());
+    }
+
+    let label_parts = build_label_parts(semantic_model, &typ);
+    if label_parts.is_empty() {
+        return Some(());
+    }
+
+    let document = semantic_model.get_document();
+    let lsp_range = document.to_lsp_range(call_expr.get_range())?;
+    let hint = InlayHint {
+        kind: Some(InlayHintKind::TYPE),
+        label: InlayHintLabel::LabelParts(label_parts),
+        position: lsp_range.end,
+        text_edits: None,
+        tooltip: None,
+        padding_left: Some(true),
+        padding_right: None,
+        data: None,
+    };
+
+    result.push(hint);
+    Some(())
+}
+
+/// 调用与链上相邻的调用之间是否有换行, 如 `q:where(...)\n:limit(10)`
+fn is_in_multi_line_chain(semantic_model: &SemanticModel, call_expr: &LuaCallExpr) -> bool {
+    let document = semantic_model.get_document();
+    let is_line_break = |prev_call: &LuaCallExpr, index_expr: &LuaIndexExpr| -> Option<bool> {
+        let prev_end = document.get_line(prev_call.get_range().end())?;
+        let index_start = document.get_line(index_expr.get_index_token()?.get_range().start())?;
+        Some(index_start > prev_end)
+    };
+
+    // 下一个调用换行
+    if let Some(index_expr) = call_expr.get_parent::<LuaIndexExpr>()
+        && index_expr.get_parent::<LuaCallExpr>().is_some()
+        && is_line_break(call_expr, &index_expr) == Some(true)
+    {
+        return true;
+    }
+
+    // 当前调用本身处于换行的位置
+    if let Some(LuaExpr::IndexExpr(index_expr)) = call_expr.get_prefix_expr()
+        && let Some(LuaExpr::CallExpr(prev_call)) = index_expr.get_prefix_expr()
+        && is_line_break(&prev_call, &index_expr) == Some(true)
+    {
+        return true;
+    }
+
+    false
+}
+
+fn build_self_hint(
+    semantic_model: &SemanticModel,
+    result: &mut Vec<InlayHint>,
+    closure: LuaClosureExpr,
+) -> Option<()> {
+    if !semantic_model.get_emmyrc().hint.self_hint {
+        return Some(());
+    }
+
+    let func_stat = closure.get_parent::<LuaFuncStat>()?;
+    let LuaVarExpr::IndexExpr(index_expr) = func_stat.get_func_name()? else {
+        return Some(());
+    };
+    let params_list = closure.get_params_list()?;
+    let is_colon_define = index_expr.get_index_token()?.is_colon();
+    let first_param = params_list.get_params().next();
+    if !is_colon_define {
+        let is_self_param = first_param
+            .as_ref()
+            .and_then(|param| param.get_name_token())
+            .is_some_and(|token| token.get_name_text() == "self");
+        if !is_self_param {
+            return Some(());
+        }
+    }
+
+    let self_type = semantic_model
+        .infer_expr(index_expr.get_prefix_expr()?)
+        .ok()?;
+    if self_type.is_unknown() || self_type.is_any() {
+        return Some(());
+    }
+    let mut label_parts = build_label_parts(semantic_model, &self_type);
+    if label_parts.is_empty() {
+        return Some(());
+    }
+
+    let document = semantic_model.get_document();
+    let (position, padding_left, padding_right) = if is_colon_define {
+        // 隐式 self 显示在参数列表的开头
+        let left_paren = params_list.syntax().first_token()?;
+        let position = document.to_lsp_range(left_paren.text_range())?.end;
+        let first_part = label_parts.first_mut()?;
+        first_part.value = format!("self{}", first_part.value);
+        if first_param.is_some() {
+            label_parts.last_mut()?.value.push(',');
+        }
+        (position, None, Some(true))
+    } else {
+        // 参数提示已经显示了 self 的类型
+        if semantic_model.get_emmyrc().hint.param_hint {
+            let signature_id = LuaSignatureId::from_closure(semantic_model.get_file_id(), &closure);
+            let has_param_hint = semantic_model
+                .get_db()
+                .get_signature_index()
+                .get(&signature_id)
+                .and_then(|signature| signature.get_param_info_by_name("self"))
+                .is_some_and(|param| !param.type_ref.is_any());
+            if has_param_hint {
+                return Some(());
+            }
+        }
+        let position = document.to_lsp_range(first_param?.get_range())?.end;
+        (position, Some(true), None)
+    };
+
+    let hint = InlayHint {
+        kind: Some(InlayHintKind::TYPE),
+        label: InlayHintLabel::LabelParts(label_parts),
+        position,
+        text_edits: None,
+        tooltip: None,
+        padding_left,
+        padding_right,
+        data: None,
+    };
+
+    result.push(hint);
+    Some(())
+}
+
 fn build_enum_param_hint(
     semantic_model: &SemanticModel,
     result: &mut Vec<InlayHint>,
@@ -24,6 +24,7 @@ mod implementation;
 mod initialized;
 mod inlay_hint;
 mod inline_values;
+mod moniker;
 mod notification_handler;
 mod references;
 mod rename;
@@ -40,6 +41,8 @@ mod test;
 #[cfg(test)]
 mod test_lib;
 
+#[cfg(all(test, feature = "slow-tests"))]
+pub use initialized::init_std_lib;
 pub use initialized::{ClientConfig, init_analysis, initialized_handler};
 use lsp_types::{ClientCapabilities, ServerCapabilities};
 pub use notification_handler::on_notification_handler;
@@ -98,6 +101,7 @@ capabilities!(modules: {
     workspace_symbol => WorkspaceSymbolCapabilities,
     configuration => ConfigurationCapabilities,
     call_hierarchy => CallHierarchyCapabilities,
+    moniker => MonikerCapabilities,
     workspace => WorkspaceCapabilities,
     diagnostic => DiagnosticCapabilities,
 });
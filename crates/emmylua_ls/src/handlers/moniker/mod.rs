@@ -0,0 +1,97 @@
+use crate::context::ServerContextSnapshot;
+use emmylua_code_analysis::{
+    DbIndex, EmmyLuaAnalysis, FileId, LuaSemanticDeclId, MONIKER_SCHEME, SemanticDeclLevel,
+    get_moniker_identifier,
+};
+use emmylua_parser::{LuaAstNode, LuaTokenKind};
+use lsp_types::{
+    ClientCapabilities, Moniker, MonikerKind, MonikerParams, OneOf, Position, ServerCapabilities,
+    UniquenessLevel,
+};
+use rowan::TokenAtOffset;
+use tokio_util::sync::CancellationToken;
+
+use super::RegisterCapabilities;
+
+pub async fn on_moniker_handler(
+    context: ServerContextSnapshot,
+    params: MonikerParams,
+    _: CancellationToken,
+) -> Option<Vec<Moniker>> {
+    let uri = params.text_document_position_params.text_document.uri;
+    let analysis = context.analysis().read().await;
+    let file_id = analysis.get_file_id(&uri)?;
+    let position = params.text_document_position_params.position;
+
+    moniker(&analysis, file_id, position)
+}
+
+pub fn moniker(
+    analysis: &EmmyLuaAnalysis,
+    file_id: FileId,
+    position: Position,
+) -> Option<Vec<Moniker>> {
+    let semantic_model = analysis.compilation.get_semantic_model(file_id)?;
+    let root = semantic_model.get_root();
+    let position_offset = {
+        let document = semantic_model.get_document();
+        document.get_offset(position.line as usize, position.character as usize)?
+    };
+
+    if position_offset > root.syntax().text_range().end() {
+        return None;
+    }
+
+    let token = match root.syntax().token_at_offset(position_offset) {
+        TokenAtOffset::Single(token) => token,
+        TokenAtOffset::Between(left, right) => {
+            if left.kind() == LuaTokenKind::TkName.into() {
+                left
+            } else {
+                right
+            }
+        }
+        TokenAtOffset::None => return None,
+    };
+
+    let semantic_decl = semantic_model.find_decl(token.into(), SemanticDeclLevel::default())?;
+    let db = semantic_model.get_db();
+    let identifier = get_moniker_identifier(db, &semantic_decl)?;
+
+    Some(vec![Moniker {
+        scheme: MONIKER_SCHEME.to_string(),
+        identifier,
+        unique: UniquenessLevel::Scheme,
+        kind: Some(get_moniker_kind(db, &semantic_decl)),
+    }])
+}
+
+/// Symbols defined in the main workspace are exported, symbols from the std library or
+/// library folders are imported.
+fn get_moniker_kind(db: &DbIndex, semantic_decl: &LuaSemanticDeclId) -> MonikerKind {
+    let file_id = match semantic_decl {
+        LuaSemanticDeclId::TypeDecl(type_decl_id) => db
+            .get_type_index()
+            .get_type_decl(type_decl_id)
+            .and_then(|type_decl| type_decl.get_locations().first().map(|it| it.file_id)),
+        LuaSemanticDeclId::Member(member_id) => Some(member_id.file_id),
+        LuaSemanticDeclId::LuaDecl(decl_id) => Some(decl_id.file_id),
+        LuaSemanticDeclId::Signature(signature_id) => Some(signature_id.get_file_id()),
+    };
+
+    let module_index = db.get_module_index();
+    match file_id {
+        Some(file_id) if module_index.is_std(&file_id) || module_index.is_library(&file_id) => {
+            MonikerKind::Import
+        }
+        _ => MonikerKind::Export,
+    }
+}
+
+pub struct MonikerCapabilities;
+
+impl RegisterCapabilities for MonikerCapabilities {
+    fn register_capabilities(server_capabilities: &mut ServerCapabilities, _: &ClientCapabilities) {
+        server_capabilities.moniker_provider = Some(OneOf::Left(true));
+    }
+}
@@ -515,7 +515,7 @@ fn fuzzy_search_references(
     Some(())
 }
 
-fn search_type_decl_references(
+pub fn search_type_decl_references(
     semantic_model: &SemanticModel,
     type_decl_id: LuaTypeDeclId,
     include_declaration: bool,
@@ -8,9 +8,9 @@ use lsp_types::request::{
     DocumentColor, DocumentDiagnosticRequest, DocumentHighlightRequest, DocumentLinkRequest,
     DocumentLinkResolve, DocumentSymbolRequest, ExecuteCommand, FoldingRangeRequest, Formatting,
     GotoDefinition, GotoImplementation, HoverRequest, InlayHintRequest, InlayHintResolveRequest,
-    InlineValueRequest, OnTypeFormatting, PrepareRenameRequest, RangeFormatting, References,
-    Rename, Request as LspRequest, ResolveCompletionItem, SelectionRangeRequest,
-    SemanticTokensFullRequest, SignatureHelpRequest, WorkspaceDiagnosticRequest,
+    InlineValueRequest, MonikerRequest, OnTypeFormatting, PrepareRenameRequest, RangeFormatting,
+    References, Rename, Request as LspRequest, ResolveCompletionItem, SelectionRangeRequest,
+    SemanticTokensFullRequest, SignatureHelpRequest, WillRenameFiles, WorkspaceDiagnosticRequest,
     WorkspaceSymbolRequest,
 };
 
@@ -49,10 +49,12 @@ use super::{
     implementation::on_implementation_handler,
     inlay_hint::{on_inlay_hint_handler, on_resolve_inlay_hint},
     inline_values::on_inline_values_handler,
+    moniker::on_moniker_handler,
     references::on_references_handler,
     rename::{on_prepare_rename_handler, on_rename_handler},
     semantic_token::on_semantic_token_handler,
     signature_helper::on_signature_helper_handler,
+    workspace::on_will_rename_files_handler,
     workspace_symbol::on_workspace_symbol_handler,
 };
 
@@ -118,6 +120,7 @@ pub async fn on_request_handler(
         DocumentHighlightRequest => on_document_highlight_handler,
         SemanticTokensFullRequest => on_semantic_token_handler,
         ExecuteCommand => on_execute_command_handler,
+        WillRenameFiles => on_will_rename_files_handler,
         CodeActionRequest => on_code_action_handler,
         InlineValueRequest => on_inline_values_handler,
         WorkspaceSymbolRequest => on_workspace_symbol_handler,
@@ -127,6 +130,7 @@ pub async fn on_request_handler(
         CallHierarchyPrepare => on_prepare_call_hierarchy_handler,
         CallHierarchyIncomingCalls => on_incoming_calls_handler,
         CallHierarchyOutgoingCalls => on_outgoing_calls_handler,
+        MonikerRequest => on_moniker_handler,
         DocumentDiagnosticRequest => on_pull_document_diagnostic,
         WorkspaceDiagnosticRequest => on_pull_workspace_diagnostic,
     });
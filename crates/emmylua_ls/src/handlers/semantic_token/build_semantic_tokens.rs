@@ -223,6 +223,7 @@ fn build_tokens_semantic_token(
         | LuaTokenKind::TkDocNew
         | LuaTokenKind::TkDocAs
         | LuaTokenKind::TkDocIn
+        | LuaTokenKind::TkDocOut
         | LuaTokenKind::TkDocInfer
         | LuaTokenKind::TkDocReadonly => {
             builder.push_with_modifier(
@@ -267,7 +268,7 @@ fn build_tokens_semantic_token(
                 SemanticTokenModifierKind::DOCUMENTATION,
             );
         }
-        LuaTokenKind::TkStringTemplateType => {
+        LuaTokenKind::TkStringTemplateType | LuaTokenKind::TkDocTemplateText => {
             builder.push_with_modifier(
                 token,
                 SemanticTokenTypeKind::String,
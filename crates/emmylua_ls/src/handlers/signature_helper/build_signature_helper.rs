@@ -9,6 +9,7 @@ use lsp_types::{
     SignatureInformation,
 };
 use rowan::{NodeOrToken, TextRange};
+use std::sync::Arc;
 
 use emmylua_code_analysis::humanize_type;
 
@@ -56,8 +57,12 @@ pub fn build_signature_helper(
     };
 
     if let Some(mut help) = help {
-        // 将所有参数均相同的签名放在最前面
-        process_best_call_params_info(&builder, &mut help.signatures);
+        // 重载已经按参数选出活动签名时保持原有顺序
+        if help.active_signature.is_none() || help.signatures.len() == 1 {
+            // 将所有参数均相同的签名放在最前面
+            process_best_call_params_info(&builder, &mut help.signatures);
+            help.active_signature = Some(0);
+        }
         Some(help)
     } else {
         None
@@ -128,11 +133,12 @@ fn build_doc_function_signature_help(
         _ => {}
     }
 
+    // 可变参数之后的实参都落在可变参数上
     if let Some((name, _)) = params.last()
         && name == "..."
-        && current_idx >= params.len()
+        && current_idx >= param_infos.len()
     {
-        current_idx = params.len() - 1;
+        current_idx = param_infos.len().saturating_sub(1);
     }
 
     let label = build_function_label(
@@ -231,11 +237,12 @@ fn build_sig_id_signature_help(
         _ => {}
     }
 
+    // 可变参数之后的实参都落在可变参数上
     if let Some((name, _)) = params.last()
         && name == "..."
-        && current_idx >= params.len()
+        && current_idx >= param_infos.len()
     {
-        current_idx = params.len() - 1;
+        current_idx = param_infos.len().saturating_sub(1);
     }
 
     let label = build_function_label(
@@ -252,7 +259,20 @@ fn build_sig_id_signature_help(
         active_parameter: Some(current_idx as u32),
     };
 
-    let mut signatures = vec![signature_info];
+    if signature.overloads.is_empty() {
+        return Some(SignatureHelp {
+            signatures: vec![signature_info],
+            active_signature: Some(0),
+            active_parameter: Some(current_idx as u32),
+        });
+    }
+
+    let main_func = if is_call_operator {
+        signature.to_call_operator_func_type()
+    } else {
+        signature.to_doc_func_type()
+    };
+    let mut candidates = vec![(main_func, signature_info)];
     for overload in &signature.overloads {
         let signature = build_doc_function_signature_help(
             builder,
@@ -263,15 +283,61 @@ fn build_sig_id_signature_help(
         );
         if let Some(mut signature) = signature {
             signature.signatures[0].documentation = builder.description.clone();
-            signatures.push(signature.signatures[0].clone());
+            candidates.push((overload.clone(), signature.signatures[0].clone()));
         }
     }
 
-    Some(SignatureHelp {
+    Some(build_overload_signature_help(
+        builder,
+        candidates,
+        colon_call && !is_call_operator,
+        origin_current_idx,
+    ))
+}
+
+/// 根据已输入的参数过滤不兼容的重载, 并将最匹配的重载设为活动签名
+fn build_overload_signature_help(
+    builder: &SignatureHelperBuilder,
+    candidates: Vec<(Arc<LuaFunctionType>, SignatureInformation)>,
+    colon_call: bool,
+    current_idx: usize,
+) -> SignatureHelp {
+    let semantic_model = builder.semantic_model;
+    let arg_types = builder.infer_typed_arg_types(current_idx);
+    let arg_count = Some(current_idx + 1);
+    let compatible = candidates
+        .iter()
+        .filter(|(func, _)| {
+            semantic_model.callable_accepts_args(func, &arg_types, colon_call, arg_count)
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    // 没有任何重载兼容时, 大概率是用户仍在输入, 保留全部签名
+    let candidates = if compatible.is_empty() {
+        candidates
+    } else {
+        compatible
+    };
+
+    let funcs = candidates
+        .iter()
+        .map(|(func, _)| func.clone())
+        .collect::<Vec<_>>();
+    let active_signature = semantic_model
+        .resolve_signature_by_args(&funcs, &arg_types, colon_call, arg_count)
+        .and_then(|best| funcs.iter().position(|func| *func == best))
+        .unwrap_or(0);
+
+    let signatures = candidates
+        .into_iter()
+        .map(|(_, info)| info)
+        .collect::<Vec<_>>();
+    let active_parameter = signatures[active_signature].active_parameter;
+    SignatureHelp {
         signatures,
-        active_signature: Some(0),
-        active_parameter: Some(current_idx as u32),
-    })
+        active_signature: Some(active_signature as u32),
+        active_parameter,
+    }
 }
 
 // todo support overload
@@ -452,7 +518,7 @@ fn build_union_type_signature_help(
 
     Some(SignatureHelp {
         signatures,
-        active_signature: Some(0),
+        active_signature: None,
         active_parameter: Some(active_parameter),
     })
 }
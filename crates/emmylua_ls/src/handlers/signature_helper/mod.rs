@@ -5,7 +5,7 @@ use crate::context::ServerContextSnapshot;
 use build_signature_helper::build_signature_helper;
 pub use build_signature_helper::get_current_param_index;
 use emmylua_code_analysis::{EmmyLuaAnalysis, FileId};
-use emmylua_parser::{LuaAstNode, LuaCallExpr, LuaSyntaxKind, LuaTokenKind};
+use emmylua_parser::{LuaAstNode, LuaCallExpr, LuaSyntaxKind, LuaSyntaxNode, LuaTokenKind};
 use lsp_types::Position;
 use lsp_types::{
     ClientCapabilities, ServerCapabilities, SignatureHelp, SignatureHelpContext,
@@ -65,6 +65,15 @@ pub fn signature_help(
                 let call_expr = LuaCallExpr::cast(node.parent()?)?;
                 build_signature_helper(&semantic_model, call_expr, token)
             }
+            LuaSyntaxKind::TableArrayExpr
+            | LuaSyntaxKind::TableObjectExpr
+            | LuaSyntaxKind::TableEmptyExpr
+            | LuaSyntaxKind::TableFieldAssign
+            | LuaSyntaxKind::TableFieldValue => {
+                let arg_list = find_table_arg_owner_list(node)?;
+                let call_expr = LuaCallExpr::cast(arg_list.parent()?)?;
+                build_signature_helper(&semantic_model, call_expr, token)
+            }
             // todo
             LuaSyntaxKind::TypeGeneric | LuaSyntaxKind::DocTypeList => None,
             _ => None,
@@ -99,6 +108,22 @@ pub fn signature_help(
     }
 }
 
+/// 光标位于作为实参的表构造式中时(例如 `f({ name = 1, <??> })`), 找到该实参所在的参数列表
+fn find_table_arg_owner_list(node: LuaSyntaxNode) -> Option<LuaSyntaxNode> {
+    node.ancestors()
+        .find(|node| {
+            !matches!(
+                node.kind().into(),
+                LuaSyntaxKind::TableArrayExpr
+                    | LuaSyntaxKind::TableObjectExpr
+                    | LuaSyntaxKind::TableEmptyExpr
+                    | LuaSyntaxKind::TableFieldAssign
+                    | LuaSyntaxKind::TableFieldValue
+            )
+        })
+        .filter(|node| node.kind() == LuaSyntaxKind::CallArgList.into())
+}
+
 pub struct SignatureHelperCapabilities;
 
 impl RegisterCapabilities for SignatureHelperCapabilities {
@@ -168,4 +168,20 @@ impl<'a> SignatureHelperBuilder<'a> {
     pub fn get_best_call_params_info(&self) -> &[ParameterInformation] {
         &self.params_info
     }
+
+    /// 推断光标所在参数之前已输入参数的类型
+    pub fn infer_typed_arg_types(&self, current_idx: usize) -> Vec<LuaType> {
+        let Some(args_list) = self.call_expr.get_args_list() else {
+            return Vec::new();
+        };
+        args_list
+            .get_args()
+            .take(current_idx)
+            .map(|arg| {
+                self.semantic_model
+                    .infer_expr(arg)
+                    .unwrap_or(LuaType::Unknown)
+            })
+            .collect()
+    }
 }
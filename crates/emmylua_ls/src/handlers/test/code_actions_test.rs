@@ -1,8 +1,15 @@
 #[cfg(test)]
 mod tests {
-    use crate::handlers::test_lib::{ProviderVirtualWorkspace, VirtualCodeAction, check};
+    use crate::{
+        context::RuntimeTraceCache,
+        handlers::{
+            code_actions::{code_action, convert_method, move_symbol},
+            test_lib::{ProviderVirtualWorkspace, VirtualCodeAction, check},
+        },
+    };
     use emmylua_code_analysis::{DiagnosticCode, Emmyrc};
     use googletest::prelude::*;
+    use lsp_types::{CodeActionOrCommand, Position, Range};
 
     #[gtest]
     fn test_1() -> Result<()> {
@@ -87,4 +94,663 @@ mod tests {
 
         Ok(())
     }
+
+    #[gtest]
+    fn test_extract_variable() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor(
+            r#"
+            local function f(a, b)
+                return <??>a * b<??> + 2
+            end
+            "#,
+            "Extract into variable",
+            Some(
+                r#"
+            local function f(a, b)
+                local new_var = a * b
+                return new_var + 2
+            end
+            "#
+            ),
+        ));
+        check!(ws.check_refactor(
+            "local x = t and <??>t.value<??>",
+            "Extract into variable",
+            None,
+        ));
+        check!(ws.check_refactor(
+            "while <??>next(t)<??> do end",
+            "Extract into variable",
+            None,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_extract_function() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor(
+            r#"
+local function f(list)
+    local sum = 0
+    <??>for _, v in ipairs(list) do
+        sum = sum + v
+    end<??>
+    return sum
+end
+"#,
+            "Extract into function",
+            Some(
+                r#"
+---@param list any
+---@param sum integer
+---@return integer
+local function new_function(list, sum)
+    for _, v in ipairs(list) do
+        sum = sum + v
+    end
+    return sum
+end
+
+local function f(list)
+    local sum = 0
+    sum = new_function(list, sum)
+    return sum
+end
+"#
+            ),
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_extract_function_locals() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor(
+            r#"
+local count = 1
+<??>local name = "a" .. count
+print(name)<??>
+print(name)
+"#,
+            "Extract into function",
+            Some(
+                r#"
+local count = 1
+---@return string
+local function new_function()
+    local name = "a" .. count
+    print(name)
+    return name
+end
+
+local name = new_function()
+print(name)
+"#
+            ),
+        ));
+        check!(ws.check_refactor(
+            "local x = 1 + <??>2 * 3<??>",
+            "Extract into function",
+            Some(
+                r#"---@return integer
+local function new_function()
+    return 2 * 3
+end
+
+local x = 1 + new_function()"#
+            ),
+        ));
+        check!(ws.check_refactor(
+            r#"
+for i = 1, 10 do
+    <??>if i > 5 then
+        break
+    end<??>
+end
+"#,
+            "Extract into function",
+            None,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_inline_variable() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_refactor(
+            r#"
+local function f(a)
+    local <??>n = a + 1
+    print(n * 2, n)
+end
+"#,
+            "Inline variable 'n'",
+            Some(
+                r#"
+local function f(a)
+    print((a + 1) * 2, a + 1)
+end
+"#
+            ),
+        ));
+        check!(ws.check_refactor(
+            r#"
+local v = g()
+print(<??>v)
+"#,
+            "Inline variable 'v'",
+            Some(
+                r#"
+print(g())
+"#
+            ),
+        ));
+        check!(ws.check_refactor(
+            r#"
+local v = g()
+print(h(), <??>v)
+"#,
+            "Inline variable 'v'",
+            None,
+        ));
+        check!(ws.check_refactor(
+            r#"
+local <??>v = 1
+v = 2
+print(v)
+"#,
+            "Inline variable 'v'",
+            None,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_move_symbol() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let source = ws.def_file(
+            "utils.lua",
+            r#"local M = {}
+
+---@param s string
+function M.trim(s)
+    return M.strip(s)
+end
+
+function M.strip(s)
+    return s
+end
+
+return M
+"#,
+        );
+        let target = ws.def_file(
+            "strings.lua",
+            r#"local M = {}
+
+function M.upper(s)
+    return s
+end
+
+return M
+"#,
+        );
+        ws.def_file(
+            "main.lua",
+            r#"local utils = require("utils")
+print(utils.trim(" a "), utils.strip("b"))
+"#,
+        );
+        ws.def_file(
+            "other.lua",
+            r#"local utils = require("utils")
+print(utils.trim(" a "))
+print(require("utils").trim("b"))
+"#,
+        );
+
+        let edit = move_symbol(&ws.analysis, source, Position::new(3, 12), target).or_fail()?;
+        verify_eq!(
+            ws.apply_workspace_edit(edit)?,
+            vec![
+                (
+                    "main.lua".to_string(),
+                    r#"local utils = require("utils")
+local strings = require("strings")
+print(strings.trim(" a "), utils.strip("b"))
+"#
+                    .to_string()
+                ),
+                (
+                    "other.lua".to_string(),
+                    r#"local utils = require("strings")
+print(utils.trim(" a "))
+print(require("strings").trim("b"))
+"#
+                    .to_string()
+                ),
+                (
+                    "strings.lua".to_string(),
+                    r#"local utils = require("utils")
+local M = {}
+
+function M.upper(s)
+    return s
+end
+
+---@param s string
+function M.trim(s)
+    return utils.strip(s)
+end
+
+return M
+"#
+                    .to_string()
+                ),
+                (
+                    "utils.lua".to_string(),
+                    r#"local M = {}
+
+function M.strip(s)
+    return s
+end
+
+return M
+"#
+                    .to_string()
+                ),
+            ]
+        )
+    }
+
+    #[gtest]
+    fn test_move_class() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let source = ws.def_file(
+            "shapes.lua",
+            r#"local M = {}
+
+---@class Point
+local Point = {}
+
+function Point:len()
+    return 0
+end
+
+M.Point = Point
+
+function M.origin()
+    return Point
+end
+
+return M
+"#,
+        );
+        let target = ws.def_file("point.lua", "");
+
+        let edit = move_symbol(&ws.analysis, source, Position::new(3, 8), target).or_fail()?;
+        verify_eq!(
+            ws.apply_workspace_edit(edit)?,
+            vec![
+                (
+                    "point.lua".to_string(),
+                    r#"local M = {}
+
+---@class Point
+local Point = {}
+
+function Point:len()
+    return 0
+end
+
+M.Point = Point
+
+return M
+"#
+                    .to_string()
+                ),
+                (
+                    "shapes.lua".to_string(),
+                    r#"local point = require("point")
+local M = {}
+
+function M.origin()
+    return point.Point
+end
+
+return M
+"#
+                    .to_string()
+                ),
+            ]
+        )
+    }
+
+    #[gtest]
+    fn test_convert_method_to_colon() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let source = ws.def_file(
+            "point.lua",
+            r#"---@class Point
+local Point = {}
+
+---@param self Point
+---@param dx number
+function Point.move(self, dx)
+    self.x = self.x + dx
+end
+
+return Point
+"#,
+        );
+        ws.def_file(
+            "main.lua",
+            r#"local Point = require("point")
+local p = Point
+---@return Point
+local function get() return p end
+Point.move(p, 1)
+Point.move(Point, 2)
+get().move(get(), 3)
+"#,
+        );
+
+        let conversion = convert_method(&ws.analysis, source, Position::new(5, 16)).or_fail()?;
+        verify_eq!(conversion.flagged.len(), 1)?;
+        verify_eq!(conversion.flagged[0].range.start, Position::new(6, 0))?;
+        verify_eq!(
+            ws.apply_workspace_edit(conversion.edit)?,
+            vec![
+                (
+                    "main.lua".to_string(),
+                    r#"local Point = require("point")
+local p = Point
+---@return Point
+local function get() return p end
+Point.move(p, 1)
+Point:move(2)
+get().move(get(), 3)
+"#
+                    .to_string()
+                ),
+                (
+                    "point.lua".to_string(),
+                    r#"---@class Point
+local Point = {}
+
+---@param dx number
+function Point:move(dx)
+    self.x = self.x + dx
+end
+
+return Point
+"#
+                    .to_string()
+                ),
+            ]
+        )
+    }
+
+    #[gtest]
+    fn test_convert_method_to_dot() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let source = ws.def_file(
+            "stack.lua",
+            r#"---@class Stack
+local Stack = {}
+
+function Stack:clear()
+end
+
+function Stack:push(v)
+end
+
+return Stack
+"#,
+        );
+        ws.def_file(
+            "main.lua",
+            r#"local Stack = require("stack")
+Stack:clear()
+Stack:push(1)
+Stack:push "a"
+---@return Stack
+local function new() return Stack end
+new():push(2)
+"#,
+        );
+
+        let conversion = convert_method(&ws.analysis, source, Position::new(6, 16)).or_fail()?;
+        verify_eq!(conversion.flagged.len(), 1)?;
+        verify_eq!(
+            ws.apply_workspace_edit(conversion.edit)?,
+            vec![
+                (
+                    "main.lua".to_string(),
+                    r#"local Stack = require("stack")
+Stack:clear()
+Stack.push(Stack, 1)
+Stack.push(Stack, "a")
+---@return Stack
+local function new() return Stack end
+new():push(2)
+"#
+                    .to_string()
+                ),
+                (
+                    "stack.lua".to_string(),
+                    r#"---@class Stack
+local Stack = {}
+
+function Stack:clear()
+end
+
+function Stack.push(self, v)
+end
+
+return Stack
+"#
+                    .to_string()
+                ),
+            ]
+        )
+    }
+
+    #[gtest]
+    fn test_convert_method_action() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let file_id = ws.def(
+            r#"local A = {}
+function A.f(self) end
+function A.g(x) end
+function A:h() end
+"#,
+        );
+        let titles = |line| {
+            code_action(
+                &ws.analysis,
+                &RuntimeTraceCache::new(),
+                file_id,
+                Range::new(Position::new(line, 12), Position::new(line, 12)),
+                Vec::new(),
+            )
+            .unwrap_or_default()
+            .into_iter()
+            .map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) => action.title,
+                CodeActionOrCommand::Command(command) => command.title,
+            })
+            .collect::<Vec<_>>()
+        };
+        verify_that!(titles(1), contains(eq("Convert 'f' to colon method")))?;
+        verify_that!(titles(2), not(contains(starts_with("Convert"))))?;
+        verify_that!(titles(3), contains(eq("Convert 'h' to dot method")))
+    }
+
+    #[gtest]
+    fn test_add_missing_branches() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let mut emmyrc = Emmyrc::default();
+        emmyrc
+            .diagnostics
+            .enables
+            .push(DiagnosticCode::NonExhaustiveBranches);
+        ws.analysis.update_config(emmyrc.into());
+        check!(ws.check_fix(
+            r#"
+---@enum Direction
+Direction = { Up = 1, Down = 2, Left = 3, Right = 4 }
+
+---@param dir Direction
+local function move(dir)
+    if dir == Direction.Up then
+        return 1
+    elseif dir == Direction.Down then
+        return 2
+    end
+end
+"#,
+            "Add missing branches",
+            r#"
+---@enum Direction
+Direction = { Up = 1, Down = 2, Left = 3, Right = 4 }
+
+---@param dir Direction
+local function move(dir)
+    if dir == Direction.Up then
+        return 1
+    elseif dir == Direction.Down then
+        return 2
+    elseif dir == Direction.Left then
+    elseif dir == Direction.Right then
+    end
+end
+"#,
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_add_trace_annotations() -> Result<()> {
+        let trace_file =
+            std::env::temp_dir().join(format!("emmylua-trace-action-{}.json", std::process::id()));
+        std::fs::write(
+            &trace_file,
+            r#"[{ "source": "@virtual_0.lua", "line": 2, "params": [{ "name": "a", "types": "integer" }, { "name": "b", "types": ["string", "nil"] }], "returns": ["string"] }]"#,
+        )
+        .or_fail()?;
+        let mut ws = ProviderVirtualWorkspace::new();
+        let mut emmyrc = Emmyrc::default();
+        emmyrc.code_action.trace_files = vec![trace_file.to_string_lossy().to_string()];
+        ws.analysis.update_config(emmyrc.into());
+        let result = ws.check_refactor(
+            r#"
+            local function f(a, b)
+                return <??>tostring(a) .. (b or "")
+            end
+            "#,
+            "Add annotations from runtime trace",
+            Some(
+                r#"
+            ---@param a integer
+            ---@param b? string
+            ---@return string
+            local function f(a, b)
+                return tostring(a) .. (b or "")
+            end
+            "#,
+            ),
+        );
+        let _ = std::fs::remove_file(&trace_file);
+        check!(result);
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_trace_cache_reloads_modified_file() -> Result<()> {
+        let trace_file =
+            std::env::temp_dir().join(format!("emmylua-trace-cache-{}.json", std::process::id()));
+        let trace_files = vec![trace_file.to_string_lossy().to_string()];
+        let record = |line: u32| {
+            format!(
+                r#"[{{ "source": "@main.lua", "line": {line}, "params": [{{ "name": "a", "types": "integer" }}] }}]"#
+            )
+        };
+        std::fs::write(&trace_file, record(1)).or_fail()?;
+
+        let cache = RuntimeTraceCache::new();
+        let first = cache.get(&trace_files);
+        let second = cache.get(&trace_files);
+        verify_that!(std::sync::Arc::ptr_eq(&first, &second), eq(true))?;
+
+        std::fs::write(&trace_file, record(2)).or_fail()?;
+        std::fs::File::options()
+            .write(true)
+            .open(&trace_file)
+            .and_then(|file| {
+                file.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(10))
+            })
+            .or_fail()?;
+        let reloaded = cache.get(&trace_files);
+        let _ = std::fs::remove_file(&trace_file);
+
+        verify_that!(std::sync::Arc::ptr_eq(&first, &reloaded), eq(false))?;
+        let main = std::path::Path::new("main.lua");
+        verify_that!(reloaded.find_function(main, 1), none())?;
+        verify_that!(reloaded.find_function(main, 2), some(anything()))
+    }
+
+    #[gtest]
+    fn test_add_missing_table_keys() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let mut emmyrc = Emmyrc::default();
+        emmyrc
+            .diagnostics
+            .enables
+            .push(DiagnosticCode::NonExhaustiveBranches);
+        ws.analysis.update_config(emmyrc.into());
+        check!(ws.check_fix(
+            r#"
+---@enum Event
+Event = { Open = 1, Close = 2, Error = 3 }
+
+---@type table<Event, fun()>
+local handlers = {
+    [Event.Open] = function() end
+}
+"#,
+            "Add missing keys",
+            r#"
+---@enum Event
+Event = { Open = 1, Close = 2, Error = 3 }
+
+---@type table<Event, fun()>
+local handlers = {
+    [Event.Open] = function() end,
+    [Event.Close] = function() end,
+    [Event.Error] = function() end,
+}
+"#,
+        ));
+        check!(ws.check_fix(
+            r#"
+---@enum Key
+Key = { A = 1, B = 2 }
+
+---@type table<Key, fun()>
+local handlers = { [Key.A] = function() end, }
+"#,
+            "Add missing keys",
+            r#"
+---@enum Key
+Key = { A = 1, B = 2 }
+
+---@type table<Key, fun()>
+local handlers = { [Key.A] = function() end, [Key.B] = function() end, }
+"#,
+        ));
+        Ok(())
+    }
 }
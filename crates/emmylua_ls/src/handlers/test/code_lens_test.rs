@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_class_and_override_lens() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_code_lens(
+            r#"
+                ---@class Animal
+                local Animal = {}
+
+                function Animal:speak()
+                end
+
+                ---@class Dog: Animal
+                local Dog = {}
+
+                function Dog:speak()
+                end
+
+                ---@type Dog
+                local dog
+            "#,
+            vec![
+                (1, "1 reference"),
+                (1, "1 subclass"),
+                (4, "0 usages"),
+                (4, "overridden in 1 class"),
+                (7, "1 reference"),
+                (10, "0 usages"),
+                (10, "implements Animal.speak"),
+            ],
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_lens_counts_indirect_subclasses() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_code_lens(
+            r#"
+                ---@class Animal
+                local Animal = {}
+
+                function Animal:speak()
+                end
+
+                ---@class Dog: Animal
+                local Dog = {}
+
+                ---@class Puppy: Dog
+                local Puppy = {}
+
+                function Puppy:speak()
+                end
+            "#,
+            vec![
+                (1, "1 reference"),
+                (1, "2 subclasses"),
+                (4, "0 usages"),
+                (4, "overridden in 1 class"),
+                (7, "1 reference"),
+                (7, "1 subclass"),
+                (10, "0 references"),
+                (13, "0 usages"),
+                (13, "implements Animal.speak"),
+            ],
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::ProviderVirtualWorkspace;
+    use googletest::prelude::*;
+    use lsp_types::{
+        CreateFile, DocumentChangeOperation, DocumentChanges, MessageType, OneOf3, Position, Range,
+        ResourceOp, TextEdit,
+    };
+
+    #[gtest]
+    fn test_structural_replace() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let a = ws.def_file("a.lua", "local t = obj:GetComponent(\"X\")\n");
+        ws.def_file("b.lua", "print(obj:GetComponent(\"Y\"))\n");
+        let uri = ws.analysis.get_uri(a).or_fail()?;
+        let result = ws.execute_command(
+            "emmy.ssr",
+            vec![
+                serde_json::json!(r#"$o:GetComponent("X") ==>> $o.x"#),
+                serde_json::to_value(&uri).or_fail()?,
+            ],
+        );
+        verify_eq!(result.edits.len(), 1)?;
+        let changes = result.edits[0].changes.clone().or_fail()?;
+        verify_eq!(changes.len(), 1)?;
+        verify_eq!(
+            changes.get(&uri).or_fail()?,
+            &vec![TextEdit {
+                range: Range::new(Position::new(0, 10), Position::new(0, 31)),
+                new_text: "obj.x".to_string(),
+            }]
+        )
+    }
+
+    #[gtest]
+    fn test_structural_replace_invalid_rule() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def_file("a.lua", "local t = 1\n");
+        let result = ws.execute_command("emmy.ssr", vec![serde_json::json!("f($a) ==>> g($b)")]);
+        verify_that!(result.edits, is_empty())?;
+        verify_eq!(result.messages.len(), 1)?;
+        verify_eq!(result.messages[0].typ, MessageType::ERROR)?;
+        verify_that!(
+            result.messages[0].message,
+            starts_with("Invalid structural replace rule")
+        )
+    }
+
+    #[gtest]
+    fn test_generate_doc_stubs() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let a = ws.def_file("a.lua", "local function f(x)\n    return 1\nend\n");
+        ws.def_file("b.lua", "---@param x string\nlocal function g(x)\nend\n");
+        let result = ws.execute_command("emmy.generateDocStubs", Vec::new());
+        verify_eq!(result.value, Some(serde_json::Value::Null))?;
+        verify_eq!(result.edits.len(), 1)?;
+        let changes = result.edits[0].changes.clone().or_fail()?;
+        verify_eq!(changes.len(), 1)?;
+        verify_eq!(
+            changes.get(&ws.analysis.get_uri(a).or_fail()?).or_fail()?,
+            &vec![TextEdit {
+                range: Range::new(Position::new(0, 0), Position::new(0, 0)),
+                new_text: "---@param x any\n---@return integer\n".to_string(),
+            }]
+        )
+    }
+
+    #[gtest]
+    fn test_generate_doc_stubs_for_file() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def_file("a.lua", "local function f(x)\nend\n");
+        let b = ws.def_file("b.lua", "---@param x string\nlocal function g(x)\nend\n");
+        let uri = ws.analysis.get_uri(b).or_fail()?;
+        let result = ws.execute_command(
+            "emmy.generateDocStubs",
+            vec![serde_json::to_value(&uri).or_fail()?],
+        );
+        verify_eq!(result.value, Some(serde_json::Value::Null))?;
+        verify_that!(result.edits, is_empty())
+    }
+
+    #[gtest]
+    fn test_move_symbol_to_new_module() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let source = ws.def_file(
+            "move_source.lua",
+            "local M = {}\n\nfunction M.trim(s)\n    return s\nend\n\nreturn M\n",
+        );
+        let source_uri = ws.analysis.get_uri(source).or_fail()?;
+        let target_uri = ws.virtual_url_generator.new_uri("move_target_new.lua");
+        let result = ws.execute_command(
+            "emmy.move.symbol",
+            vec![
+                serde_json::to_value(&source_uri).or_fail()?,
+                serde_json::to_value(Position::new(2, 12)).or_fail()?,
+                serde_json::to_value(&target_uri).or_fail()?,
+            ],
+        );
+        verify_that!(result.messages, is_empty())?;
+        verify_eq!(result.edits.len(), 1)?;
+        let Some(DocumentChanges::Operations(operations)) =
+            result.edits[0].document_changes.clone()
+        else {
+            return fail!("expected document change operations");
+        };
+        verify_eq!(
+            operations[0],
+            DocumentChangeOperation::Op(ResourceOp::Create(CreateFile {
+                uri: target_uri.clone(),
+                options: None,
+                annotation_id: None,
+            }))
+        )?;
+        let target_edits = operations
+            .iter()
+            .find_map(|operation| match operation {
+                DocumentChangeOperation::Edit(edit) if edit.text_document.uri == target_uri => {
+                    Some(edit.edits.clone())
+                }
+                _ => None,
+            })
+            .or_fail()?;
+        verify_eq!(
+            target_edits,
+            vec![OneOf3::Left(TextEdit {
+                range: Range::new(Position::new(0, 0), Position::new(0, 0)),
+                new_text: "local M = {}\n\nfunction M.trim(s)\n    return s\nend\n\nreturn M\n"
+                    .to_string(),
+            })]
+        )
+    }
+}
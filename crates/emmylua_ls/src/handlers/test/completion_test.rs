@@ -754,6 +754,11 @@ mod tests {
                     kind: CompletionItemKind::ENUM_MEMBER,
                     ..Default::default()
                 },
+                VirtualCompletionItem {
+                    label: "opaque".to_string(),
+                    kind: CompletionItemKind::ENUM_MEMBER,
+                    ..Default::default()
+                },
                 VirtualCompletionItem {
                     label: "file".to_string(),
                     kind: CompletionItemKind::ENUM_MEMBER,
@@ -794,6 +799,11 @@ mod tests {
                     kind: CompletionItemKind::ENUM_MEMBER,
                     ..Default::default()
                 },
+                VirtualCompletionItem {
+                    label: "opaque".to_string(),
+                    kind: CompletionItemKind::ENUM_MEMBER,
+                    ..Default::default()
+                },
                 VirtualCompletionItem {
                     label: "file".to_string(),
                     kind: CompletionItemKind::ENUM_MEMBER,
@@ -854,6 +864,36 @@ mod tests {
         Ok(())
     }
 
+    #[gtest]
+    fn test_template_literal_type() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new_with_init_std_lib();
+        check!(ws.check_completion_with_kind(
+            r#"
+                ---@alias Key "click" | "hover"
+
+                ---@param name `on${Capitalize<Key>}`
+                local function listen(name)
+                end
+
+                listen(<??>)
+            "#,
+            vec![
+                VirtualCompletionItem {
+                    label: "\"onClick\"".to_string(),
+                    kind: CompletionItemKind::ENUM_MEMBER,
+                    ..Default::default()
+                },
+                VirtualCompletionItem {
+                    label: "\"onHover\"".to_string(),
+                    kind: CompletionItemKind::ENUM_MEMBER,
+                    ..Default::default()
+                },
+            ],
+            CompletionTriggerKind::TRIGGER_CHARACTER,
+        ));
+        Ok(())
+    }
+
     #[gtest]
     fn test_str_tpl_ref_1() -> Result<()> {
         let mut ws = ProviderVirtualWorkspace::new_with_init_std_lib();
@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_control_flow_symbol_names() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_document_symbols(
+            r#"
+                for i = 1, 10 do
+                end
+                for k, v in pairs(t) do
+                end
+                if state == "idle" then
+                elseif state == "run" then
+                else
+                end
+            "#,
+            vec![
+                ("for i = 1, 10", 1, 2),
+                ("  i", 1, 1),
+                ("for k, v in pairs(t)", 3, 4),
+                ("  k", 3, 3),
+                ("  v", 3, 3),
+                ("if state == \"idle\"", 5, 8),
+                ("  elseif state == \"run\"", 6, 6),
+                ("  else", 7, 7),
+            ]
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_loop_symbols() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_document_symbols(
+            r#"
+                while running do
+                    local a = 1
+                end
+                repeat
+                    local b = 2
+                until b
+            "#,
+            vec![
+                ("while running", 1, 3),
+                ("  a", 2, 2),
+                ("repeat", 4, 6),
+                ("  b", 5, 5),
+            ]
+        ));
+        Ok(())
+    }
+}
@@ -835,4 +835,29 @@ mod tests {
 
         Ok(())
     }
+
+    #[gtest]
+    fn test_const_value() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_hover(
+            r#"
+                local MINUTE <const> = 60
+                local TIME<??>OUT = MINUTE * 5
+            "#,
+            VirtualHoverResult {
+                value: "```lua\nlocal TIMEOUT: integer = 300\n```".to_string(),
+            },
+        ));
+        check!(ws.check_hover(
+            r#"
+                local Flags = { EXEC = 1 << 2 }
+                local f = Flags.EX<??>EC
+            "#,
+            VirtualHoverResult {
+                value: "```lua\n(field) EXEC: integer = 4\n```".to_string(),
+            },
+        ));
+
+        Ok(())
+    }
 }
@@ -391,4 +391,190 @@ mod tests {
         ));
         Ok(())
     }
+
+    #[gtest]
+    fn test_const_value_hint() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let mut emmyrc = ws.get_emmyrc();
+        emmyrc.hint.const_value_hint = true;
+        ws.update_emmyrc(emmyrc);
+        check!(ws.check_inlay_hint(
+            r#"
+                local TIMEOUT = 60 * 5
+                local NAME = "a" .. "b"
+                local MINUS = -1
+                local t = { FLAG = 1 << 3 }
+            "#,
+            vec![
+                VirtualInlayHint {
+                    label: "= 300".to_string(),
+                    line: 1,
+                    pos: 38,
+                    ref_file: None,
+                },
+                VirtualInlayHint {
+                    label: "= \"ab\"".to_string(),
+                    line: 2,
+                    pos: 39,
+                    ref_file: None,
+                },
+                VirtualInlayHint {
+                    label: "= 8".to_string(),
+                    line: 4,
+                    pos: 41,
+                    ref_file: None,
+                },
+            ]
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_const_value_hint_lua_escape() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let mut emmyrc = ws.get_emmyrc();
+        emmyrc.hint.const_value_hint = true;
+        ws.update_emmyrc(emmyrc);
+        check!(ws.check_inlay_hint(
+            r#"
+                local ESC = "\27[" .. "0m\n"
+            "#,
+            vec![VirtualInlayHint {
+                label: r#"= "\27[0m\n""#.to_string(),
+                line: 1,
+                pos: 44,
+                ref_file: None,
+            }]
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_block_end_hint() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let mut emmyrc = ws.get_emmyrc();
+        emmyrc.hint.local_hint = false;
+        emmyrc.hint.param_hint = false;
+        emmyrc.hint.block_end_hint = true;
+        emmyrc.hint.block_end_hint_min_lines = 3;
+        ws.update_emmyrc(emmyrc);
+        check!(ws.check_inlay_hint(
+            r#"
+                local Player = {}
+                function Player:update(state)
+                    if state == "idle" then
+                        return
+                    end
+                    for i = 1, 10 do print(i) end
+                    repeat
+                        state = nil
+                    until state
+                end
+            "#,
+            vec![
+                VirtualInlayHint {
+                    label: "-- function Player:update".to_string(),
+                    line: 10,
+                    pos: 19,
+                    ref_file: None,
+                },
+                VirtualInlayHint {
+                    label: "-- if state == \"idle\"".to_string(),
+                    line: 5,
+                    pos: 23,
+                    ref_file: None,
+                },
+                VirtualInlayHint {
+                    label: "-- repeat".to_string(),
+                    line: 9,
+                    pos: 31,
+                    ref_file: None,
+                },
+            ]
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_chain_call_hint() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let mut emmyrc = ws.get_emmyrc();
+        emmyrc.hint.local_hint = false;
+        emmyrc.hint.param_hint = false;
+        emmyrc.hint.chain_call_hint = true;
+        ws.update_emmyrc(emmyrc);
+        ws.def(
+            r#"
+                ---@class Query
+                ---@field where fun(self: Query, key: string): Query
+                ---@field count fun(self: Query): integer
+            "#,
+        );
+        check!(ws.check_inlay_hint(
+            r#"
+                ---@type Query
+                local q
+                local n = q:where("a")
+                    :where("b")
+                    :count()
+                local m = q:where("a"):count()
+            "#,
+            vec![
+                VirtualInlayHint {
+                    label: ": integer".to_string(),
+                    line: 5,
+                    pos: 28,
+                    ref_file: None,
+                },
+                VirtualInlayHint {
+                    label: ": Query".to_string(),
+                    line: 4,
+                    pos: 31,
+                    ref_file: Some("".to_string()),
+                },
+                VirtualInlayHint {
+                    label: ": Query".to_string(),
+                    line: 3,
+                    pos: 38,
+                    ref_file: Some("".to_string()),
+                },
+            ]
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_self_hint() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let mut emmyrc = ws.get_emmyrc();
+        emmyrc.hint.self_hint = true;
+        ws.update_emmyrc(emmyrc);
+        check!(ws.check_inlay_hint(
+            r#"
+                ---@class Player
+                local Player = {}
+
+                function Player:update(dt)
+                end
+
+                function Player.draw(self)
+                end
+            "#,
+            vec![
+                VirtualInlayHint {
+                    label: "self: Player,".to_string(),
+                    line: 4,
+                    pos: 39,
+                    ref_file: Some("".to_string()),
+                },
+                VirtualInlayHint {
+                    label: ": Player".to_string(),
+                    line: 7,
+                    pos: 41,
+                    ref_file: Some("".to_string()),
+                },
+            ]
+        ));
+        Ok(())
+    }
 }
@@ -1,12 +1,17 @@
 mod code_actions_test;
+mod code_lens_test;
+mod command_test;
 mod completion_resolve_test;
 mod completion_test;
 mod definition_test;
+mod document_symbol_test;
 mod hover_function_test;
 mod hover_test;
 mod implementation_test;
 mod inlay_hint_test;
+mod moniker_test;
 mod references_test;
 mod rename_test;
 mod semantic_token_test;
 mod signature_helper_test;
+mod will_rename_files_test;
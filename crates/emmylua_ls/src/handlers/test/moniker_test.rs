@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::test_lib::{ProviderVirtualWorkspace, check};
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_module_member() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        ws.def_file(
+            "sdk/shape.lua",
+            r#"
+                ---@class Shape
+                local Shape = {}
+
+                function Shape:area()
+                    return 0
+                end
+
+                local M = {}
+
+                function M.new()
+                    return Shape
+                end
+
+                return M
+            "#,
+        );
+        check!(ws.check_moniker(
+            r#"
+                local shape = require("sdk.shape")
+                shape.ne<??>w()
+            "#,
+            Some("sdk.shape:new"),
+        ));
+        check!(ws.check_moniker(
+            r#"
+                local shape = require("sdk.shape")
+                shape.new():ar<??>ea()
+            "#,
+            Some("sdk.shape:Shape.area"),
+        ));
+        check!(ws.check_moniker(
+            r#"
+                ---@type Sha<??>pe
+                local s
+            "#,
+            Some("sdk.shape:Shape"),
+        ));
+        check!(ws.check_moniker(
+            r#"
+                local sh<??>ape = require("sdk.shape")
+            "#,
+            None,
+        ));
+        Ok(())
+    }
+}
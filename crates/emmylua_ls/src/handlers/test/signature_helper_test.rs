@@ -44,4 +44,91 @@ mod tests {
         ));
         Ok(())
     }
+
+    #[gtest]
+    fn test_overload_active_signature() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let def = r#"
+                ---@overload fun(name: string, age: integer): string
+                ---@overload fun(id: integer, flag: boolean): string
+                ---@param a table
+                ---@return string
+                local function find(a)
+                end
+        "#;
+        check!(ws.check_signature_helper(
+            &format!("{def}\nfind(1, <??>)"),
+            VirtualSignatureHelp {
+                target_label: "find(id: integer, flag: boolean): string".to_string(),
+                active_signature: 0,
+                active_parameter: 1,
+            },
+        ));
+        check!(ws.check_signature_helper(
+            &format!("{def}\nfind(\"x\", <??>)"),
+            VirtualSignatureHelp {
+                target_label: "find(name: string, age: integer): string".to_string(),
+                active_signature: 0,
+                active_parameter: 1,
+            },
+        ));
+        check!(ws.check_signature_helper(
+            &format!("{def}\nfind(<??>)"),
+            VirtualSignatureHelp {
+                target_label: "find(a: table): string".to_string(),
+                active_signature: 0,
+                active_parameter: 0,
+            },
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_variadic_active_parameter() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_signature_helper(
+            r#"
+                ---@class Logger
+                local Logger = {}
+
+                ---@param fmt string
+                ---@param ... any
+                function Logger:log(fmt, ...)
+                end
+
+                Logger:log("%s %s", 1, 2, <??>)
+            "#,
+            VirtualSignatureHelp {
+                target_label: "Logger:log(fmt: string, ...: any)".to_string(),
+                active_signature: 0,
+                active_parameter: 1,
+            },
+        ));
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_table_arg_active_parameter() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        check!(ws.check_signature_helper(
+            r#"
+                ---@class OpenOptions
+                ---@field width integer
+                ---@field height integer
+
+                ---@param name string
+                ---@param opts OpenOptions
+                local function open(name, opts)
+                end
+
+                open("main", { width = 1, <??> })
+            "#,
+            VirtualSignatureHelp {
+                target_label: "open(name: string, opts: OpenOptions)".to_string(),
+                active_signature: 0,
+                active_parameter: 1,
+            },
+        ));
+        Ok(())
+    }
 }
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use crate::handlers::{test_lib::ProviderVirtualWorkspace, workspace::will_rename_files};
+    use emmylua_code_analysis::FileId;
+    use googletest::prelude::*;
+    use std::path::PathBuf;
+
+    fn get_path(ws: &ProviderVirtualWorkspace, file_id: FileId) -> PathBuf {
+        ws.analysis
+            .compilation
+            .get_db()
+            .get_vfs()
+            .get_file_path(&file_id)
+            .unwrap()
+            .clone()
+    }
+
+    #[gtest]
+    fn test_rename_file() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let mut emmyrc = ws.get_emmyrc();
+        emmyrc
+            .runtime
+            .require_like_function
+            .push("import".to_string());
+        ws.update_emmyrc(emmyrc);
+        let b = ws.def_file("a/b.lua", "return {}");
+        ws.def_file("a/c.lua", "return {}");
+        ws.def_file(
+            "main.lua",
+            r#"local b = require("a.b")
+local b2 = import 'a/b'
+local c = require("a.c")
+"#,
+        );
+
+        let old_path = get_path(&ws, b);
+        let new_path = old_path.parent().unwrap().join("d.lua");
+        let edit = will_rename_files(&ws.analysis, &[(old_path, new_path)]).or_fail()?;
+        verify_eq!(
+            ws.apply_workspace_edit(edit)?,
+            vec![(
+                "main.lua".to_string(),
+                r#"local b = require("a.d")
+local b2 = import 'a/d'
+local c = require("a.c")
+"#
+                .to_string()
+            )]
+        )
+    }
+
+    #[gtest]
+    fn test_rename_folder() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let b = ws.def_file("a/b.lua", "return {}");
+        ws.def_file("a/c.lua", "local b = require('a.b')\nreturn {}");
+        ws.def_file(
+            "main.lua",
+            r#"local b = require("a.b")
+local c = require("a.c")
+"#,
+        );
+
+        let old_dir = get_path(&ws, b).parent().unwrap().to_path_buf();
+        let new_dir = old_dir.parent().unwrap().join("lib").join("x");
+        let edit = will_rename_files(&ws.analysis, &[(old_dir, new_dir)]).or_fail()?;
+        verify_eq!(
+            ws.apply_workspace_edit(edit)?,
+            vec![
+                (
+                    "c.lua".to_string(),
+                    "local b = require('lib.x.b')\nreturn {}".to_string()
+                ),
+                (
+                    "main.lua".to_string(),
+                    r#"local b = require("lib.x.b")
+local c = require("lib.x.c")
+"#
+                    .to_string()
+                ),
+            ]
+        )
+    }
+
+    #[gtest]
+    fn test_rename_keeps_delimiter_and_skips_variables() -> Result<()> {
+        let mut ws = ProviderVirtualWorkspace::new();
+        let b = ws.def_file("a/b.lua", "return {}");
+        ws.def_file(
+            "main.lua",
+            r#"local b = require [[a.b]]
+local b2 = require([==[a.b]==])
+local name = "a.b"
+local b3 = require(name)
+"#,
+        );
+
+        let old_path = get_path(&ws, b);
+        let new_path = old_path.parent().unwrap().join("d.lua");
+        let edit = will_rename_files(&ws.analysis, &[(old_path, new_path)]).or_fail()?;
+        verify_eq!(
+            ws.apply_workspace_edit(edit)?,
+            vec![(
+                "main.lua".to_string(),
+                r#"local b = require [[a.d]]
+local b2 = require([==[a.d]==])
+local name = "a.b"
+local b3 = require(name)
+"#
+                .to_string()
+            )]
+        )
+    }
+}
@@ -1,20 +1,30 @@
-use emmylua_code_analysis::{EmmyLuaAnalysis, Emmyrc, FileId, VirtualUrlGenerator};
+use emmylua_code_analysis::{
+    EmmyLuaAnalysis, Emmyrc, FileId, VirtualUrlGenerator, apply_text_edits,
+};
 use googletest::prelude::*;
 use itertools::Itertools;
+use lsp_server::{Connection, Message, Response};
+use lsp_types::{
+    ApplyWorkspaceEditParams, ApplyWorkspaceEditResponse, ClientCapabilities, ShowMessageParams,
+};
 use lsp_types::{
     CodeActionOrCommand, CompletionItem, CompletionItemKind, CompletionResponse,
-    CompletionTriggerKind, Documentation, GotoDefinitionResponse, Hover, HoverContents,
-    InlayHintLabel, Location, MarkupContent, Position, SemanticToken, SemanticTokensResult,
-    SignatureHelpContext, SignatureHelpTriggerKind, SignatureInformation, TextEdit,
+    CompletionTriggerKind, DocumentSymbol, Documentation, GotoDefinitionResponse, Hover,
+    HoverContents, InlayHintLabel, Location, MarkupContent, Position, Range, SemanticToken,
+    SemanticTokensResult, SignatureHelpContext, SignatureHelpTriggerKind, SignatureInformation,
+    TextEdit, WorkspaceEdit,
 };
+use serde_json::Value;
 use std::collections::HashSet;
+use std::time::Duration;
 use std::{ops::Deref, sync::Arc};
 use tokio_util::sync::CancellationToken;
 
 use crate::{
-    context::ClientId,
+    context::{ClientId, RuntimeTraceCache, ServerContext},
     handlers::{
         code_actions::code_action,
+        command::dispatch_command,
         completion::{completion, completion_resolve},
         inlay_hint::inlay_hint,
         rename::rename,
@@ -23,7 +33,14 @@ use crate::{
     },
 };
 
-use super::{hover::hover, implementation::implementation, references::references};
+use super::{
+    code_lens::{build_code_lens, resolve_code_lens},
+    document_symbol::build_document_symbol,
+    hover::hover,
+    implementation::implementation,
+    moniker::moniker,
+    references::references,
+};
 
 /// Calling this macro on a [`Result`] is equivalent to `result?`,
 /// but adds info about current location to the error message.
@@ -100,6 +117,16 @@ pub struct VirtualCodeAction {
     pub title: String,
 }
 
+/// What a workspace command sent to the client.
+#[derive(Debug, Default)]
+pub struct VirtualCommandResult {
+    pub value: Option<Value>,
+    /// Edits sent with `workspace/applyEdit`.
+    pub edits: Vec<WorkspaceEdit>,
+    /// Messages sent with `window/showMessage`.
+    pub messages: Vec<ShowMessageParams>,
+}
+
 #[allow(unused)]
 impl ProviderVirtualWorkspace {
     pub fn new() -> Self {
@@ -334,6 +361,79 @@ impl ProviderVirtualWorkspace {
         Self::assert_definition(result, expected)
     }
 
+    pub fn check_moniker(&mut self, block_str: &str, expected: Option<&str>) -> Result<()> {
+        let (content, position) = Self::handle_file_content(block_str)?;
+        let file_id = self.def(&content);
+        let identifier = moniker(&self.analysis, file_id, position)
+            .and_then(|monikers| monikers.into_iter().next())
+            .map(|moniker| moniker.identifier);
+        verify_eq!(identifier.as_deref(), expected)
+    }
+
+    /// Resolves all code lenses of the file and compares `(line, title)` pairs in order.
+    pub fn check_code_lens(&mut self, block_str: &str, expected: Vec<(u32, &str)>) -> Result<()> {
+        let file_id = self.def(block_str);
+        let compilation = &self.analysis.compilation;
+        let semantic_model = compilation
+            .get_semantic_model(file_id)
+            .ok_or("failed to get semantic model")
+            .or_fail()?;
+        let lenses = build_code_lens(&semantic_model)
+            .ok_or("failed to get code lens")
+            .or_fail()?;
+        let items = lenses
+            .into_iter()
+            .filter_map(|lens| resolve_code_lens(compilation, lens, ClientId::VSCode))
+            .filter_map(|lens| Some((lens.range.start.line, lens.command?.title)))
+            .collect::<Vec<_>>();
+        let expected = expected
+            .into_iter()
+            .map(|(line, title)| (line, title.to_string()))
+            .collect::<Vec<_>>();
+        verify_eq!(items, expected)
+    }
+
+    /// Builds the document symbols of the file and compares `(name, start line, end line)` of
+    /// every symbol in depth-first order, names are indented by two spaces per nesting level.
+    pub fn check_document_symbols(
+        &mut self,
+        block_str: &str,
+        expected: Vec<(&str, u32, u32)>,
+    ) -> Result<()> {
+        fn flatten(
+            symbols: Vec<DocumentSymbol>,
+            depth: usize,
+            result: &mut Vec<(String, u32, u32)>,
+        ) {
+            for symbol in symbols {
+                result.push((
+                    format!("{}{}", "  ".repeat(depth), symbol.name),
+                    symbol.range.start.line,
+                    symbol.range.end.line,
+                ));
+                flatten(symbol.children.unwrap_or_default(), depth + 1, result);
+            }
+        }
+
+        let file_id = self.def(block_str);
+        let semantic_model = self
+            .analysis
+            .compilation
+            .get_semantic_model(file_id)
+            .ok_or("failed to get semantic model")
+            .or_fail()?;
+        let root = build_document_symbol(&semantic_model)
+            .ok_or("failed to get document symbols")
+            .or_fail()?;
+        let mut symbols = Vec::new();
+        flatten(root.children.unwrap_or_default(), 0, &mut symbols);
+        let expected = expected
+            .into_iter()
+            .map(|(name, start, end)| (name.to_string(), start, end))
+            .collect::<Vec<_>>();
+        verify_eq!(symbols, expected)
+    }
+
     pub fn check_definition(
         &mut self,
         block_str: &str,
@@ -431,6 +531,10 @@ impl ProviderVirtualWorkspace {
                 )
             })
             .or_fail()?;
+        verify_that!(
+            result.active_signature,
+            eq(Some(expected.active_signature as u32))
+        )?;
         verify_that!(
             signature,
             all![
@@ -519,9 +623,15 @@ impl ProviderVirtualWorkspace {
             .diagnose_file(file_id, CancellationToken::new())
             .ok_or("failed to diagnose file")
             .or_fail()?;
-        let result = code_action(&self.analysis, file_id, result)
-            .ok_or("failed to generate code action")
-            .or_fail()?;
+        let result = code_action(
+            &self.analysis,
+            &RuntimeTraceCache::new(),
+            file_id,
+            Range::default(),
+            result,
+        )
+        .ok_or("failed to generate code action")
+        .or_fail()?;
 
         fn get_code_action_label(response: &CodeActionOrCommand) -> String {
             match response {
@@ -539,6 +649,125 @@ impl ProviderVirtualWorkspace {
         )
     }
 
+    /// Applies the quick fix titled `title` offered for the file's diagnostics and compares the
+    /// result.
+    pub fn check_fix(&mut self, block_str: &str, title: &str, expected: &str) -> Result<()> {
+        let file_id = self.def(block_str);
+        let diagnostics = self
+            .analysis
+            .diagnose_file(file_id, CancellationToken::new())
+            .ok_or("failed to diagnose file")
+            .or_fail()?;
+        let actions = code_action(
+            &self.analysis,
+            &RuntimeTraceCache::new(),
+            file_id,
+            Range::default(),
+            diagnostics,
+        )
+        .unwrap_or_default();
+        let edit = actions
+            .into_iter()
+            .find_map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) if action.title == title => action.edit,
+                _ => None,
+            })
+            .ok_or("code action not found")
+            .or_fail()?;
+        let files = self.apply_workspace_edit(edit)?;
+        let (_, text) = files.into_iter().next().or_fail()?;
+        verify_eq!(text, expected)
+    }
+
+    /// Applies the code action titled `title` at the selection between two `<??>` markers (or
+    /// at a single `<??>` cursor) and compares the result, `None` meaning no such action.
+    pub fn check_refactor(
+        &mut self,
+        block_str: &str,
+        title: &str,
+        expected: Option<&str>,
+    ) -> Result<()> {
+        let (content, range) = Self::handle_file_range_content(block_str)?;
+        let file_id = self.def(&content);
+        let actions = code_action(
+            &self.analysis,
+            &RuntimeTraceCache::new(),
+            file_id,
+            range,
+            Vec::new(),
+        )
+        .unwrap_or_default();
+        let edit = actions.into_iter().find_map(|action| match action {
+            CodeActionOrCommand::CodeAction(action) if action.title == title => action.edit,
+            _ => None,
+        });
+        let Some(expected) = expected else {
+            return verify_that!(edit, none());
+        };
+        let edits = edit
+            .and_then(|edit| edit.changes)
+            .and_then(|changes| changes.into_values().next())
+            .ok_or("code action not found")
+            .or_fail()?;
+        let semantic_model = self
+            .analysis
+            .compilation
+            .get_semantic_model(file_id)
+            .or_fail()?;
+        let document = semantic_model.get_document();
+        let edits = edits
+            .into_iter()
+            .map(|edit| Some((document.to_rowan_range(edit.range)?, edit.new_text)))
+            .collect::<Option<Vec<_>>>()
+            .or_fail()?;
+        verify_eq!(apply_text_edits(&content, edits), expected)
+    }
+
+    /// Applies `edit` and returns the new text of every changed file, keyed by file name.
+    pub fn apply_workspace_edit(&self, edit: WorkspaceEdit) -> Result<Vec<(String, String)>> {
+        let mut result = Vec::new();
+        for (uri, edits) in edit.changes.or_fail()? {
+            let file_id = self.analysis.get_file_id(&uri).or_fail()?;
+            let semantic_model = self
+                .analysis
+                .compilation
+                .get_semantic_model(file_id)
+                .or_fail()?;
+            let document = semantic_model.get_document();
+            let edits = edits
+                .into_iter()
+                .map(|edit| Some((document.to_rowan_range(edit.range)?, edit.new_text)))
+                .collect::<Option<Vec<_>>>()
+                .or_fail()?;
+            let file_name = document.get_file_name().or_fail()?;
+            result.push((file_name, apply_text_edits(document.get_text(), edits)));
+        }
+        result.sort();
+        Ok(result)
+    }
+
+    fn handle_file_range_content(content: &str) -> Result<(String, Range)> {
+        let markers = content
+            .match_indices("<??>")
+            .map(|(pos, _)| pos)
+            .collect::<Vec<_>>();
+        let (start, end) = match markers[..] {
+            [cursor] => (cursor, cursor),
+            [start, end] => (start, end - "<??>".len()),
+            _ => return Err("content should include one or two <??>").or_fail(),
+        };
+        let content = content.replace("<??>", "");
+
+        fn to_position(content: &str, byte_pos: usize) -> Position {
+            let before = &content[..byte_pos];
+            let line = before.matches('\n').count();
+            let column = before.rsplit('\n').next().unwrap_or("").chars().count();
+            Position::new(line as u32, column as u32)
+        }
+        let range = Range::new(to_position(&content, start), to_position(&content, end));
+        Ok((content, range))
+    }
+
     pub fn check_semantic_token(&mut self, block_str: &str, expected: Vec<u32>) -> Result<()> {
         let result_data = self.get_semantic_token_data(block_str)?;
         verify_eq!(result_data, expected)
@@ -646,4 +875,66 @@ impl ProviderVirtualWorkspace {
             .or_fail()?;
         Self::assert_locations(result, expected)
     }
+
+    /// Runs a workspace command against a server connected to an in-memory client, which
+    /// accepts every `workspace/applyEdit` request.
+    pub fn execute_command(&mut self, command: &str, args: Vec<Value>) -> VirtualCommandResult {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build runtime");
+        let analysis = std::mem::replace(&mut self.analysis, EmmyLuaAnalysis::new());
+        let (result, analysis) = runtime.block_on(async move {
+            let (server, client) = Connection::memory();
+            let context = ServerContext::new(server, ClientCapabilities::default());
+            let snapshot = context.snapshot();
+            *snapshot.analysis().write().await = analysis;
+
+            let command = command.to_string();
+            let task_snapshot = snapshot.clone();
+            let task =
+                tokio::spawn(async move { dispatch_command(task_snapshot, &command, args).await });
+            let mut result = VirtualCommandResult::default();
+            loop {
+                let finished = task.is_finished();
+                match client.receiver.recv_timeout(Duration::from_millis(20)) {
+                    Ok(Message::Request(request)) if request.method == "workspace/applyEdit" => {
+                        if let Ok(params) =
+                            serde_json::from_value::<ApplyWorkspaceEditParams>(request.params)
+                        {
+                            result.edits.push(params.edit);
+                        }
+                        let response = ApplyWorkspaceEditResponse {
+                            applied: true,
+                            failure_reason: None,
+                            failed_change: None,
+                        };
+                        context
+                            .send_response(Response::new_ok(request.id, response))
+                            .await;
+                    }
+                    Ok(Message::Notification(notification))
+                        if notification.method == "window/showMessage" =>
+                    {
+                        if let Ok(params) = serde_json::from_value(notification.params) {
+                            result.messages.push(params);
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(_) if finished => break,
+                    Err(_) => {}
+                }
+            }
+            result.value = task.await.ok().flatten();
+
+            let analysis = std::mem::replace(
+                &mut *snapshot.analysis().write().await,
+                EmmyLuaAnalysis::new(),
+            );
+            context.close().await;
+            (result, analysis)
+        });
+        self.analysis = analysis;
+        result
+    }
 }
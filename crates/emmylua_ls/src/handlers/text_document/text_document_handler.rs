@@ -1,7 +1,8 @@
 use emmylua_code_analysis::uri_to_file_path;
 use lsp_types::{
     DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams,
+    DidSaveTextDocumentParams, MessageType, Position, ShowMessageParams,
+    TextDocumentContentChangeEvent,
 };
 use std::time::Duration;
 
@@ -93,7 +94,71 @@ pub async fn on_did_change_text_document(
     params: DidChangeTextDocumentParams,
 ) -> Option<()> {
     let uri = params.text_document.uri;
-    let text = params.content_changes.first()?.text.clone();
+    let mut changes = params.content_changes;
+    let (is_open, is_desynced) = {
+        let workspace_manager = context.workspace_manager().read().await;
+        (
+            workspace_manager.is_open_file(&uri),
+            workspace_manager.is_file_desynced(&uri),
+        )
+    };
+    if is_desynced {
+        // 失去同步的文档只能从整篇替换恢复
+        let full_change = changes.iter().rposition(|change| change.range.is_none())?;
+        changes.drain(..full_change);
+    }
+    let base_text = if is_open && !is_desynced {
+        None
+    } else {
+        let analysis = context.analysis().read().await;
+        let text = analysis
+            .get_file_id(&uri)
+            .and_then(|file_id| {
+                analysis
+                    .compilation
+                    .get_db()
+                    .get_vfs()
+                    .get_file_content(&file_id)
+                    .cloned()
+            })
+            .unwrap_or_default();
+        Some(text)
+    };
+
+    // 直接在已打开文档的文本上应用变更, 避免复制整个文档
+    let result = {
+        let mut workspace_manager = context.workspace_manager().write().await;
+        match base_text {
+            Some(mut text) => apply_content_changes(&mut text, changes).map(|()| {
+                workspace_manager.sync_open_file(uri.clone(), text.clone());
+                text
+            }),
+            None => {
+                let text = workspace_manager.get_open_file_text_mut(&uri)?;
+                apply_content_changes(text, changes).map(|()| text.clone())
+            }
+        }
+    };
+    let text = match result {
+        Ok(text) => text,
+        Err(err) => {
+            log::error!("Failed to apply changes to {}: {}", uri.as_str(), err);
+            context
+                .workspace_manager()
+                .write()
+                .await
+                .mark_file_desynced(&uri);
+            context.client().show_message(ShowMessageParams {
+                typ: MessageType::WARNING,
+                message: t!(
+                    "%{uri} is out of sync with the editor, reopen it to resync",
+                    uri = uri.as_str()
+                )
+                .to_string(),
+            });
+            return None;
+        }
+    };
 
     // Check if file should be filtered before acquiring locks
     // Follow lock order: workspace_manager (read) -> analysis (write)
@@ -109,11 +174,6 @@ pub async fn on_did_change_text_document(
         }
     };
 
-    {
-        let mut workspace = context.workspace_manager().write().await;
-        workspace.sync_open_file(uri.clone(), text.clone());
-    }
-
     if !should_process {
         return None;
     }
@@ -148,6 +208,59 @@ pub async fn on_did_change_text_document(
     Some(())
 }
 
+/// Applies the changes of a `textDocument/didChange` notification in order. Positions use
+/// UTF-16 columns, the default LSP position encoding. A change whose range is not inside the
+/// text is an error, since the text then no longer matches the editor.
+fn apply_content_changes(
+    text: &mut String,
+    changes: Vec<TextDocumentContentChangeEvent>,
+) -> Result<(), String> {
+    for change in changes {
+        let Some(range) = change.range else {
+            *text = change.text;
+            continue;
+        };
+
+        let start = position_to_offset(text, range.start)
+            .ok_or_else(|| format!("invalid position {:?}", range.start))?;
+        let end = position_to_offset(text, range.end)
+            .ok_or_else(|| format!("invalid position {:?}", range.end))?;
+        if end < start {
+            return Err(format!("invalid range {:?}", range));
+        }
+        text.replace_range(start..end, &change.text);
+    }
+
+    Ok(())
+}
+
+/// Converts a position with a UTF-16 column to a byte offset. As the LSP specification says, a
+/// column past the end of the line means the end of the line.
+fn position_to_offset(text: &str, position: Position) -> Option<usize> {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        line_start += text[line_start..].find('\n')? + 1;
+    }
+    let line = &text[line_start..];
+    let line = &line[..line.find('\n').unwrap_or(line.len())];
+    let line = line.strip_suffix('\r').unwrap_or(line);
+
+    let column = position.character as usize;
+    let mut utf16_column = 0;
+    for (offset, c) in line.char_indices() {
+        if utf16_column == column {
+            return Some(line_start + offset);
+        }
+        utf16_column += c.len_utf16();
+        // 列落在代理对中间
+        if utf16_column > column {
+            return None;
+        }
+    }
+
+    Some(line_start + line.len())
+}
+
 pub async fn on_did_close_document(
     context: ServerContextSnapshot,
     params: DidCloseTextDocumentParams,
@@ -197,3 +310,96 @@ pub async fn on_did_close_document(
 
     Some(())
 }
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::{Position, Range, TextDocumentContentChangeEvent};
+
+    use super::apply_content_changes;
+
+    fn change(range: (u32, u32, u32, u32), text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: Some(Range::new(
+                Position::new(range.0, range.1),
+                Position::new(range.2, range.3),
+            )),
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    fn apply(text: &str, changes: Vec<TextDocumentContentChangeEvent>) -> Result<String, String> {
+        let mut text = text.to_string();
+        apply_content_changes(&mut text, changes)?;
+        Ok(text)
+    }
+
+    #[test]
+    fn test_multiple_changes_apply_in_order() {
+        let changes = vec![
+            change((0, 6, 0, 7), "b"),
+            change((1, 0, 1, 0), "local c = 3\n"),
+            change((0, 10, 0, 11), "10"),
+        ];
+        assert_eq!(
+            apply("local a = 1\nreturn a\n", changes).unwrap(),
+            "local b = 10\nlocal c = 3\nreturn a\n"
+        );
+    }
+
+    #[test]
+    fn test_full_change_resets_text() {
+        let full = TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: "x\n".to_string(),
+        };
+        let changes = vec![change((0, 0, 0, 1), "y"), full, change((0, 1, 0, 1), "z")];
+        assert_eq!(apply("abc", changes).unwrap(), "xz\n");
+    }
+
+    #[test]
+    fn test_utf16_columns() {
+        // 😀 占两个 UTF-16 单元, 4 个字节
+        let text = "local s = '😀' .. x\n";
+        assert_eq!(
+            apply(text, vec![change((0, 18, 0, 19), "y")]).unwrap(),
+            "local s = '😀' .. y\n"
+        );
+        assert_eq!(
+            apply(text, vec![change((0, 11, 0, 13), "中")]).unwrap(),
+            "local s = '中' .. x\n"
+        );
+        assert!(apply(text, vec![change((0, 12, 0, 13), "")]).is_err());
+    }
+
+    #[test]
+    fn test_crlf_lines() {
+        let text = "a = 1\r\nb = 2\r\n";
+        assert_eq!(
+            apply(text, vec![change((1, 4, 1, 5), "3")]).unwrap(),
+            "a = 1\r\nb = 3\r\n"
+        );
+        // 行尾之后的列指向 \r 之前
+        assert_eq!(
+            apply(text, vec![change((0, 99, 0, 99), ";")]).unwrap(),
+            "a = 1;\r\nb = 2\r\n"
+        );
+        assert_eq!(
+            apply(text, vec![change((0, 5, 1, 0), " ")]).unwrap(),
+            "a = 1 b = 2\r\n"
+        );
+    }
+
+    #[test]
+    fn test_end_of_file_ranges() {
+        assert_eq!(
+            apply("a\n", vec![change((1, 0, 1, 0), "b")]).unwrap(),
+            "a\nb"
+        );
+        assert_eq!(apply("a", vec![change((0, 1, 0, 1), "b")]).unwrap(), "ab");
+        assert_eq!(apply("", vec![change((0, 0, 0, 0), "a")]).unwrap(), "a");
+        assert!(apply("a\n", vec![change((2, 0, 2, 0), "b")]).is_err());
+        assert!(apply("a\nb", vec![change((1, 1, 0, 0), "")]).is_err());
+    }
+}
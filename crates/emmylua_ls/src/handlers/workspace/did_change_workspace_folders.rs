@@ -0,0 +1,26 @@
+use emmylua_code_analysis::uri_to_file_path;
+use lsp_types::DidChangeWorkspaceFoldersParams;
+
+use crate::context::ServerContextSnapshot;
+
+pub async fn on_did_change_workspace_folders_handler(
+    context: ServerContextSnapshot,
+    params: DidChangeWorkspaceFoldersParams,
+) -> Option<()> {
+    let added = params
+        .event
+        .added
+        .iter()
+        .filter_map(|folder| uri_to_file_path(&folder.uri))
+        .collect();
+    let removed = params
+        .event
+        .removed
+        .iter()
+        .filter_map(|folder| uri_to_file_path(&folder.uri))
+        .collect();
+
+    let mut workspace_manager = context.workspace_manager().write().await;
+    workspace_manager.change_workspace_folders(context.clone(), added, removed);
+    Some(())
+}
@@ -1,11 +1,18 @@
+mod did_change_workspace_folders;
 mod did_rename_files;
+mod will_rename_files;
 
+pub use did_change_workspace_folders::on_did_change_workspace_folders_handler;
 pub use did_rename_files::on_did_rename_files_handler;
 use lsp_types::{
     ClientCapabilities, FileOperationFilter, FileOperationPattern, FileOperationPatternOptions,
-    FileOperationRegistrationOptions, ServerCapabilities,
-    WorkspaceFileOperationsServerCapabilities, WorkspaceServerCapabilities,
+    FileOperationRegistrationOptions, OneOf, ServerCapabilities,
+    WorkspaceFileOperationsServerCapabilities, WorkspaceFoldersServerCapabilities,
+    WorkspaceServerCapabilities,
 };
+pub use will_rename_files::on_will_rename_files_handler;
+#[cfg(test)]
+pub(crate) use will_rename_files::will_rename_files;
 
 use crate::handlers::RegisterCapabilities;
 
@@ -13,23 +20,28 @@ pub struct WorkspaceCapabilities;
 
 impl RegisterCapabilities for WorkspaceCapabilities {
     fn register_capabilities(server_capabilities: &mut ServerCapabilities, _: &ClientCapabilities) {
+        let registration = FileOperationRegistrationOptions {
+            filters: vec![FileOperationFilter {
+                scheme: Some(String::from("file")),
+                pattern: FileOperationPattern {
+                    glob: "**/*".to_string(),
+                    matches: None,
+                    options: Some(FileOperationPatternOptions {
+                        ignore_case: Some(true),
+                    }),
+                },
+            }],
+        };
         server_capabilities.workspace = Some(WorkspaceServerCapabilities {
+            workspace_folders: Some(WorkspaceFoldersServerCapabilities {
+                supported: Some(true),
+                change_notifications: Some(OneOf::Left(true)),
+            }),
             file_operations: Some(WorkspaceFileOperationsServerCapabilities {
-                did_rename: Some(FileOperationRegistrationOptions {
-                    filters: vec![FileOperationFilter {
-                        scheme: Some(String::from("file")),
-                        pattern: FileOperationPattern {
-                            glob: "**/*".to_string(),
-                            matches: None,
-                            options: Some(FileOperationPatternOptions {
-                                ignore_case: Some(true),
-                            }),
-                        },
-                    }],
-                }),
+                did_rename: Some(registration.clone()),
+                will_rename: Some(registration),
                 ..Default::default()
             }),
-            ..Default::default()
         });
     }
 }
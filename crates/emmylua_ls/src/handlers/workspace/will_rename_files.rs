@@ -0,0 +1,146 @@
+use std::{collections::HashMap, path::PathBuf, str::FromStr};
+
+use emmylua_code_analysis::{EmmyLuaAnalysis, FileId, SemanticModel, uri_to_file_path};
+use emmylua_parser::{LuaAstNode, LuaAstToken, LuaCallExpr, LuaExpr, LuaLiteralToken};
+use lsp_types::{RenameFilesParams, TextEdit, Uri, WorkspaceEdit};
+use tokio_util::sync::CancellationToken;
+
+use crate::context::ServerContextSnapshot;
+
+pub async fn on_will_rename_files_handler(
+    context: ServerContextSnapshot,
+    params: RenameFilesParams,
+    _: CancellationToken,
+) -> Option<WorkspaceEdit> {
+    let mut renames = Vec::new();
+    for file_rename in params.files {
+        let old_path = uri_to_file_path(&Uri::from_str(&file_rename.old_uri).ok()?)?;
+        let new_path = uri_to_file_path(&Uri::from_str(&file_rename.new_uri).ok()?)?;
+        renames.push((old_path, new_path));
+    }
+
+    let analysis = context.analysis().read().await;
+    will_rename_files(&analysis, &renames)
+}
+
+/// Rewrites every `require` of a module affected by the renames, before the files move.
+/// Renaming a directory renames every module below it.
+pub(crate) fn will_rename_files(
+    analysis: &EmmyLuaAnalysis,
+    renames: &[(PathBuf, PathBuf)],
+) -> Option<WorkspaceEdit> {
+    let db = analysis.compilation.get_db();
+    let module_index = db.get_module_index();
+    let vfs = db.get_vfs();
+
+    // old file id => new full module name
+    let mut new_module_paths: HashMap<FileId, String> = HashMap::new();
+    for file_id in vfs.get_all_local_file_ids() {
+        if module_index.is_std(&file_id) {
+            continue;
+        }
+        let Some(file_path) = vfs.get_file_path(&file_id) else {
+            continue;
+        };
+        for (old_path, new_path) in renames {
+            let Ok(relative_path) = file_path.strip_prefix(old_path) else {
+                continue;
+            };
+            let new_file_path = if relative_path.as_os_str().is_empty() {
+                new_path.clone()
+            } else {
+                new_path.join(relative_path)
+            };
+            let Some((module_path, _)) = module_index.extract_module_path(new_file_path.to_str()?)
+            else {
+                continue;
+            };
+            new_module_paths.insert(file_id, module_path.replace(['\\', '/'], "."));
+            break;
+        }
+    }
+    if new_module_paths.is_empty() {
+        return None;
+    }
+
+    #[allow(clippy::mutable_key_type)]
+    let mut changes: HashMap<Uri, Vec<TextEdit>> = HashMap::new();
+    for file_id in vfs.get_all_local_file_ids() {
+        if module_index.is_std(&file_id) {
+            continue;
+        }
+        let Some(semantic_model) = analysis.compilation.get_semantic_model(file_id) else {
+            continue;
+        };
+        let edits = semantic_model
+            .get_root()
+            .descendants::<LuaCallExpr>()
+            .filter(|call_expr| call_expr.is_require())
+            .filter_map(|call_expr| {
+                rewrite_require_path(&semantic_model, &call_expr, &new_module_paths)
+            })
+            .collect::<Vec<_>>();
+        if !edits.is_empty() {
+            changes.insert(semantic_model.get_document().get_uri(), edits);
+        }
+    }
+
+    Some(WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    })
+}
+
+fn rewrite_require_path(
+    semantic_model: &SemanticModel,
+    call_expr: &LuaCallExpr,
+    new_module_paths: &HashMap<FileId, String>,
+) -> Option<TextEdit> {
+    // only literal paths are rewritten, a path held in a variable is left as is
+    let LuaExpr::LiteralExpr(literal_expr) = call_expr.get_args_list()?.get_args().next()? else {
+        return None;
+    };
+    let LuaLiteralToken::String(string_token) = literal_expr.get_literal()? else {
+        return None;
+    };
+    let old_path = string_token.get_value();
+    let module_info = semantic_model
+        .get_db()
+        .get_module_index()
+        .find_module(&old_path)?;
+    let new_module_path = new_module_paths.get(&module_info.file_id)?;
+
+    // keep the separator and the number of segments written in the original path
+    let separator = if old_path.contains('/') { "/" } else { "." };
+    let segment_count = old_path.split(['.', '/']).count();
+    let new_segments = new_module_path.split('.').collect::<Vec<_>>();
+    let new_path = if old_path.replace('/', ".") == module_info.full_module_name
+        || segment_count >= new_segments.len()
+    {
+        new_segments.join(separator)
+    } else {
+        new_segments[new_segments.len() - segment_count..].join(separator)
+    };
+    if new_path == old_path {
+        return None;
+    }
+
+    let (open, close) = get_string_delimiters(string_token.get_text());
+    Some(TextEdit {
+        range: semantic_model
+            .get_document()
+            .to_lsp_range(string_token.get_range())?,
+        new_text: format!("{}{}{}", open, new_path, close),
+    })
+}
+
+/// `"a.b"` -> (`"`, `"`), `[==[a.b]==]` -> (`[==[`, `]==]`)
+fn get_string_delimiters(text: &str) -> (String, String) {
+    if let Some(rest) = text.strip_prefix('[') {
+        let level = rest.chars().take_while(|c| *c == '=').count();
+        let equals = "=".repeat(level);
+        return (format!("[{}[", equals), format!("]{}]", equals));
+    }
+    let quote = text.chars().next().unwrap_or('"').to_string();
+    (quote.clone(), quote)
+}
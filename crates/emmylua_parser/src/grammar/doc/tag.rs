@@ -170,6 +170,21 @@ fn parse_generic_param(p: &mut LuaDocParser) -> DocParseResult {
 }
 
 fn parse_generic_modifier(p: &mut LuaDocParser) -> Result<(), LuaParseError> {
+    // in T / out T, 变体修饰符后面必须紧跟泛型参数名, 否则视为普通的参数名
+    if matches!(
+        (p.current_token(), p.current_token_text()),
+        (LuaTokenKind::TkIn, _) | (LuaTokenKind::TkName, "out")
+    ) && is_generic_name_followed(p)
+    {
+        let kind = if p.current_token() == LuaTokenKind::TkIn {
+            LuaTokenKind::TkDocIn
+        } else {
+            LuaTokenKind::TkDocOut
+        };
+        p.set_current_token_kind(kind);
+        p.bump();
+    }
+
     if p.current_token() == LuaTokenKind::TkName && p.current_token_text() == "const" {
         let range = p.current_token_range();
         p.set_current_token_kind(LuaTokenKind::TkDocConst);
@@ -188,6 +203,18 @@ fn parse_generic_modifier(p: &mut LuaDocParser) -> Result<(), LuaParseError> {
     Ok(())
 }
 
+fn is_generic_name_followed(p: &LuaDocParser) -> bool {
+    let rest = &p.origin_text()[p.current_token_range().end_offset()..];
+    let rest = rest.trim_start_matches([' ', '\t']);
+    let name_len = rest
+        .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+        .unwrap_or(rest.len());
+    let name = &rest[..name_len];
+    !name.is_empty()
+        && !name.starts_with(|ch: char| ch.is_ascii_digit())
+        && !matches!(name, "in" | "extends")
+}
+
 // ---@enum A
 // ---@enum A : number
 fn parse_tag_enum(p: &mut LuaDocParser) -> DocParseResult {
@@ -1105,6 +1105,42 @@ Syntax(Chunk)@0..62
         assert_ast_eq!(code, result);
     }
 
+    #[test]
+    fn test_generic_variance_modifier_doc() {
+        let code = "---@class A<in T, out R, out>\n";
+
+        let result = r#"
+Syntax(Chunk)@0..30
+  Syntax(Block)@0..30
+    Syntax(Comment)@0..29
+      Token(TkDocStart)@0..4 "---@"
+      Syntax(DocTagClass)@4..29
+        Token(TkTagClass)@4..9 "class"
+        Token(TkWhitespace)@9..10 " "
+        Token(TkName)@10..11 "A"
+        Syntax(DocGenericDeclareList)@11..29
+          Token(TkLt)@11..12 "<"
+          Syntax(DocGenericParameter)@12..16
+            Token(TkDocIn)@12..14 "in"
+            Token(TkWhitespace)@14..15 " "
+            Token(TkName)@15..16 "T"
+          Token(TkComma)@16..17 ","
+          Token(TkWhitespace)@17..18 " "
+          Syntax(DocGenericParameter)@18..23
+            Token(TkDocOut)@18..21 "out"
+            Token(TkWhitespace)@21..22 " "
+            Token(TkName)@22..23 "R"
+          Token(TkComma)@23..24 ","
+          Token(TkWhitespace)@24..25 " "
+          Syntax(DocGenericParameter)@25..28
+            Token(TkName)@25..28 "out"
+          Token(TkGt)@28..29 ">"
+    Token(TkEndOfLine)@29..30 "\n"
+        "#;
+
+        assert_ast_eq!(code, result);
+    }
+
     #[test]
     fn test_generic_const_modifier_requires_identifier() {
         let tree = LuaParser::parse("---@class A<const>\n", ParserConfig::default());
@@ -2586,6 +2622,57 @@ Syntax(Chunk)@0..123
         assert_ast_eq!(code, result);
     }
 
+    #[test]
+    fn test_template_literal_type() {
+        let code = r#"
+        ---@alias A `on${Capitalize<K>}_x`
+        ---@alias B `${T}`
+        "#;
+        let result = r#"
+Syntax(Chunk)@0..79
+  Syntax(Block)@0..79
+    Token(TkEndOfLine)@0..1 "\n"
+    Token(TkWhitespace)@1..9 "        "
+    Syntax(Comment)@9..70
+      Token(TkDocStart)@9..13 "---@"
+      Syntax(DocTagAlias)@13..43
+        Token(TkTagAlias)@13..18 "alias"
+        Token(TkWhitespace)@18..19 " "
+        Token(TkName)@19..20 "A"
+        Token(TkWhitespace)@20..21 " "
+        Syntax(TypeTemplateLiteral)@21..43
+          Token(TkDocTemplateText)@21..26 "`on${"
+          Syntax(TypeGeneric)@26..39
+            Syntax(TypeName)@26..36
+              Token(TkName)@26..36 "Capitalize"
+            Token(TkLt)@36..37 "<"
+            Syntax(DocTypeList)@37..38
+              Syntax(TypeName)@37..38
+                Token(TkName)@37..38 "K"
+            Token(TkGt)@38..39 ">"
+          Token(TkRightBrace)@39..40 "}"
+          Token(TkDocTemplateText)@40..43 "_x`"
+      Token(TkEndOfLine)@43..44 "\n"
+      Token(TkWhitespace)@44..52 "        "
+      Token(TkDocStart)@52..56 "---@"
+      Syntax(DocTagAlias)@56..70
+        Token(TkTagAlias)@56..61 "alias"
+        Token(TkWhitespace)@61..62 " "
+        Token(TkName)@62..63 "B"
+        Token(TkWhitespace)@63..64 " "
+        Syntax(TypeTemplateLiteral)@64..70
+          Token(TkDocTemplateText)@64..67 "`${"
+          Syntax(TypeName)@67..68
+            Token(TkName)@67..68 "T"
+          Token(TkRightBrace)@68..69 "}"
+          Token(TkDocTemplateText)@69..70 "`"
+    Token(TkEndOfLine)@70..71 "\n"
+    Token(TkWhitespace)@71..79 "        "
+        "#;
+
+        assert_ast_eq!(code, result);
+    }
+
     #[test]
     fn test_comment() {
         let code = r#"
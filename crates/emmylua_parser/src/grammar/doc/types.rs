@@ -200,6 +200,7 @@ fn parse_primary_type(p: &mut LuaDocParser) -> DocParseResult {
             }
         }
         LuaTokenKind::TkStringTemplateType => parse_string_template_type(p),
+        LuaTokenKind::TkDocTemplateText => parse_template_literal_type(p),
         LuaTokenKind::TkDots => parse_vararg_type(p),
         LuaTokenKind::TkDocNew => parse_constructor_type(p),
         _ => Err(LuaParseError::doc_error_from(
@@ -548,6 +549,44 @@ fn parse_string_template_type(p: &mut LuaDocParser) -> DocParseResult {
     Ok(m.complete(p))
 }
 
+// `text${Type}text`
+fn parse_template_literal_type(p: &mut LuaDocParser) -> DocParseResult {
+    let m = p.mark(LuaSyntaxKind::TypeTemplateLiteral);
+    let lexer_state = p.lexer.state;
+    while p.current_token() == LuaTokenKind::TkDocTemplateText {
+        if !p.current_token_text().ends_with("${") {
+            p.bump();
+            return Ok(m.complete(p));
+        }
+
+        p.bump();
+        parse_type(p)?;
+        if p.current_token() != LuaTokenKind::TkRightBrace {
+            return Err(LuaParseError::doc_error_from(
+                &t!(
+                    "expected %{token}, but get %{current}",
+                    token = LuaTokenKind::TkRightBrace,
+                    current = p.current_token()
+                ),
+                p.current_token_range(),
+            ));
+        }
+        // `}` 之后的文本需要按模板字面量重新切分
+        p.set_lexer_state(LuaDocLexerState::TemplateLiteral);
+        p.bump();
+        p.set_lexer_state(lexer_state);
+    }
+
+    Err(LuaParseError::doc_error_from(
+        &t!(
+            "expected %{token}, but get %{current}",
+            token = LuaTokenKind::TkDocTemplateText,
+            current = p.current_token()
+        ),
+        p.current_token_range(),
+    ))
+}
+
 // just compact luals, trivia type
 // ...<name type>
 fn parse_vararg_type(p: &mut LuaDocParser) -> DocParseResult {
@@ -164,6 +164,7 @@ pub enum LuaTokenKind {
     TkDocNew,             // new
     TkDocAs,              // as
     TkDocIn,              // in
+    TkDocOut,             // out
     TkDocInfer,           // infer
     TkDocConst,           // const
     TkDocElse,            // else (for return_cast)
@@ -176,6 +177,7 @@ pub enum LuaTokenKind {
     TkAt,                 // '@', invalid lua token, but for postfix completion
     TkDocVersionNumber,   // version number
     TkStringTemplateType, // type template
+    TkDocTemplateText,    // `text${ or }text` in template literal type
     TkDocMatch,           // =
     TKDocPath,            // path
     TkDocRegion,          // region
@@ -30,6 +30,7 @@ pub enum LuaDocLexerState {
     AttributeUse,
     Mapped,
     Extends,
+    TemplateLiteral,
 }
 
 impl LuaDocLexer<'_> {
@@ -79,6 +80,7 @@ impl LuaDocLexer<'_> {
             LuaDocLexerState::AttributeUse => self.lex_attribute_use(),
             LuaDocLexerState::Mapped => self.lex_mapped(),
             LuaDocLexerState::Extends => self.lex_extends(),
+            LuaDocLexerState::TemplateLiteral => self.lex_template_literal(),
         }
     }
 
@@ -319,6 +321,11 @@ impl LuaDocLexer<'_> {
 
                 LuaTokenKind::TkString
             }
+            '`' if is_template_literal_start(reader.tail_text()) => {
+                reader.bump();
+                read_template_text(reader);
+                LuaTokenKind::TkDocTemplateText
+            }
             ch if is_name_start(ch) || ch == '`' => {
                 let (text, str_tpl) = read_doc_name(reader);
                 if str_tpl {
@@ -698,6 +705,13 @@ impl LuaDocLexer<'_> {
         }
     }
 
+    // 模板字面量类型中 `}` 之后的文本, 直到下一个 `${` 或结尾的 '`'
+    fn lex_template_literal(&mut self) -> LuaTokenKind {
+        let reader = self.reader.as_mut().unwrap();
+        read_template_text(reader);
+        LuaTokenKind::TkDocTemplateText
+    }
+
     fn lex_number(&mut self) -> LuaTokenKind {
         enum NumberState {
             Int,
@@ -889,6 +903,32 @@ fn read_doc_name<'a>(reader: &'a mut Reader) -> (&'a str, bool /* str tpl */) {
     (reader.current_text(), str_tpl)
 }
 
+// '`' 之后在闭合的 '`' 之前出现 `${` 才视为模板字面量类型, 否则仍是 `T` 形式的字符串模板
+fn is_template_literal_start(text: &str) -> bool {
+    let body = &text[1..];
+    match body.find('`') {
+        Some(end) => body[..end].contains("${"),
+        None => body.contains("${"),
+    }
+}
+
+fn read_template_text(reader: &mut Reader) {
+    while !reader.is_eof() {
+        match reader.current_char() {
+            '`' => {
+                reader.bump();
+                return;
+            }
+            '$' if reader.next_char() == '{' => {
+                reader.bump();
+                reader.bump();
+                return;
+            }
+            _ => reader.bump(),
+        }
+    }
+}
+
 fn is_source_continue(ch: char) -> bool {
     is_name_continue(ch)
         || ch == '.'
@@ -2,6 +2,7 @@ mod lua_doc_parser;
 mod lua_parser;
 mod marker;
 mod parser_config;
+mod reparse;
 
 pub use lua_doc_parser::LuaDocParser;
 pub use lua_doc_parser::LuaDocParserState;
@@ -45,6 +45,17 @@ impl<'cache> ParserConfig<'cache> {
         self.node_cache.as_deref_mut()
     }
 
+    /// Returns a config with the same settings that shares this config's node cache.
+    pub(crate) fn reborrow(&mut self) -> ParserConfig<'_> {
+        ParserConfig {
+            level: self.level,
+            lexer_config: self.lexer_config,
+            node_cache: self.node_cache.as_deref_mut(),
+            special_like: self.special_like.clone(),
+            enable_emmylua_doc: self.enable_emmylua_doc,
+        }
+    }
+
     pub fn get_special_function(&self, name: &str) -> SpecialFunction {
         match name {
             "require" => SpecialFunction::Require,
@@ -0,0 +1,278 @@
+use rowan::{TextRange, TextSize};
+
+use super::{lua_parser::LuaParser, parser_config::ParserConfig};
+use crate::{
+    LuaAstNode, LuaStat, LuaSyntaxNode, LuaSyntaxToken, LuaSyntaxTree,
+    kind::{LuaSyntaxKind, LuaTokenKind},
+    parser_error::LuaParseError,
+};
+
+impl<'a> LuaParser<'a> {
+    /// Parses `text`, which is the text of `old_tree` with `edit_range` (in the old text)
+    /// replaced.
+    ///
+    /// Only the innermost statement enclosing the edit is parsed again when that is enough to
+    /// get the same tree as a full parse; every other green node of `old_tree` is reused.
+    /// Otherwise the whole text is parsed.
+    pub fn reparse(
+        old_tree: &LuaSyntaxTree,
+        edit_range: TextRange,
+        text: &'a str,
+        mut config: ParserConfig,
+    ) -> LuaSyntaxTree {
+        if let Some(tree) = reparse_stat(old_tree, edit_range, text, &mut config) {
+            return tree;
+        }
+
+        LuaParser::parse(text, config)
+    }
+}
+
+fn reparse_stat(
+    old_tree: &LuaSyntaxTree,
+    edit_range: TextRange,
+    text: &str,
+    config: &mut ParserConfig,
+) -> Option<LuaSyntaxTree> {
+    if old_tree.has_syntax_errors() {
+        return None;
+    }
+    let root = old_tree.get_red_root();
+    let old_len = root.text_range().end();
+    let new_len = TextSize::of(text);
+    if edit_range.end() > old_len || new_len + edit_range.len() < old_len {
+        return None;
+    }
+
+    let covering_node = match root.covering_element(edit_range) {
+        rowan::NodeOrToken::Node(node) => node,
+        rowan::NodeOrToken::Token(token) => token.parent()?,
+    };
+    for stat in covering_node.ancestors().filter_map(LuaStat::cast) {
+        let stat = stat.syntax();
+        let first_token = stat.first_token()?;
+        let last_token = stat.last_token()?;
+        // the edit must leave the first and last token of the statement alone, so that the
+        // statement still starts and ends at the same place
+        if edit_range.start() < first_token.text_range().end()
+            || edit_range.end() > last_token.text_range().start()
+        {
+            continue;
+        }
+        if next_significant_token(&last_token).is_some_and(|token| can_continue_stat(&token)) {
+            continue;
+        }
+
+        let stat_range = stat.text_range();
+        let new_stat_range =
+            TextRange::new(stat_range.start(), stat_range.end() + new_len - old_len);
+        let fragment = &text[new_stat_range];
+        let fragment_tree = LuaParser::parse(fragment, config.reborrow());
+        if fragment_tree.has_syntax_errors() {
+            return None;
+        }
+        let Some(new_stat) = get_single_stat(&fragment_tree) else {
+            continue;
+        };
+        if new_stat.kind() != stat.kind()
+            || !is_same_token(new_stat.first_token(), &first_token)
+            || !is_same_token(new_stat.last_token(), &last_token)
+        {
+            continue;
+        }
+
+        let new_root = stat.replace_with(new_stat.green().into_owned());
+        let errors = shift_errors(
+            old_tree.get_errors(),
+            fragment_tree.get_errors(),
+            stat_range,
+            new_stat_range,
+        );
+        return Some(LuaSyntaxTree::new(new_root, errors));
+    }
+
+    None
+}
+
+/// Returns the statement of `tree` if it consists of exactly one statement and nothing else.
+fn get_single_stat(tree: &LuaSyntaxTree) -> Option<LuaSyntaxNode> {
+    let root = tree.get_red_root();
+    let block = root.first_child()?;
+    if block.kind() != LuaSyntaxKind::Block.into() {
+        return None;
+    }
+    let mut children = block.children();
+    let stat = children.next()?;
+    if children.next().is_some() || stat.text_range() != root.text_range() {
+        return None;
+    }
+
+    Some(stat)
+}
+
+fn is_same_token(token: Option<LuaSyntaxToken>, old_token: &LuaSyntaxToken) -> bool {
+    token.is_some_and(|token| token.kind() == old_token.kind() && token.text() == old_token.text())
+}
+
+fn next_significant_token(token: &LuaSyntaxToken) -> Option<LuaSyntaxToken> {
+    let mut next = token.next_token();
+    while let Some(token) = next {
+        let is_trivia = matches!(
+            token.kind().into(),
+            LuaTokenKind::TkWhitespace
+                | LuaTokenKind::TkEndOfLine
+                | LuaTokenKind::TkShortComment
+                | LuaTokenKind::TkLongComment
+        ) || token
+            .parent_ancestors()
+            .any(|node| node.kind() == LuaSyntaxKind::Comment.into());
+        if !is_trivia {
+            return Some(token);
+        }
+        next = token.next_token();
+    }
+
+    None
+}
+
+/// Whether `token` could continue the expression at the end of the previous statement, as in
+/// `x = f` followed by `(g)()`.
+fn can_continue_stat(token: &LuaSyntaxToken) -> bool {
+    matches!(
+        token.kind().into(),
+        LuaTokenKind::TkLeftParen
+            | LuaTokenKind::TkLeftBracket
+            | LuaTokenKind::TkLeftBrace
+            | LuaTokenKind::TkDot
+            | LuaTokenKind::TkColon
+            | LuaTokenKind::TkSafeNavigation
+            | LuaTokenKind::TkString
+            | LuaTokenKind::TkLongString
+    )
+}
+
+/// Keeps the errors of `old_errors` outside the reparsed statement, moved by the edit, and adds
+/// the errors of the reparsed statement.
+fn shift_errors(
+    old_errors: &[LuaParseError],
+    fragment_errors: &[LuaParseError],
+    old_range: TextRange,
+    new_range: TextRange,
+) -> Vec<LuaParseError> {
+    let mut errors = old_errors
+        .iter()
+        .filter(|error| error.range.end() <= old_range.start())
+        .cloned()
+        .collect::<Vec<_>>();
+    errors.extend(fragment_errors.iter().map(|error| {
+        let mut error = error.clone();
+        error.range += new_range.start();
+        error
+    }));
+    errors.extend(
+        old_errors
+            .iter()
+            .filter(|error| error.range.start() >= old_range.end())
+            .map(|error| {
+                let mut error = error.clone();
+                error.range = TextRange::new(
+                    error.range.start() - old_range.end() + new_range.end(),
+                    error.range.end() - old_range.end() + new_range.end(),
+                );
+                error
+            }),
+    );
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use rowan::{TextRange, TextSize};
+
+    use crate::{LuaParser, ParserConfig};
+
+    fn check_reparse(old_text: &str, edit_range: TextRange, insert: &str) -> bool {
+        let old_tree = LuaParser::parse(old_text, ParserConfig::default());
+        let mut text = old_text.to_string();
+        text.replace_range(
+            usize::from(edit_range.start())..usize::from(edit_range.end()),
+            insert,
+        );
+
+        let tree = LuaParser::reparse(&old_tree, edit_range, &text, ParserConfig::default());
+        let full_tree = LuaParser::parse(&text, ParserConfig::default());
+        assert_eq!(
+            format!("{:#?}", tree.get_red_root()),
+            format!("{:#?}", full_tree.get_red_root())
+        );
+        assert_eq!(tree.get_errors(), full_tree.get_errors());
+
+        // whether the green node of the first statement was reused
+        let old_first = old_tree
+            .get_red_root()
+            .first_child()
+            .and_then(|block| block.first_child());
+        let new_first = tree
+            .get_red_root()
+            .first_child()
+            .and_then(|block| block.first_child());
+        old_first.zip(new_first).is_some_and(|(old, new)| {
+            std::ptr::eq(
+                old.green().as_ref() as *const _,
+                new.green().as_ref() as *const _,
+            )
+        })
+    }
+
+    fn range(text: &str, pattern: &str) -> TextRange {
+        let start = text.find(pattern).unwrap();
+        TextRange::at(TextSize::from(start as u32), TextSize::of(pattern))
+    }
+
+    #[test]
+    fn test_reparse_stat() {
+        let code = r#"local a = 1
+local function f(x)
+    local y = x + 1
+    return y
+end
+print(f(a))
+"#;
+        assert!(check_reparse(code, range(code, "x + 1"), "x * 2"));
+        assert!(check_reparse(code, range(code, "return y"), "return y, x"));
+        assert!(check_reparse(code, range(code, "(a)"), "(a, 2)"));
+    }
+
+    #[test]
+    fn test_reparse_fallback() {
+        let code = r#"local a = 1
+local t = {}
+(print)(a)
+local function f(x)
+    return x + 1
+end
+"#;
+        // `local t = f{}` would swallow the next line
+        assert!(!check_reparse(
+            code,
+            TextRange::empty(range(code, "{}").start()),
+            "f"
+        ));
+        // syntax errors are reported by a full parse
+        assert!(!check_reparse(code, range(code, "+ 1"), "+ + 1"));
+        // the edit replaces the end of a statement
+        assert!(!check_reparse(code, range(code, "1\n"), "2 + 3\n"));
+    }
+
+    #[test]
+    fn test_reparse_keeps_doc_errors() {
+        let code = r#"---@param
+local function f(x)
+    return x
+end
+---@type
+local b = 2
+"#;
+        assert!(check_reparse(code, range(code, "return x"), "return x, x"));
+    }
+}
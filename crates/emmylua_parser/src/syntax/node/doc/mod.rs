@@ -369,6 +369,14 @@ impl LuaDocGenericDecl {
     pub fn has_const_modifier(&self) -> bool {
         self.token_by_kind(LuaTokenKind::TkDocConst).is_some()
     }
+
+    pub fn has_in_modifier(&self) -> bool {
+        self.token_by_kind(LuaTokenKind::TkDocIn).is_some()
+    }
+
+    pub fn has_out_modifier(&self) -> bool {
+        self.token_by_kind(LuaTokenKind::TkDocOut).is_some()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
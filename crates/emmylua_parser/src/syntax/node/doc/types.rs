@@ -24,6 +24,7 @@ pub enum LuaDocType {
     Nullable(LuaDocNullableType),
     Generic(LuaDocGenericType),
     StrTpl(LuaDocStrTplType),
+    TemplateLiteral(LuaDocTemplateLiteralType),
     MultiLineUnion(LuaDocMultiLineUnionType),
     Mapped(LuaDocMappedType),
     IndexAccess(LuaDocIndexAccessType),
@@ -46,6 +47,7 @@ impl LuaAstNode for LuaDocType {
             LuaDocType::Nullable(it) => it.syntax(),
             LuaDocType::Generic(it) => it.syntax(),
             LuaDocType::StrTpl(it) => it.syntax(),
+            LuaDocType::TemplateLiteral(it) => it.syntax(),
             LuaDocType::MultiLineUnion(it) => it.syntax(),
             LuaDocType::Mapped(it) => it.syntax(),
             LuaDocType::IndexAccess(it) => it.syntax(),
@@ -72,6 +74,7 @@ impl LuaAstNode for LuaDocType {
                 | LuaSyntaxKind::TypeNullable
                 | LuaSyntaxKind::TypeGeneric
                 | LuaSyntaxKind::TypeStringTemplate
+                | LuaSyntaxKind::TypeTemplateLiteral
                 | LuaSyntaxKind::TypeMultiLineUnion
                 | LuaSyntaxKind::TypeMapped
                 | LuaSyntaxKind::TypeIndexAccess
@@ -113,6 +116,9 @@ impl LuaAstNode for LuaDocType {
             LuaSyntaxKind::TypeStringTemplate => {
                 Some(LuaDocType::StrTpl(LuaDocStrTplType::cast(syntax)?))
             }
+            LuaSyntaxKind::TypeTemplateLiteral => Some(LuaDocType::TemplateLiteral(
+                LuaDocTemplateLiteralType::cast(syntax)?,
+            )),
             LuaSyntaxKind::TypeMultiLineUnion => Some(LuaDocType::MultiLineUnion(
                 LuaDocMultiLineUnionType::cast(syntax)?,
             )),
@@ -768,6 +774,77 @@ impl LuaDocStrTplType {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LuaDocTemplateLiteralType {
+    syntax: LuaSyntaxNode,
+}
+
+impl LuaAstNode for LuaDocTemplateLiteralType {
+    fn syntax(&self) -> &LuaSyntaxNode {
+        &self.syntax
+    }
+
+    fn can_cast(kind: LuaSyntaxKind) -> bool
+    where
+        Self: Sized,
+    {
+        kind == LuaSyntaxKind::TypeTemplateLiteral
+    }
+
+    fn cast(syntax: LuaSyntaxNode) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if Self::can_cast(syntax.kind().into()) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LuaDocTemplateLiteralPart {
+    Text(String),
+    Type(LuaDocType),
+}
+
+impl LuaDocTemplateLiteralType {
+    /// `on${K}Changed` => [Text("on"), Type(K), Text("Changed")], empty text is skipped
+    pub fn get_parts(&self) -> Vec<LuaDocTemplateLiteralPart> {
+        let mut parts = Vec::new();
+        let mut is_first = true;
+        for element in self.syntax().children_with_tokens() {
+            match element {
+                SyntaxElement::Node(node) => {
+                    if let Some(doc_type) = LuaDocType::cast(node) {
+                        parts.push(LuaDocTemplateLiteralPart::Type(doc_type));
+                    }
+                }
+                SyntaxElement::Token(token) => {
+                    if token.kind() != LuaTokenKind::TkDocTemplateText.into() {
+                        continue;
+                    }
+                    let mut text = token.text();
+                    if is_first {
+                        text = text.strip_prefix('`').unwrap_or(text);
+                        is_first = false;
+                    }
+                    text = match text.strip_suffix("${") {
+                        Some(text) => text,
+                        None => text.strip_suffix('`').unwrap_or(text),
+                    };
+                    if !text.is_empty() {
+                        parts.push(LuaDocTemplateLiteralPart::Text(text.to_string()));
+                    }
+                }
+            }
+        }
+
+        parts
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LuaDocMultiLineUnionType {
     syntax: LuaSyntaxNode,
@@ -904,6 +981,15 @@ impl LuaDocMappedType {
     }
 
     pub fn is_optional(&self) -> bool {
+        self.get_optional_modifier() == Some(true)
+    }
+
+    /// `-?` removes optionality from the mapped properties
+    pub fn is_required(&self) -> bool {
+        self.get_optional_modifier() == Some(false)
+    }
+
+    fn get_optional_modifier(&self) -> Option<bool> {
         let mut seen_key = false;
         let mut modifier: Option<bool> = None;
 
@@ -923,7 +1009,7 @@ impl LuaDocMappedType {
                     match kind {
                         LuaTokenKind::TkPlus => modifier = Some(true),
                         LuaTokenKind::TkMinus => modifier = Some(false),
-                        LuaTokenKind::TkDocQuestion => return modifier.unwrap_or(true),
+                        LuaTokenKind::TkDocQuestion => return Some(modifier.unwrap_or(true)),
                         LuaTokenKind::TkColon => break,
                         _ => {}
                     }
@@ -931,7 +1017,7 @@ impl LuaDocMappedType {
             }
         }
 
-        false
+        None
     }
 }
 